@@ -162,6 +162,11 @@ fn generate_lint_files(
         update_mode,
         &gen_declared_lints(internal_lints.iter(), usable_lints.iter()),
     );
+    process_file(
+        "clippy_lints/src/pass_lints.rs",
+        update_mode,
+        &gen_pass_lints(&gather_lint_passes()),
+    );
     process_file(
         "clippy_lints/src/lib.deprecated.rs",
         update_mode,
@@ -672,6 +677,13 @@ impl DeprecatedLint {
     }
 }
 
+/// A lint pass declared with `declare_lint_pass!` or `impl_lint_pass!`, and its lints.
+#[derive(PartialEq, Eq, Debug)]
+struct LintPass {
+    name: String,
+    lints: Vec<String>,
+}
+
 struct RenamedLint {
     old_name: String,
     new_name: String,
@@ -733,6 +745,21 @@ fn gen_declared_lints<'a>(
     output
 }
 
+/// Generates the list of the lints of each lint pass
+#[must_use]
+fn gen_pass_lints(passes: &[LintPass]) -> String {
+    let mut output = GENERATED_FILE_COMMENT.to_string();
+    output.push_str("#[rustfmt::skip]\n");
+    output.push_str("pub(crate) static PASS_LINTS: &[(&str, &[&str])] = &[\n");
+    for pass in passes.iter().filter(|pass| !pass.lints.is_empty()) {
+        let lints = pass.lints.iter().map(|lint| format!("\"{lint}\"")).join(", ");
+        let _ = writeln!(output, "    (\"{}\", &[{lints}]),", pass.name);
+    }
+    output.push_str("];\n");
+
+    output
+}
+
 fn gen_deprecated_lints_test(lints: &[DeprecatedLint]) -> String {
     let mut res: String = GENERATED_FILE_COMMENT.into();
     for lint in lints {
@@ -808,6 +835,19 @@ fn gather_all() -> (Vec<Lint>, Vec<DeprecatedLint>, Vec<RenamedLint>) {
     (lints, deprecated_lints, renamed_lints)
 }
 
+/// Gathers all lint passes declared in `clippy_lints/src`, sorted by name
+fn gather_lint_passes() -> Vec<LintPass> {
+    let mut passes = Vec::with_capacity(500);
+    for (_, file) in clippy_lints_src_files() {
+        let path = file.path();
+        let contents =
+            fs::read_to_string(path).unwrap_or_else(|e| panic!("Cannot read from `{}`: {e}", path.display()));
+        parse_lint_passes(&contents, &mut passes);
+    }
+    passes.sort_by(|a, b| a.name.cmp(&b.name));
+    passes
+}
+
 fn clippy_lints_src_files() -> impl Iterator<Item = (PathBuf, DirEntry)> {
     let root_path = clippy_project_root().join("clippy_lints/src");
     let iter = WalkDir::new(&root_path).into_iter();
@@ -902,6 +942,76 @@ fn parse_contents(contents: &str, module: &str, lints: &mut Vec<Lint>) {
     }
 }
 
+/// Parse a source file looking for `declare_lint_pass` and `impl_lint_pass` macro invocations.
+fn parse_lint_passes(contents: &str, passes: &mut Vec<LintPass>) {
+    let mut tokens = tokenize(contents)
+        .scan(0usize, |offset, t| {
+            let range = *offset..*offset + t.len as usize;
+            *offset = range.end;
+            Some((t.kind, &contents[range]))
+        })
+        .filter(|(kind, _)| {
+            !matches!(
+                kind,
+                TokenKind::Whitespace | TokenKind::LineComment { .. } | TokenKind::BlockComment { .. }
+            )
+        });
+
+    while tokens
+        .by_ref()
+        .any(|(kind, content)| kind == TokenKind::Ident && matches!(content, "declare_lint_pass" | "impl_lint_pass"))
+    {
+        if !matches!(
+            (tokens.next(), tokens.next()),
+            (Some((TokenKind::Bang, _)), Some((TokenKind::OpenParen, _)))
+        ) {
+            continue;
+        }
+        // The arguments, up to the closing parenthesis
+        let mut depth = 0usize;
+        let args: Vec<_> = tokens
+            .by_ref()
+            .take_while(|(kind, _)| {
+                match kind {
+                    TokenKind::OpenParen | TokenKind::OpenBracket | TokenKind::OpenBrace => depth += 1,
+                    TokenKind::CloseParen | TokenKind::CloseBracket | TokenKind::CloseBrace => {
+                        if depth == 0 {
+                            return false;
+                        }
+                        depth -= 1;
+                    },
+                    _ => {},
+                }
+                true
+            })
+            .collect();
+
+        // Skip the attributes of the struct declared by `declare_lint_pass!`
+        let mut args = &args[..];
+        while let [(TokenKind::Pound, _), (TokenKind::OpenBracket, _), rest @ ..] = args {
+            let Some(end) = rest.iter().position(|(kind, _)| *kind == TokenKind::CloseBracket) else {
+                break;
+            };
+            args = &rest[end + 1..];
+        }
+        // `Name => [LINT, path::LINT]`
+        let Some(arrow) = args
+            .windows(2)
+            .position(|w| w[0].0 == TokenKind::Eq && w[1].0 == TokenKind::Gt)
+        else {
+            continue;
+        };
+        passes.push(LintPass {
+            name: args[..arrow].iter().map(|(_, content)| *content).collect(),
+            lints: args[arrow + 2..]
+                .split(|(kind, _)| *kind == TokenKind::Comma)
+                .filter_map(|lint| lint.iter().rev().find(|(kind, _)| *kind == TokenKind::Ident))
+                .map(|(_, lint)| lint.to_lowercase())
+                .collect(),
+        });
+    }
+}
+
 /// Parse a source file looking for `declare_deprecated_lint` macro invocations.
 fn parse_deprecated_contents(contents: &str, lints: &mut Vec<DeprecatedLint>) {
     let mut offset = 0usize;
@@ -1139,6 +1249,42 @@ mod tests {
         assert_eq!(expected, result);
     }
 
+    #[test]
+    fn test_parse_lint_passes() {
+        static CONTENTS: &str = r#"
+            declare_lint_pass!(
+                /// Docs
+                #[derive(Default)]
+                Ptr => [PTR_ARG, CMP_NULL]
+            );
+
+            impl_lint_pass!(Dereferencing<'_> => [
+                EXPLICIT_DEREF_METHODS,
+                crate::borrow_deref_ref::NEEDLESS_BORROW,
+            ]);
+
+            impl_lint_pass!(NoLints => []);
+        "#;
+        let mut result = Vec::new();
+        parse_lint_passes(CONTENTS, &mut result);
+
+        let expected = vec![
+            LintPass {
+                name: "Ptr".into(),
+                lints: vec!["ptr_arg".into(), "cmp_null".into()],
+            },
+            LintPass {
+                name: "Dereferencing<'_>".into(),
+                lints: vec!["explicit_deref_methods".into(), "needless_borrow".into()],
+            },
+            LintPass {
+                name: "NoLints".into(),
+                lints: vec![],
+            },
+        ];
+        assert_eq!(expected, result);
+    }
+
     #[test]
     fn test_parse_deprecated_contents() {
         static DEPRECATED_CONTENTS: &str = r#"
@@ -0,0 +1,49 @@
+use std::env;
+use std::path::Path;
+use std::process::Command;
+
+fn main() {
+    // The version of lint libraries, see `LINT_LIBRARY_VERSION`. It stays empty if either commit is
+    // unknown, the driver then refuses to load any library.
+    let version = match (git(&["rev-parse", "--short", "HEAD"]), rustc_commit_hash()) {
+        (Some(git_hash), Some(rustc_hash)) if !git_hash.is_empty() && !rustc_hash.is_empty() => format!(
+            "{} ({git_hash}, rustc {rustc_hash})",
+            env::var("CARGO_PKG_VERSION").unwrap()
+        ),
+        _ => String::new(),
+    };
+    println!("cargo:rustc-env=LINT_LIBRARY_VERSION={version}");
+    println!("cargo:rerun-if-changed=build.rs");
+    // Update the hash after a commit, even if this crate didn't change. Only the driver and the lint
+    // libraries depend on it, so this doesn't rebuild the lints.
+    let branch = git(&["symbolic-ref", "-q", "HEAD"]);
+    for git_path in ["HEAD"].into_iter().chain(branch.as_deref()) {
+        match git(&["rev-parse", "--git-path", git_path]) {
+            Some(path) if Path::new(&path).exists() => println!("cargo:rerun-if-changed={path}"),
+            _ => {},
+        }
+    }
+}
+
+fn rustc_commit_hash() -> Option<String> {
+    let rustc = env::var("RUSTC").unwrap_or_else(|_| "rustc".into());
+    let output = Command::new(rustc).arg("-vV").output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    String::from_utf8(output.stdout)
+        .ok()?
+        .lines()
+        .find_map(|line| line.strip_prefix("commit-hash: "))
+        .filter(|hash| *hash != "unknown")
+        .map(str::to_string)
+}
+
+fn git(args: &[&str]) -> Option<String> {
+    let output = Command::new("git").args(args).output().ok()?;
+    if output.status.success() {
+        String::from_utf8(output.stdout).ok().map(|s| s.trim().to_string())
+    } else {
+        None
+    }
+}
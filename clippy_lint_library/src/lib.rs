@@ -0,0 +1,77 @@
+//! Support for lint libraries: dynamic libraries with additional lint passes, which
+//! `clippy-driver` loads from the paths in the `lint-libraries` configuration.
+//!
+//! A lint library is a `dylib` crate built with the same nightly and against `clippy_utils` and
+//! this crate from the same commit as the driver loading it. It declares its lints with
+//! `declare_tool_lint!` and registers them with [`declare_lint_library!`].
+//!
+//! The commit is only known to this crate, so that committing doesn't rebuild `clippy_utils` and
+//! the crates depending on it.
+
+#![feature(rustc_private)]
+#![cfg_attr(feature = "deny-warnings", deny(warnings))]
+// warn on lints, that are included in `rust-lang/rust`s bootstrap
+#![warn(rust_2018_idioms, unused_lifetimes)]
+
+// The `rustc_driver` crate seems to be required in order to use the `rust_ast` crate.
+#[allow(unused_extern_crates)]
+extern crate rustc_driver;
+extern crate rustc_lint;
+extern crate rustc_session;
+
+pub use rustc_lint::LintStore;
+pub use rustc_session::Session;
+use std::ffi::c_char;
+
+/// The version a lint library has to be built with: the version of Clippy, and the commits of
+/// Clippy and of the compiler. Libraries built for another version are refused before anything
+/// else in them is called.
+///
+/// Empty if either commit couldn't be found when building this crate, in which case the driver
+/// refuses every library.
+pub const LINT_LIBRARY_VERSION: &str = env!("LINT_LIBRARY_VERSION");
+
+/// [`LINT_LIBRARY_VERSION`], terminated by a NUL byte for the [`VersionFn`].
+#[doc(hidden)]
+pub const LINT_LIBRARY_VERSION_C: &str = concat!(env!("LINT_LIBRARY_VERSION"), "\0");
+
+/// The symbol of the [`VersionFn`] of a library.
+pub const VERSION_SYMBOL: &[u8] = b"clippy_lint_library_version";
+
+/// Returns the [`LINT_LIBRARY_VERSION`] a library was built with, as a C string. It uses the C
+/// ABI, which doesn't depend on the version of the compiler.
+pub type VersionFn = extern "C" fn() -> *const c_char;
+
+/// The symbol of the [`RegisterLintsFn`] of a library.
+pub const REGISTER_SYMBOL: &[u8] = b"clippy_register_lints";
+
+/// Registers the lints and lint passes of a library, like `clippy_lints::register_plugins`.
+pub type RegisterLintsFn = fn(&mut LintStore, &Session);
+
+/// Exports the version and the [`RegisterLintsFn`] of a lint library.
+///
+/// ```ignore
+/// clippy_lint_library::declare_lint_library!(register_lints);
+///
+/// fn register_lints(store: &mut LintStore, _: &Session) {
+///     store.register_lints(&[LEGACY_LOGGING]);
+///     store.register_late_pass(|_| Box::new(LegacyLogging));
+/// }
+/// ```
+#[macro_export]
+macro_rules! declare_lint_library {
+    ($register:path) => {
+        #[no_mangle]
+        pub extern "C" fn clippy_lint_library_version() -> *const ::std::ffi::c_char {
+            $crate::LINT_LIBRARY_VERSION_C.as_ptr().cast()
+        }
+
+        #[no_mangle]
+        pub fn clippy_register_lints(store: &mut $crate::LintStore, sess: &$crate::Session) {
+            // The library has its own copy of `clippy_utils`, so this only affects its lints
+            ::clippy_utils::diagnostics::disable_docs_links();
+            let register: $crate::RegisterLintsFn = $register;
+            register(store, sess);
+        }
+    };
+}
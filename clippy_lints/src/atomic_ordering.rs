@@ -0,0 +1,454 @@
+use clippy_utils::diagnostics::{span_lint_and_help, span_lint_and_sugg, span_lint_hir_and_then};
+use clippy_utils::source::snippet;
+use clippy_utils::visitors::for_each_expr;
+use clippy_utils::{expr_or_init, get_parent_expr, match_def_path, paths};
+use core::ops::ControlFlow;
+use rustc_errors::Applicability;
+use rustc_hir::def::{DefKind, Res};
+use rustc_hir::def_id::{DefId, LocalDefId};
+use rustc_hir::{BinOpKind, BlockCheckMode, Expr, ExprKind, HirId, Item, ItemKind, Node, QPath, UnOp, UnsafeSource};
+use rustc_lint::{LateContext, LateLintPass};
+use rustc_middle::ty::{self, DefIdTree};
+use rustc_session::{declare_tool_lint, impl_lint_pass};
+use rustc_span::symbol::kw;
+use rustc_span::{Span, Symbol};
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for atomic loads with `Ordering::Release` or `Ordering::AcqRel`, atomic stores with
+    /// `Ordering::Acquire` or `Ordering::AcqRel`, and failure orderings of `compare_exchange`,
+    /// `compare_exchange_weak` and `fetch_update` which are `Ordering::Release` or
+    /// `Ordering::AcqRel`, when the ordering comes from a local or a constant.
+    ///
+    /// ### Why is this bad?
+    /// These orderings are invalid for the operation, which panics at runtime. Rustc's
+    /// `invalid_atomic_ordering` lint catches them when written at the call, but not when they
+    /// are passed through a variable.
+    ///
+    /// ### Example
+    /// ```rust,no_run
+    /// # use std::sync::atomic::{AtomicBool, Ordering};
+    /// let flag = AtomicBool::new(false);
+    /// let ordering = Ordering::Release;
+    /// flag.store(true, ordering);
+    /// flag.load(ordering);
+    /// ```
+    /// Use instead:
+    /// ```rust
+    /// # use std::sync::atomic::{AtomicBool, Ordering};
+    /// let flag = AtomicBool::new(false);
+    /// flag.store(true, Ordering::Release);
+    /// flag.load(Ordering::Acquire);
+    /// ```
+    #[clippy::version = "1.68.0"]
+    pub PANICKING_ATOMIC_ORDERING,
+    correctness,
+    "atomic operations with an ordering which makes them panic"
+}
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for `compare_exchange`, `compare_exchange_weak` and `fetch_update` calls whose
+    /// failure ordering is stronger than their success ordering, e.g. a success ordering of
+    /// `Ordering::Relaxed` with a failure ordering of `Ordering::Acquire`.
+    ///
+    /// ### Why is this bad?
+    /// Such orderings panicked before Rust 1.64. They are allowed now, but the load of a
+    /// successful exchange is then weaker than the load of a failed one, which is rarely
+    /// intended: the success ordering was usually meant to include the failure ordering.
+    ///
+    /// ### Example
+    /// ```rust
+    /// # use std::sync::atomic::{AtomicUsize, Ordering};
+    /// let x = AtomicUsize::new(0);
+    /// let _ = x.compare_exchange(0, 1, Ordering::Release, Ordering::Acquire);
+    /// ```
+    /// Use instead:
+    /// ```rust
+    /// # use std::sync::atomic::{AtomicUsize, Ordering};
+    /// let x = AtomicUsize::new(0);
+    /// let _ = x.compare_exchange(0, 1, Ordering::AcqRel, Ordering::Acquire);
+    /// ```
+    #[clippy::version = "1.68.0"]
+    pub COMPARE_EXCHANGE_FAILURE_ORDERING,
+    suspicious,
+    "`compare_exchange` with a failure ordering stronger than the success ordering"
+}
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for `AtomicBool` fields which are stored with `Ordering::Relaxed` in a method and
+    /// loaded with `Ordering::Relaxed` in the condition of an `if` or `while` guarding an
+    /// `unsafe` block, in the same `impl` block.
+    ///
+    /// ### Why is this bad?
+    /// A `Relaxed` store doesn't publish the writes made before it, so the data the flag is
+    /// supposed to guard may not be visible yet to the thread which sees the flag set. The
+    /// store must use `Ordering::Release` and the load `Ordering::Acquire`.
+    ///
+    /// ### Known problems
+    /// This is a heuristic: the flag may not guard what the `unsafe` block accesses.
+    ///
+    /// ### Example
+    /// ```rust
+    /// # use std::cell::UnsafeCell;
+    /// # use std::sync::atomic::{AtomicBool, Ordering};
+    /// struct Slot {
+    ///     ready: AtomicBool,
+    ///     value: UnsafeCell<u32>,
+    /// }
+    ///
+    /// impl Slot {
+    ///     unsafe fn publish(&self, value: u32) {
+    ///         *self.value.get() = value;
+    ///         self.ready.store(true, Ordering::Relaxed);
+    ///     }
+    ///
+    ///     fn read(&self) -> Option<u32> {
+    ///         if self.ready.load(Ordering::Relaxed) {
+    ///             Some(unsafe { *self.value.get() })
+    ///         } else {
+    ///             None
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    /// Use instead:
+    /// ```rust
+    /// # use std::cell::UnsafeCell;
+    /// # use std::sync::atomic::{AtomicBool, Ordering};
+    /// # struct Slot {
+    /// #     ready: AtomicBool,
+    /// #     value: UnsafeCell<u32>,
+    /// # }
+    /// impl Slot {
+    ///     unsafe fn publish(&self, value: u32) {
+    ///         *self.value.get() = value;
+    ///         self.ready.store(true, Ordering::Release);
+    ///     }
+    ///
+    ///     fn read(&self) -> Option<u32> {
+    ///         if self.ready.load(Ordering::Acquire) {
+    ///             Some(unsafe { *self.value.get() })
+    ///         } else {
+    ///             None
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    #[clippy::version = "1.68.0"]
+    pub RELAXED_ATOMIC_FLAG,
+    suspicious,
+    "an atomic flag guarding non-atomic data with `Relaxed` accesses"
+}
+
+const ATOMIC_TYPES: [&str; 12] = [
+    "AtomicBool",
+    "AtomicI8",
+    "AtomicI16",
+    "AtomicI32",
+    "AtomicI64",
+    "AtomicIsize",
+    "AtomicPtr",
+    "AtomicU8",
+    "AtomicU16",
+    "AtomicU32",
+    "AtomicU64",
+    "AtomicUsize",
+];
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Ordering {
+    Relaxed,
+    Release,
+    Acquire,
+    AcqRel,
+    SeqCst,
+}
+
+impl Ordering {
+    const ALL: [Self; 5] = [Self::Relaxed, Self::Release, Self::Acquire, Self::AcqRel, Self::SeqCst];
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Relaxed => "Relaxed",
+            Self::Release => "Release",
+            Self::Acquire => "Acquire",
+            Self::AcqRel => "AcqRel",
+            Self::SeqCst => "SeqCst",
+        }
+    }
+
+    /// The strength of the load performed with this ordering, `Release` loads like `Relaxed`.
+    fn load_strength(self) -> u8 {
+        match self {
+            Self::Relaxed | Self::Release => 0,
+            Self::Acquire | Self::AcqRel => 1,
+            Self::SeqCst => 2,
+        }
+    }
+
+    /// The weakest success ordering at least as strong as `self` whose load is as strong as the one
+    /// of `failure`.
+    fn with_failure(self, failure: Self) -> Self {
+        match (self, failure) {
+            (_, Self::SeqCst) => Self::SeqCst,
+            (Self::Relaxed, Self::Acquire) => Self::Acquire,
+            (Self::Release, Self::Acquire) => Self::AcqRel,
+            _ => self,
+        }
+    }
+}
+
+/// An access to an atomic field of `self` with `Ordering::Relaxed`.
+struct FlagAccess {
+    impl_id: LocalDefId,
+    field: Symbol,
+    hir_id: HirId,
+    span: Span,
+}
+
+#[derive(Default)]
+pub struct AtomicOrdering {
+    relaxed_stores: Vec<FlagAccess>,
+    relaxed_guards: Vec<FlagAccess>,
+}
+
+impl_lint_pass!(AtomicOrdering => [PANICKING_ATOMIC_ORDERING, COMPARE_EXCHANGE_FAILURE_ORDERING, RELAXED_ATOMIC_FLAG]);
+
+impl<'tcx> LateLintPass<'tcx> for AtomicOrdering {
+    fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'tcx>) {
+        let ExprKind::MethodCall(method, receiver, args, _) = expr.kind else {
+            return;
+        };
+        if expr.span.from_expansion() || !is_atomic_method(cx, expr) {
+            return;
+        }
+        match (method.ident.as_str(), args) {
+            ("load", [ordering]) => {
+                check_invalid(cx, ordering, &[Ordering::Release, Ordering::AcqRel], "load");
+                if match_ordering(cx, ordering) == Some(Ordering::Relaxed)
+                    && guards_unsafe_block(cx, expr)
+                    && let Some(access) = flag_access(cx, expr, receiver)
+                {
+                    self.relaxed_guards.push(access);
+                }
+            },
+            ("store", [_, ordering]) => {
+                check_invalid(cx, ordering, &[Ordering::Acquire, Ordering::AcqRel], "store");
+                if match_ordering(cx, ordering) == Some(Ordering::Relaxed)
+                    && let Some(access) = flag_access(cx, expr, receiver)
+                {
+                    self.relaxed_stores.push(access);
+                }
+            },
+            ("compare_exchange" | "compare_exchange_weak", [_, _, success, failure])
+            | ("fetch_update", [success, failure, _]) => {
+                let invalid = &[Ordering::Release, Ordering::AcqRel];
+                if !check_invalid(cx, failure, invalid, "failure") {
+                    check_failure_stronger(cx, method.ident.name, success, failure);
+                }
+            },
+            _ => {},
+        }
+    }
+
+    fn check_item_post(&mut self, cx: &LateContext<'tcx>, item: &'tcx Item<'tcx>) {
+        if !matches!(item.kind, ItemKind::Impl(_)) {
+            return;
+        }
+        let impl_id = item.owner_id.def_id;
+        for guard in self.relaxed_guards.iter().filter(|guard| guard.impl_id == impl_id) {
+            let Some(store) = self
+                .relaxed_stores
+                .iter()
+                .find(|store| store.impl_id == impl_id && store.field == guard.field)
+            else {
+                continue;
+            };
+            span_lint_hir_and_then(
+                cx,
+                RELAXED_ATOMIC_FLAG,
+                guard.hir_id,
+                guard.span,
+                &format!(
+                    "`self.{}` is loaded with `Relaxed` to guard an `unsafe` block",
+                    guard.field
+                ),
+                |diag| {
+                    diag.span_note(store.span, "the flag is set with `Relaxed` here");
+                    diag.help(
+                        "use `Ordering::Release` for the store and `Ordering::Acquire` for the load, so that \
+                        the writes made before setting the flag are visible once it's seen set",
+                    );
+                },
+            );
+        }
+        self.relaxed_guards.retain(|guard| guard.impl_id != impl_id);
+        self.relaxed_stores.retain(|store| store.impl_id != impl_id);
+    }
+}
+
+/// Returns the access made by `expr` if `receiver` is an `AtomicBool` field of `self` and `expr`
+/// is in a method.
+fn flag_access(cx: &LateContext<'_>, expr: &Expr<'_>, receiver: &Expr<'_>) -> Option<FlagAccess> {
+    if let ExprKind::Field(base, field) = receiver.kind
+        && let ExprKind::Path(QPath::Resolved(None, path)) = base.kind
+        && path.segments.len() == 1
+        && path.segments[0].ident.name == kw::SelfLower
+        && let ty::Adt(adt, _) = cx.typeck_results().expr_ty(receiver).kind()
+        && match_def_path(cx, adt.did(), &paths::ATOMIC_BOOL)
+        && let Some(impl_id) = cx.tcx.impl_of_method(cx.tcx.hir().enclosing_body_owner(expr.hir_id).to_def_id())
+        && let Some(impl_id) = impl_id.as_local()
+    {
+        Some(FlagAccess {
+            impl_id,
+            field: field.name,
+            hir_id: expr.hir_id,
+            span: expr.span,
+        })
+    } else {
+        None
+    }
+}
+
+/// Checks if `expr` calls a method of one of the atomic types of the standard library.
+fn is_atomic_method(cx: &LateContext<'_>, expr: &Expr<'_>) -> bool {
+    if let Some(method_id) = cx.typeck_results().type_dependent_def_id(expr.hir_id)
+        && let Some(impl_id) = cx.tcx.impl_of_method(method_id)
+        && cx.tcx.trait_id_of_impl(impl_id).is_none()
+        && let ty::Adt(adt, _) = cx.tcx.type_of(impl_id).kind()
+    {
+        ATOMIC_TYPES
+            .iter()
+            .any(|ty| match_def_path(cx, adt.did(), &["core", "sync", "atomic", ty]))
+    } else {
+        false
+    }
+}
+
+/// Returns the ordering `expr` evaluates to, looking through locals and constants.
+fn match_ordering(cx: &LateContext<'_>, expr: &Expr<'_>) -> Option<Ordering> {
+    let expr = expr_or_init(cx, expr);
+    let ExprKind::Path(ref qpath) = expr.kind else {
+        return None;
+    };
+    match cx.qpath_res(qpath, expr.hir_id) {
+        Res::Def(DefKind::Ctor(..), id) => ordering_of_ctor(cx, id),
+        Res::Def(DefKind::Const, id) => {
+            let Some(Node::Item(Item {
+                kind: ItemKind::Const(_, body_id),
+                ..
+            })) = cx.tcx.hir().get_if_local(id)
+            else {
+                return None;
+            };
+            let value = cx.tcx.hir().body(*body_id).value;
+            if let ExprKind::Path(ref qpath) = value.kind
+                && let Res::Def(DefKind::Ctor(..), id) = cx.tcx.typeck_body(*body_id).qpath_res(qpath, value.hir_id)
+            {
+                ordering_of_ctor(cx, id)
+            } else {
+                None
+            }
+        },
+        _ => None,
+    }
+}
+
+fn ordering_of_ctor(cx: &LateContext<'_>, ctor_id: DefId) -> Option<Ordering> {
+    let variant_id = cx.tcx.parent(ctor_id);
+    let name = cx.tcx.item_name(variant_id);
+    if !match_def_path(cx, cx.tcx.parent(variant_id), &paths::ATOMIC_ORDERING) {
+        return None;
+    }
+    Ordering::ALL
+        .into_iter()
+        .find(|ordering| name.as_str() == ordering.name())
+}
+
+/// Checks if `ordering` is a local or a constant evaluating to one of the `invalid` orderings,
+/// which rustc doesn't lint, and lints it.
+fn check_invalid(cx: &LateContext<'_>, ordering: &Expr<'_>, invalid: &[Ordering], operation: &str) -> bool {
+    let is_variant = matches!(
+        ordering.kind,
+        ExprKind::Path(ref qpath) if matches!(cx.qpath_res(qpath, ordering.hir_id), Res::Def(DefKind::Ctor(..), _))
+    );
+    match match_ordering(cx, ordering) {
+        Some(found) if invalid.contains(&found) => {
+            if !is_variant {
+                span_lint_and_help(
+                    cx,
+                    PANICKING_ATOMIC_ORDERING,
+                    ordering.span,
+                    &format!(
+                        "this is `Ordering::{}`, which panics as a {operation} ordering",
+                        found.name()
+                    ),
+                    None,
+                    &format!(
+                        "use one of {}",
+                        Ordering::ALL
+                            .into_iter()
+                            .filter(|ordering| !invalid.contains(ordering))
+                            .map(|ordering| format!("`Ordering::{}`", ordering.name()))
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    ),
+                );
+            }
+            true
+        },
+        _ => false,
+    }
+}
+
+fn check_failure_stronger(cx: &LateContext<'_>, method: Symbol, success: &Expr<'_>, failure: &Expr<'_>) {
+    if let Some(success_ordering) = match_ordering(cx, success)
+        && let Some(failure_ordering) = match_ordering(cx, failure)
+        && failure_ordering.load_strength() > success_ordering.load_strength()
+    {
+        let suggested = success_ordering.with_failure(failure_ordering);
+        span_lint_and_sugg(
+            cx,
+            COMPARE_EXCHANGE_FAILURE_ORDERING,
+            success.span,
+            &format!(
+                "the failure ordering of this `{method}` is stronger than its success ordering `{}`",
+                snippet(cx, success.span, "..")
+            ),
+            "use a success ordering including the failure ordering",
+            format!("Ordering::{}", suggested.name()),
+            Applicability::MaybeIncorrect,
+        );
+    }
+}
+
+/// Checks if `expr` is the condition, or a part of the condition, of an `if` or `while` whose
+/// body contains an `unsafe` block.
+fn guards_unsafe_block<'tcx>(cx: &LateContext<'tcx>, mut expr: &'tcx Expr<'tcx>) -> bool {
+    while let Some(parent) = get_parent_expr(cx, expr) {
+        match parent.kind {
+            ExprKind::Unary(UnOp::Not, _) | ExprKind::DropTemps(_) => expr = parent,
+            ExprKind::Binary(op, ..)
+                if matches!(op.node, BinOpKind::And | BinOpKind::Or | BinOpKind::Eq | BinOpKind::Ne) =>
+            {
+                expr = parent;
+            },
+            // `while` loops are desugared to an `if` in a `loop`
+            ExprKind::If(cond, then, _) => return cond.hir_id == expr.hir_id && contains_unsafe_block(then),
+            _ => return false,
+        }
+    }
+    false
+}
+
+fn contains_unsafe_block(expr: &Expr<'_>) -> bool {
+    for_each_expr(expr, |e| match e.kind {
+        ExprKind::Block(block, _) if block.rules == BlockCheckMode::UnsafeBlock(UnsafeSource::UserProvided) => {
+            ControlFlow::Break(())
+        },
+        _ => ControlFlow::Continue(()),
+    })
+    .is_some()
+}
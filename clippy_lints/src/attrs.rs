@@ -1,14 +1,20 @@
 //! checks for attributes
 
-use clippy_utils::diagnostics::{span_lint, span_lint_and_help, span_lint_and_sugg, span_lint_and_then};
+use clippy_utils::diagnostics::{
+    is_allow_used, span_lint, span_lint_and_help, span_lint_and_sugg, span_lint_and_then, span_lint_hir_and_then,
+};
 use clippy_utils::macros::{is_panic, macro_backtrace};
 use clippy_utils::msrvs::{self, Msrv};
-use clippy_utils::source::{first_line_of_span, is_present_in_source, snippet_opt, without_block_comments};
+use clippy_utils::source::{
+    first_line_of_span, is_present_in_source, reattach_comments, snippet_opt, snippet_with_applicability,
+    without_block_comments,
+};
 use if_chain::if_chain;
 use rustc_ast::{AttrKind, AttrStyle, Attribute, LitKind, MetaItemKind, MetaItemLit, NestedMetaItem};
-use rustc_errors::Applicability;
+use rustc_errors::{Applicability, Diagnostic};
 use rustc_hir::{
-    Block, Expr, ExprKind, ImplItem, ImplItemKind, Item, ItemKind, StmtKind, TraitFn, TraitItem, TraitItemKind,
+    Block, Expr, ExprKind, HirId, ImplItem, ImplItemKind, Item, ItemKind, MaybeOwner, OwnerId, StmtKind, TraitFn,
+    TraitItem, TraitItemKind,
 };
 use rustc_lint::{EarlyContext, EarlyLintPass, LateContext, LateLintPass, Level, LintContext};
 use rustc_middle::lint::in_external_macro;
@@ -16,7 +22,7 @@ use rustc_middle::ty;
 use rustc_session::{declare_lint_pass, declare_tool_lint, impl_lint_pass};
 use rustc_span::source_map::Span;
 use rustc_span::symbol::Symbol;
-use rustc_span::{sym, DUMMY_SP};
+use rustc_span::{sym, BytePos, DUMMY_SP};
 use semver::Version;
 
 static UNIX_SYSTEMS: &[&str] = &[
@@ -178,7 +184,8 @@ declare_clippy_lint! {
 
 declare_clippy_lint! {
     /// ### What it does
-    /// Checks for `warn`/`deny`/`forbid` attributes targeting the whole clippy::restriction category.
+    /// Checks for `warn`/`deny`/`forbid` attributes targeting the whole clippy::restriction category,
+    /// directly or through a lint group from the `groups` configuration which includes it.
     ///
     /// ### Why is this bad?
     /// Restriction lints sometimes are in contrast with other lints or even go against idiomatic rust.
@@ -292,7 +299,88 @@ declare_clippy_lint! {
     "ensures that all `allow` and `expect` attributes have a reason"
 }
 
-declare_lint_pass!(Attributes => [
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for `#[allow(clippy::...)]` attributes which didn't allow any lint that Clippy
+    /// would have emitted.
+    ///
+    /// ### Why is this bad?
+    /// These attributes are left behind when the code they were written for changes, or when
+    /// the false positive they worked around gets fixed. They then silently hide the lint for
+    /// any new code under them.
+    ///
+    /// ### Known problems
+    /// Only the current compilation is checked. Attributes on code which is only compiled with
+    /// other `cfg`s, e.g. only with `--tests`, are reported as unused.
+    ///
+    /// Some lints don't look for the code they lint where they are allowed. An `allow` attribute
+    /// for them counts as used as soon as the lint checks whether it's allowed, even if it
+    /// wouldn't be emitted without the attribute. As the attribute may still be needed when it's
+    /// reported, e.g. on code which is only linted on other targets, removing it isn't
+    /// automatically applicable.
+    ///
+    /// ### Example
+    /// ```rust
+    /// #[allow(clippy::needless_return)]
+    /// fn one() -> i32 {
+    ///     1
+    /// }
+    /// ```
+    ///
+    /// Use instead:
+    /// ```rust
+    /// fn one() -> i32 {
+    ///     1
+    /// }
+    /// ```
+    #[clippy::version = "1.68.0"]
+    pub UNUSED_CLIPPY_ALLOW,
+    restriction,
+    "`allow` attributes for Clippy lints which are never emitted under them"
+}
+
+pub struct Attributes {
+    /// The lint groups from the `groups` configuration which include `clippy::restriction`.
+    blanket_restriction_groups: Vec<String>,
+}
+
+impl Attributes {
+    #[must_use]
+    pub fn new(blanket_restriction_groups: Vec<String>) -> Self {
+        Self {
+            blanket_restriction_groups,
+        }
+    }
+
+    /// Checks if enabling the lint group `name` enables all restriction lints.
+    fn is_blanket_restriction(&self, name: &str) -> bool {
+        name == "restriction" || self.blanket_restriction_groups.iter().any(|group| group == name)
+    }
+
+    fn check_clippy_lint_names(&self, cx: &LateContext<'_>, name: Symbol, items: &[NestedMetaItem]) {
+        if name == sym::allow {
+            return;
+        }
+        for lint in items {
+            if let Some(lint_name) = extract_clippy_lint(lint)
+                && self.is_blanket_restriction(lint_name.as_str())
+            {
+                span_lint_and_then(
+                    cx,
+                    BLANKET_CLIPPY_RESTRICTION_LINTS,
+                    lint.span(),
+                    "`clippy::restriction` is not meant to be enabled as a group",
+                    |diag| {
+                        note_user_group(diag, lint_name.as_str());
+                        diag.help("enable the restriction lints you need individually");
+                    },
+                );
+            }
+        }
+    }
+}
+
+impl_lint_pass!(Attributes => [
     ALLOW_ATTRIBUTES_WITHOUT_REASON,
     INLINE_ALWAYS,
     DEPRECATED_SEMVER,
@@ -303,17 +391,18 @@ declare_lint_pass!(Attributes => [
 impl<'tcx> LateLintPass<'tcx> for Attributes {
     fn check_crate(&mut self, cx: &LateContext<'tcx>) {
         for (name, level) in &cx.sess().opts.lint_opts {
-            if name == "clippy::restriction" && *level > Level::Allow {
+            if let Some(group) = name.strip_prefix("clippy::")
+                && self.is_blanket_restriction(group)
+                && *level > Level::Allow
+            {
                 span_lint_and_then(
                     cx,
                     BLANKET_CLIPPY_RESTRICTION_LINTS,
                     DUMMY_SP,
                     "`clippy::restriction` is not meant to be enabled as a group",
                     |diag| {
-                        diag.note(format!(
-                            "because of the command line `--{} clippy::restriction`",
-                            level.as_str()
-                        ));
+                        diag.note(format!("because of the command line `--{} {name}`", level.as_str()));
+                        note_user_group(diag, group);
                         diag.help("enable the restriction lints you need individually");
                     },
                 );
@@ -325,7 +414,7 @@ impl<'tcx> LateLintPass<'tcx> for Attributes {
         if let Some(items) = &attr.meta_item_list() {
             if let Some(ident) = attr.ident() {
                 if is_lint_level(ident.name) {
-                    check_clippy_lint_names(cx, ident.name, items);
+                    self.check_clippy_lint_names(cx, ident.name, items);
                 }
                 if matches!(ident.name, sym::allow | sym::expect) {
                     check_lint_reason(cx, ident.name, items, attr);
@@ -453,20 +542,11 @@ fn extract_clippy_lint(lint: &NestedMetaItem) -> Option<Symbol> {
     None
 }
 
-fn check_clippy_lint_names(cx: &LateContext<'_>, name: Symbol, items: &[NestedMetaItem]) {
-    for lint in items {
-        if let Some(lint_name) = extract_clippy_lint(lint) {
-            if lint_name.as_str() == "restriction" && name != sym::allow {
-                span_lint_and_help(
-                    cx,
-                    BLANKET_CLIPPY_RESTRICTION_LINTS,
-                    lint.span(),
-                    "`clippy::restriction` is not meant to be enabled as a group",
-                    None,
-                    "enable the restriction lints you need individually",
-                );
-            }
-        }
+fn note_user_group(diag: &mut Diagnostic, group: &str) {
+    if group != "restriction" {
+        diag.note(format!(
+            "the lint group `clippy::{group}` from `clippy.toml` includes `clippy::restriction`"
+        ));
     }
 }
 
@@ -596,6 +676,144 @@ fn is_word(nmi: &NestedMetaItem, expected: Symbol) -> bool {
     }
 }
 
+/// Lints which are not checked in every run, so an `allow` for them can't be known to be unused.
+const NOT_ALWAYS_CHECKED: &[&str] = &["unused_pub_items"];
+
+struct AllowAttr {
+    /// The node the attribute is on.
+    hir_id: HirId,
+    span: Span,
+    /// The spans of all lints in the attribute, including those of other tools.
+    lints: Vec<Span>,
+    reason: Option<Span>,
+    /// The Clippy lints in the attribute which can be checked.
+    clippy_lints: Vec<(Span, Symbol)>,
+}
+
+impl AllowAttr {
+    fn new(hir_id: HirId, attr: &Attribute) -> Option<Self> {
+        if !attr.has_name(sym::allow) || attr.span.from_expansion() {
+            return None;
+        }
+        let mut allow = Self {
+            hir_id,
+            span: attr.span,
+            lints: Vec::new(),
+            reason: None,
+            clippy_lints: Vec::new(),
+        };
+        for item in attr.meta_item_list()? {
+            if item.has_name(sym::reason) {
+                allow.reason = Some(item.span());
+                continue;
+            }
+            allow.lints.push(item.span());
+            if let Some(name) = extract_clippy_lint(&item)
+                && name.as_str() != "unused_clippy_allow"
+                && !NOT_ALWAYS_CHECKED.contains(&name.as_str())
+                && crate::is_lint(name.as_str())
+            {
+                allow.clippy_lints.push((item.span(), name));
+            }
+        }
+        (!allow.clippy_lints.is_empty()).then_some(allow)
+    }
+}
+
+// Reports the lints in `allow` attributes whose level was never looked up while emitting or
+// checking for that lint. This pass has to be registered last, so every other lint ran before.
+declare_lint_pass!(UnusedClippyAllow => [UNUSED_CLIPPY_ALLOW]);
+
+impl<'tcx> LateLintPass<'tcx> for UnusedClippyAllow {
+    fn check_crate_post(&mut self, cx: &LateContext<'tcx>) {
+        for (def_id, owner) in cx.tcx.hir().krate().owners.iter_enumerated() {
+            let MaybeOwner::Owner(owner) = owner else {
+                continue;
+            };
+            for &(local_id, attrs) in owner.attrs.map.iter() {
+                let hir_id = HirId {
+                    owner: OwnerId { def_id },
+                    local_id,
+                };
+                for allow in attrs.iter().filter_map(|attr| AllowAttr::new(hir_id, attr)) {
+                    check_allow(cx, &allow);
+                }
+            }
+        }
+    }
+}
+
+fn check_allow(cx: &LateContext<'_>, allow: &AllowAttr) {
+    let unused: Vec<_> = allow
+        .clippy_lints
+        .iter()
+        .filter(|(span, _)| !is_allow_used(*span))
+        .collect();
+    if unused.is_empty() {
+        return;
+    }
+    let names = unused
+        .iter()
+        .map(|(_, name)| format!("`clippy::{name}`"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let msg = if unused.len() == 1 {
+        format!("this `allow` for {names} is unused")
+    } else {
+        format!("these `allow`s for {names} are unused")
+    };
+    let spans: Vec<_> = unused.iter().map(|(span, _)| *span).collect();
+    span_lint_hir_and_then(cx, UNUSED_CLIPPY_ALLOW, allow.hir_id, spans.clone(), &msg, |diag| {
+        let kept: Vec<_> = allow
+            .lints
+            .iter()
+            .filter(|span| !spans.contains(span))
+            .copied()
+            .collect();
+        if kept.is_empty() {
+            // The attribute may come from a `cfg_attr`, which would have to be removed instead.
+            if snippet_opt(cx, allow.span).map_or(false, |snip| snip.starts_with('#')) {
+                diag.span_suggestion(
+                    removal_span(cx, allow.span),
+                    "remove the attribute",
+                    "",
+                    Applicability::MaybeIncorrect,
+                );
+            } else {
+                diag.help("remove the attribute");
+            }
+            return;
+        }
+
+        let list = allow.lints[0].to(allow.reason.unwrap_or(allow.lints[allow.lints.len() - 1]));
+        let kept: Vec<_> = kept.into_iter().chain(allow.reason).collect();
+        let mut applicability = Applicability::MaybeIncorrect;
+        let sugg = kept
+            .iter()
+            .map(|&span| snippet_with_applicability(cx, span, "..", &mut applicability))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let sugg = reattach_comments(cx, list, &kept, sugg, &mut applicability);
+        diag.span_suggestion(list, "remove the unused lints", sugg, applicability);
+    });
+}
+
+/// The span to remove `attr`, including its line if nothing else is on it.
+fn removal_span(cx: &LateContext<'_>, attr: Span) -> Span {
+    let line = cx.sess().source_map().span_extend_to_line(attr);
+    match (snippet_opt(cx, line), snippet_opt(cx, attr)) {
+        (Some(line_snip), Some(attr_snip)) if line_snip.trim() == attr_snip => {
+            let with_newline = line.with_hi(line.hi() + BytePos(1));
+            if snippet_opt(cx, with_newline).map_or(false, |snip| snip.ends_with('\n')) {
+                with_newline
+            } else {
+                line
+            }
+        },
+        _ => attr,
+    }
+}
+
 pub struct EarlyAttributes {
     pub msrv: Msrv,
 }
@@ -0,0 +1,257 @@
+use clippy_utils::diagnostics::span_lint_hir_and_then;
+use clippy_utils::visitors::for_each_expr;
+use clippy_utils::{def_path_def_ids, is_lint_allowed};
+use core::ops::ControlFlow;
+use rustc_hir::def::{DefKind, Res};
+use rustc_hir::def_id::{DefId, DefIdMap};
+use rustc_hir::{AsyncGeneratorKind, Body, Expr, ExprKind, GeneratorKind};
+use rustc_lint::{LateContext, LateLintPass, LintContext};
+use rustc_middle::lint::in_external_macro;
+use rustc_middle::ty::{DefIdTree, TypeckResults};
+use rustc_session::{declare_tool_lint, impl_lint_pass};
+use rustc_span::Span;
+
+use crate::utils::conf;
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for calls of blocking functions, like `std::thread::sleep`, `std::fs::read` or
+    /// `std::sync::Mutex::lock`, inside of `async fn`s and `async` blocks. Calls of synchronous
+    /// functions of the crate that call a blocking function themselves are linted too.
+    ///
+    /// More blocking functions can be added with the `blocking-paths` configuration. A path can
+    /// also name a module or a type, in which case all of its functions are considered blocking.
+    ///
+    /// ### Why is this bad?
+    /// An executor runs many tasks on few threads. A blocking call stalls the thread it runs on,
+    /// and with it all the other tasks scheduled on that thread.
+    ///
+    /// ### Known problems
+    /// Locking a `std::sync::Mutex` for a short critical section is usually fine, and often faster
+    /// than an async mutex.
+    ///
+    /// ### Example
+    /// ```rust
+    /// async fn load() -> std::io::Result<String> {
+    ///     std::fs::read_to_string("config.toml")
+    /// }
+    /// ```
+    ///
+    /// Use instead:
+    /// ```rust,ignore
+    /// async fn load() -> std::io::Result<String> {
+    ///     tokio::fs::read_to_string("config.toml").await
+    /// }
+    /// ```
+    #[clippy::version = "1.68.0"]
+    pub BLOCKING_IN_ASYNC,
+    pedantic,
+    "calling a blocking function inside of an async context"
+}
+
+/// The built-in blocking functions, with a hint on what to use instead.
+const BLOCKING_PATHS: &[(&str, &str)] = &[
+    ("std::thread::sleep", SLEEP),
+    ("std::thread::JoinHandle::join", JOIN),
+    ("std::fs::canonicalize", FS),
+    ("std::fs::copy", FS),
+    ("std::fs::create_dir", FS),
+    ("std::fs::create_dir_all", FS),
+    ("std::fs::hard_link", FS),
+    ("std::fs::metadata", FS),
+    ("std::fs::read", FS),
+    ("std::fs::read_dir", FS),
+    ("std::fs::read_link", FS),
+    ("std::fs::read_to_string", FS),
+    ("std::fs::remove_dir", FS),
+    ("std::fs::remove_dir_all", FS),
+    ("std::fs::remove_file", FS),
+    ("std::fs::rename", FS),
+    ("std::fs::set_permissions", FS),
+    ("std::fs::symlink_metadata", FS),
+    ("std::fs::write", FS),
+    ("std::fs::File::create", FS),
+    ("std::fs::File::open", FS),
+    ("std::fs::OpenOptions::open", FS),
+    ("std::net::TcpListener::accept", NET),
+    ("std::net::TcpListener::bind", NET),
+    ("std::net::TcpStream::connect", NET),
+    ("std::net::TcpStream::connect_timeout", NET),
+    ("std::net::UdpSocket::bind", NET),
+    ("std::net::ToSocketAddrs::to_socket_addrs", NET),
+    ("std::process::Child::wait", PROCESS),
+    ("std::process::Child::wait_with_output", PROCESS),
+    ("std::process::Command::output", PROCESS),
+    ("std::process::Command::status", PROCESS),
+    ("std::sync::Condvar::wait", SYNC),
+    ("std::sync::Mutex::lock", SYNC),
+    ("std::sync::RwLock::read", SYNC),
+    ("std::sync::RwLock::write", SYNC),
+    ("std::sync::Barrier::wait", SYNC),
+    ("std::sync::mpsc::Receiver::recv", CHANNEL),
+    ("std::sync::mpsc::Receiver::recv_timeout", CHANNEL),
+    ("std::sync::mpsc::SyncSender::send", CHANNEL),
+    ("reqwest::blocking", REQWEST),
+];
+
+const SLEEP: &str = "use the sleep function of your async runtime instead, e.g. `tokio::time::sleep`";
+const JOIN: &str = "run the work with `spawn_blocking` instead, and `.await` its handle";
+const FS: &str = "use the async file system API of your runtime instead, e.g. `tokio::fs`, or move the call \
+                  into `spawn_blocking`";
+const NET: &str = "use the async networking API of your runtime instead, e.g. `tokio::net`";
+const PROCESS: &str = "use the async process API of your runtime instead, e.g. `tokio::process`";
+const SYNC: &str = "use an async synchronization primitive instead, e.g. `tokio::sync::Mutex`, unless the lock is \
+                    only held briefly";
+const CHANNEL: &str = "use an async channel instead, e.g. `tokio::sync::mpsc`";
+const REQWEST: &str = "use the async API of `reqwest` instead";
+const DEFAULT_HINT: &str = "move the call into `spawn_blocking`, or use an async equivalent";
+
+pub struct BlockingInAsync {
+    conf_blocking: Vec<conf::DisallowedPath>,
+    /// Maps the blocking functions, modules and types to their index in `BLOCKING_PATHS`, followed
+    /// by the configured paths.
+    blocking: DefIdMap<usize>,
+}
+
+impl BlockingInAsync {
+    pub fn new(conf_blocking: Vec<conf::DisallowedPath>) -> Self {
+        Self {
+            conf_blocking,
+            blocking: DefIdMap::default(),
+        }
+    }
+
+    fn hint(&self, index: usize) -> String {
+        match BLOCKING_PATHS.get(index) {
+            Some((_, hint)) => (*hint).to_owned(),
+            None => self.conf_blocking[index - BLOCKING_PATHS.len()]
+                .reason()
+                .unwrap_or_else(|| DEFAULT_HINT.to_owned()),
+        }
+    }
+
+    /// Checks if `def_id` is a blocking function, or a function of a blocking module or type.
+    fn blocking_index(&self, cx: &LateContext<'_>, def_id: DefId) -> Option<usize> {
+        let mut current = Some(def_id);
+        while let Some(def_id) = current {
+            if let Some(&index) = self.blocking.get(&def_id) {
+                return Some(index);
+            }
+            if cx.tcx.def_kind(def_id) == DefKind::Impl
+                && let Some(adt) = cx.tcx.type_of(def_id).ty_adt_def()
+                && let Some(&index) = self.blocking.get(&adt.did())
+            {
+                return Some(index);
+            }
+            current = cx.tcx.opt_parent(def_id);
+        }
+        None
+    }
+
+    /// Finds the first blocking call in the body of a synchronous local function.
+    fn blocking_call_in_fn(&self, cx: &LateContext<'_>, def_id: DefId) -> Option<(DefId, usize, Span)> {
+        let local_id = def_id.as_local()?;
+        if !matches!(cx.tcx.def_kind(def_id), DefKind::Fn | DefKind::AssocFn) || cx.tcx.asyncness(def_id).is_async() {
+            return None;
+        }
+        let body_id = cx.tcx.hir().maybe_body_owned_by(local_id)?;
+        let typeck = cx.tcx.typeck_body(body_id);
+        for_each_expr(cx.tcx.hir().body(body_id).value, |e| {
+            if let Some(callee) = callee(typeck, e)
+                && let Some(index) = self.blocking_index(cx, callee)
+            {
+                ControlFlow::Break((callee, index, e.span))
+            } else {
+                ControlFlow::Continue(())
+            }
+        })
+    }
+}
+
+impl_lint_pass!(BlockingInAsync => [BLOCKING_IN_ASYNC]);
+
+impl<'tcx> LateLintPass<'tcx> for BlockingInAsync {
+    fn check_crate(&mut self, cx: &LateContext<'tcx>) {
+        let paths = BLOCKING_PATHS
+            .iter()
+            .map(|(path, _)| *path)
+            .chain(self.conf_blocking.iter().map(conf::DisallowedPath::path));
+        for (index, path) in paths.enumerate() {
+            let segs: Vec<_> = path.split("::").collect();
+            for id in def_path_def_ids(cx, &segs) {
+                self.blocking.entry(id).or_insert(index);
+            }
+        }
+    }
+
+    fn check_body(&mut self, cx: &LateContext<'tcx>, body: &'tcx Body<'tcx>) {
+        let context = match body.generator_kind {
+            Some(GeneratorKind::Async(AsyncGeneratorKind::Fn)) => "`async fn`",
+            Some(GeneratorKind::Async(AsyncGeneratorKind::Block)) => "`async` block",
+            Some(GeneratorKind::Async(AsyncGeneratorKind::Closure)) => "`async` closure",
+            _ => return,
+        };
+        if self.blocking.is_empty() || is_lint_allowed(cx, BLOCKING_IN_ASYNC, body.value.hir_id) {
+            return;
+        }
+
+        // Closures and nested `async` blocks aren't visited, code in closures often runs in
+        // `spawn_blocking`.
+        let typeck = cx.typeck_results();
+        for_each_expr(body.value, |e| -> ControlFlow<!> {
+            let Some(callee) = callee(typeck, e).filter(|_| !in_external_macro(cx.sess(), e.span)) else {
+                return ControlFlow::Continue(());
+            };
+            if let Some(index) = self.blocking_index(cx, callee) {
+                span_lint_hir_and_then(
+                    cx,
+                    BLOCKING_IN_ASYNC,
+                    e.hir_id,
+                    e.span,
+                    &format!(
+                        "call to the blocking function `{}` inside of an {context}",
+                        cx.tcx.def_path_str(callee)
+                    ),
+                    |diag| {
+                        diag.note(self.hint(index));
+                    },
+                );
+            } else if let Some((blocking, index, span)) = self.blocking_call_in_fn(cx, callee) {
+                let name = cx.tcx.def_path_str(callee);
+                span_lint_hir_and_then(
+                    cx,
+                    BLOCKING_IN_ASYNC,
+                    e.hir_id,
+                    e.span,
+                    &format!("call to `{name}`, which blocks, inside of an {context}"),
+                    |diag| {
+                        diag.span_note(
+                            span,
+                            &format!(
+                                "`{name}` calls the blocking function `{}` here",
+                                cx.tcx.def_path_str(blocking)
+                            ),
+                        );
+                        diag.note(self.hint(index));
+                    },
+                );
+            }
+            ControlFlow::Continue(())
+        });
+    }
+}
+
+/// Gets the function or method called by the expression.
+fn callee(typeck: &TypeckResults<'_>, e: &Expr<'_>) -> Option<DefId> {
+    match e.kind {
+        ExprKind::Call(callee, _) => match callee.kind {
+            ExprKind::Path(ref qpath) => match typeck.qpath_res(qpath, callee.hir_id) {
+                Res::Def(DefKind::Fn | DefKind::AssocFn, def_id) => Some(def_id),
+                _ => None,
+            },
+            _ => None,
+        },
+        ExprKind::MethodCall(..) => typeck.type_dependent_def_id(e.hir_id),
+        _ => None,
+    }
+}
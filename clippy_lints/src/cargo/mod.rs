@@ -1,6 +1,7 @@
 mod common_metadata;
 mod feature_name;
 mod multiple_crate_versions;
+mod unused_pub_items;
 mod wildcard_dependencies;
 
 use cargo_metadata::MetadataCommand;
@@ -11,6 +12,8 @@ use rustc_lint::{LateContext, LateLintPass, Lint};
 use rustc_session::{declare_tool_lint, impl_lint_pass};
 use rustc_span::DUMMY_SP;
 
+pub use unused_pub_items::{UnusedPubItems, UnusedPubPhase};
+
 declare_clippy_lint! {
     /// ### What it does
     /// Checks to see if all common metadata is defined in
@@ -162,6 +165,40 @@ declare_clippy_lint! {
     "wildcard dependencies being used"
 }
 
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for exported items of a workspace library that no other crate of the workspace
+    /// uses. This lint only runs with `cargo clippy --unused-pub`, which checks the workspace
+    /// twice: once to record which items each crate uses, and once to lint.
+    ///
+    /// Crates whose API is used outside of the workspace, e.g. because they are published,
+    /// can be listed in the `published-crates` configuration.
+    ///
+    /// ### Why is this bad?
+    /// The `dead_code` lint can't tell whether `pub` items are used, as they could be used by any
+    /// crate depending on this one. In a workspace of internal crates, the other crates of the
+    /// workspace are the only users, so unused items can be made private or removed.
+    ///
+    /// ### Known problems
+    /// Items that are only used in macros of other crates, or only by code that is disabled with
+    /// `#[cfg]` in the checked configuration, are linted.
+    ///
+    /// ### Example
+    /// ```rust,ignore
+    /// // crate `utils`, not used by any other crate of the workspace
+    /// pub fn helper() {}
+    /// ```
+    ///
+    /// Use instead:
+    /// ```rust,ignore
+    /// pub(crate) fn helper() {}
+    /// ```
+    #[clippy::version = "1.68.0"]
+    pub UNUSED_PUB_ITEMS,
+    cargo,
+    "exported items no other crate of the workspace uses"
+}
+
 pub struct Cargo {
     pub ignore_publish: bool,
 }
@@ -0,0 +1,246 @@
+//! Finds exported items of workspace libraries that no other crate of the workspace uses.
+//!
+//! Every crate has to be checked twice, which is what `cargo clippy --unused-pub` does. The first
+//! run, with `--unused-pub=record`, writes the items each crate uses from other crates to
+//! `clippy-pub-usage/` next to the build output, e.g. `target/debug/clippy-pub-usage/`. The second
+//! run, with `--unused-pub=check:<crates>`, reads the records of the given workspace crates, which
+//! `cargo clippy` gets from `cargo metadata` once for the whole workspace, and lints the exported
+//! items of each library that none of them use.
+
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use clippy_utils::diagnostics::span_lint_and_help;
+use rustc_data_structures::fx::FxHashSet;
+use rustc_hir::def::Res;
+use rustc_hir::def_id::{DefId, LocalDefId, LOCAL_CRATE};
+use rustc_hir::{Expr, ExprKind, HirId, ImplItem, ImplItemKind, Item, ItemKind, Node, QPath};
+use rustc_lint::{LateContext, LateLintPass, LintContext};
+use rustc_middle::ty::DefIdTree;
+use rustc_session::config::CrateType;
+use rustc_session::impl_lint_pass;
+use rustc_span::sym;
+use serde::{Deserialize, Serialize};
+
+use super::UNUSED_PUB_ITEMS;
+
+#[derive(Clone)]
+pub enum UnusedPubPhase {
+    /// Record the items used from other crates.
+    Record,
+    /// Lint the exported items the other crates of the workspace, given by their names, don't use.
+    Check(FxHashSet<String>),
+}
+
+impl UnusedPubPhase {
+    pub fn parse(phase: &str) -> Option<Self> {
+        match phase.split_once(':') {
+            None if phase == "record" => Some(Self::Record),
+            Some(("check", crates)) => Some(Self::Check(
+                crates
+                    .split(',')
+                    .filter(|name| !name.is_empty())
+                    .map(|name| name.replace('-', "_"))
+                    .collect(),
+            )),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct UsageRecord {
+    crate_name: String,
+    /// The paths of the items used from other crates, e.g. `foo::bar::Baz`.
+    used: BTreeSet<String>,
+}
+
+pub struct UnusedPubItems {
+    phase: UnusedPubPhase,
+    published_crates: FxHashSet<String>,
+    /// Items of other crates used by this crate.
+    used: FxHashSet<String>,
+    /// Items of this crate used by the other crates of the workspace, `None` if the crate isn't
+    /// checked.
+    used_by_others: Option<FxHashSet<String>>,
+}
+
+impl UnusedPubItems {
+    pub fn new(phase: UnusedPubPhase, published_crates: &[String]) -> Self {
+        Self {
+            phase,
+            published_crates: published_crates.iter().map(|name| name.replace('-', "_")).collect(),
+            used: FxHashSet::default(),
+            used_by_others: None,
+        }
+    }
+
+    fn record_use(&mut self, cx: &LateContext<'_>, def_id: DefId) {
+        if def_id.is_local() {
+            return;
+        }
+        // Using an item also uses its parents, e.g. the struct of a constructor or the trait of a
+        // method.
+        let mut current = Some(def_id);
+        while let Some(def_id) = current
+            && self.used.insert(item_path(cx, def_id))
+        {
+            current = cx.tcx.opt_parent(def_id);
+        }
+    }
+
+    fn check_exported(&self, cx: &LateContext<'_>, def_id: LocalDefId) {
+        if let Some(used_by_others) = &self.used_by_others
+            && cx.effective_visibilities.is_exported(def_id)
+            && !cx.tcx.def_span(def_id).from_expansion()
+            && !cx.tcx.has_attr(def_id.to_def_id(), sym::no_mangle)
+            && !cx.tcx.has_attr(def_id.to_def_id(), sym::export_name)
+            && !used_by_others.contains(&item_path(cx, def_id.to_def_id()))
+        {
+            span_lint_and_help(
+                cx,
+                UNUSED_PUB_ITEMS,
+                cx.tcx.def_span(def_id),
+                &format!(
+                    "`{}` is exported, but no other crate of the workspace uses it",
+                    cx.tcx.def_path_str(def_id.to_def_id())
+                ),
+                None,
+                "consider making it `pub(crate)`, or removing it if it isn't used at all",
+            );
+        }
+    }
+}
+
+impl_lint_pass!(UnusedPubItems => [UNUSED_PUB_ITEMS]);
+
+impl<'tcx> LateLintPass<'tcx> for UnusedPubItems {
+    fn check_crate(&mut self, cx: &LateContext<'tcx>) {
+        let sess = cx.sess();
+        let crate_name = cx.tcx.crate_name(LOCAL_CRATE);
+        let UnusedPubPhase::Check(workspace_crates) = &self.phase else {
+            return;
+        };
+        // Only the API of libraries used by other Rust crates is of interest.
+        if sess.opts.test
+            || !sess
+                .crate_types()
+                .iter()
+                .all(|crate_type| matches!(crate_type, CrateType::Rlib | CrateType::Dylib))
+            || self.published_crates.contains(crate_name.as_str())
+        {
+            return;
+        }
+
+        // Records of crates which are no longer part of the workspace are ignored.
+        let mut used_by_others = FxHashSet::default();
+        for record in read_records(&usage_dir(cx)) {
+            if workspace_crates.contains(&record.crate_name) {
+                used_by_others.extend(record.used);
+            }
+        }
+        self.used_by_others = Some(used_by_others);
+    }
+
+    fn check_path(&mut self, cx: &LateContext<'tcx>, path: &rustc_hir::Path<'tcx>, _: HirId) {
+        if let Res::Def(_, def_id) = path.res {
+            self.record_use(cx, def_id);
+        }
+    }
+
+    fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'tcx>) {
+        // Resolved paths are handled by `check_path`.
+        let def_id = match expr.kind {
+            ExprKind::MethodCall(..) | ExprKind::Path(QPath::TypeRelative(..)) => {
+                cx.typeck_results().type_dependent_def_id(expr.hir_id)
+            },
+            _ => None,
+        };
+        if let Some(def_id) = def_id {
+            self.record_use(cx, def_id);
+        }
+    }
+
+    fn check_item(&mut self, cx: &LateContext<'tcx>, item: &'tcx Item<'tcx>) {
+        if matches!(
+            item.kind,
+            ItemKind::Fn(..)
+                | ItemKind::Const(..)
+                | ItemKind::Static(..)
+                | ItemKind::Struct(..)
+                | ItemKind::Enum(..)
+                | ItemKind::Union(..)
+                | ItemKind::Trait(..)
+                | ItemKind::TraitAlias(..)
+                | ItemKind::TyAlias(..)
+        ) {
+            self.check_exported(cx, item.owner_id.def_id);
+        }
+    }
+
+    fn check_impl_item(&mut self, cx: &LateContext<'tcx>, impl_item: &'tcx ImplItem<'tcx>) {
+        // Items of trait impls are used through the trait.
+        let parent = cx.tcx.hir().get_parent_item(impl_item.hir_id()).def_id;
+        if let Some(Node::Item(Item {
+            kind: ItemKind::Impl(impl_),
+            ..
+        })) = cx.tcx.hir().find_by_def_id(parent)
+            && impl_.of_trait.is_none()
+            && matches!(impl_item.kind, ImplItemKind::Fn(..) | ImplItemKind::Const(..))
+        {
+            self.check_exported(cx, impl_item.owner_id.def_id);
+        }
+    }
+
+    fn check_crate_post(&mut self, cx: &LateContext<'tcx>) {
+        if matches!(self.phase, UnusedPubPhase::Record) {
+            let crate_name = cx.tcx.crate_name(LOCAL_CRATE).to_string();
+            // The crate name isn't unique, e.g. the library and the binary of a package usually
+            // share it.
+            let file_name = format!("{crate_name}-{:016x}.json", cx.sess().local_stable_crate_id().to_u64());
+            let record = UsageRecord {
+                crate_name,
+                used: self.used.drain().collect(),
+            };
+            let dir = usage_dir(cx);
+            if let Err(error) = write_record(&dir, &file_name, &record) {
+                cx.sess().warn(format!(
+                    "could not write the `pub` usage record to `{}`: {error}",
+                    dir.join(file_name).display()
+                ));
+            }
+        }
+    }
+}
+
+/// The path of an item, including the crate name, e.g. `foo::bar::{impl#0}::new`.
+fn item_path(cx: &LateContext<'_>, def_id: DefId) -> String {
+    format!(
+        "{}{}",
+        cx.tcx.crate_name(def_id.krate),
+        cx.tcx.def_path(def_id).to_string_no_crate_verbose()
+    )
+}
+
+fn usage_dir(cx: &LateContext<'_>) -> PathBuf {
+    let out_dir = &cx.tcx.output_filenames(()).out_directory;
+    out_dir.parent().unwrap_or(out_dir).join("clippy-pub-usage")
+}
+
+fn write_record(dir: &Path, file_name: &str, record: &UsageRecord) -> std::io::Result<()> {
+    fs::create_dir_all(dir)?;
+    fs::write(dir.join(file_name), serde_json::to_string(record)?)
+}
+
+fn read_records(dir: &Path) -> Vec<UsageRecord> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    entries
+        .filter_map(|entry| {
+            let content = fs::read_to_string(entry.ok()?.path()).ok()?;
+            serde_json::from_str(&content).ok()
+        })
+        .collect()
+}
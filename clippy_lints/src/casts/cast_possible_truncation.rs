@@ -77,16 +77,20 @@ fn apply_reductions(cx: &LateContext<'_>, nbits: u64, expr: &Expr<'_>, signed: b
     }
 }
 
-pub(super) fn check(
-    cx: &LateContext<'_>,
+pub(super) fn check<'tcx>(
+    cx: &LateContext<'tcx>,
     expr: &Expr<'_>,
-    cast_expr: &Expr<'_>,
+    cast_expr: &'tcx Expr<'tcx>,
     cast_from: Ty<'_>,
     cast_to: Ty<'_>,
     cast_to_span: Span,
 ) {
     let msg = match (cast_from.kind(), cast_to.is_integral()) {
         (ty::Int(_) | ty::Uint(_), true) => {
+            if utils::value_fits_in(cx, cast_expr, cast_to) {
+                return;
+            }
+
             let from_nbits = apply_reductions(
                 cx,
                 utils::int_ty_to_nbits(cast_from, cx.tcx),
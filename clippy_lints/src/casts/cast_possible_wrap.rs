@@ -6,8 +6,14 @@ use rustc_middle::ty::Ty;
 
 use super::{utils, CAST_POSSIBLE_WRAP};
 
-pub(super) fn check(cx: &LateContext<'_>, expr: &Expr<'_>, cast_from: Ty<'_>, cast_to: Ty<'_>) {
-    if !(cast_from.is_integral() && cast_to.is_integral()) {
+pub(super) fn check<'tcx>(
+    cx: &LateContext<'tcx>,
+    expr: &Expr<'_>,
+    cast_expr: &'tcx Expr<'tcx>,
+    cast_from: Ty<'_>,
+    cast_to: Ty<'_>,
+) {
+    if !(cast_from.is_integral() && cast_to.is_integral()) || utils::value_fits_in(cx, cast_expr, cast_to) {
         return;
     }
 
@@ -1,5 +1,6 @@
 use clippy_utils::consts::{constant, Constant};
 use clippy_utils::diagnostics::span_lint;
+use clippy_utils::value_range::int_range;
 use clippy_utils::{method_chain_args, sext};
 use if_chain::if_chain;
 use rustc_hir::{Expr, ExprKind};
@@ -8,7 +9,13 @@ use rustc_middle::ty::{self, Ty};
 
 use super::CAST_SIGN_LOSS;
 
-pub(super) fn check(cx: &LateContext<'_>, expr: &Expr<'_>, cast_op: &Expr<'_>, cast_from: Ty<'_>, cast_to: Ty<'_>) {
+pub(super) fn check<'tcx>(
+    cx: &LateContext<'tcx>,
+    expr: &Expr<'_>,
+    cast_op: &'tcx Expr<'tcx>,
+    cast_from: Ty<'_>,
+    cast_to: Ty<'_>,
+) {
     if should_lint(cx, cast_op, cast_from, cast_to) {
         span_lint(
             cx,
@@ -19,7 +26,7 @@ pub(super) fn check(cx: &LateContext<'_>, expr: &Expr<'_>, cast_op: &Expr<'_>, c
     }
 }
 
-fn should_lint(cx: &LateContext<'_>, cast_op: &Expr<'_>, cast_from: Ty<'_>, cast_to: Ty<'_>) -> bool {
+fn should_lint<'tcx>(cx: &LateContext<'tcx>, cast_op: &'tcx Expr<'tcx>, cast_from: Ty<'_>, cast_to: Ty<'_>) -> bool {
     match (cast_from.is_integral(), cast_to.is_integral()) {
         (true, true) => {
             if !cast_from.is_signed() || cast_to.is_signed() {
@@ -37,6 +44,11 @@ fn should_lint(cx: &LateContext<'_>, cast_op: &Expr<'_>, cast_from: Ty<'_>, cast
                 }
             }
 
+            // Don't lint for values which are known to be non-negative.
+            if int_range(cx, cast_op).map_or(false, |range| range.lo >= 0) {
+                return false;
+            }
+
             // Don't lint for the result of methods that always return non-negative values.
             if let ExprKind::MethodCall(path, ..) = cast_op.kind {
                 let mut method_name = path.ident.name.as_str();
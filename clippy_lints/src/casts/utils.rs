@@ -1,4 +1,8 @@
+use clippy_utils::consts::constant;
 use clippy_utils::ty::{read_explicit_enum_value, EnumValue};
+use clippy_utils::value_range::int_range;
+use rustc_hir::Expr;
+use rustc_lint::LateContext;
 use rustc_middle::ty::{self, AdtDef, IntTy, Ty, TyCtxt, UintTy, VariantDiscr};
 
 /// Returns the size in bits of an integral type.
@@ -25,6 +29,14 @@ pub(super) fn int_ty_to_nbits(typ: Ty<'_>, tcx: TyCtxt<'_>) -> u64 {
     }
 }
 
+/// Checks if every value `expr` can have fits in `ty`, see `clippy_utils::value_range`. Casts of
+/// constants are still linted based on their types, a literal of the target type can be written
+/// instead.
+pub(super) fn value_fits_in<'tcx>(cx: &LateContext<'tcx>, expr: &'tcx Expr<'tcx>, ty: Ty<'_>) -> bool {
+    constant(cx, cx.typeck_results(), expr).is_none()
+        && int_range(cx, expr).map_or(false, |range| range.fits_in(cx.tcx, ty))
+}
+
 pub(super) fn enum_value_nbits(value: EnumValue) -> u64 {
     match value {
         EnumValue::Unsigned(x) => 128 - x.leading_zeros(),
@@ -13,7 +13,7 @@
 //! This lint is **warn** by default
 
 use clippy_utils::diagnostics::{span_lint_and_sugg, span_lint_and_then};
-use clippy_utils::source::{snippet, snippet_block, snippet_block_with_applicability};
+use clippy_utils::source::{reattach_comments, snippet, snippet_block, snippet_block_with_applicability};
 use clippy_utils::sugg::Sugg;
 use if_chain::if_chain;
 use rustc_ast::ast;
@@ -135,17 +135,15 @@ fn check_collapsible_maybe_if_let(cx: &EarlyContext<'_>, then_span: Span, else_:
             let requires_space = if let Some(c) = snippet(cx, up_to_else, "..").chars().last() { !c.is_whitespace() } else { false };
 
             let mut applicability = Applicability::MachineApplicable;
+            let sugg = snippet_block_with_applicability(cx, else_.span, "..", Some(block.span), &mut applicability);
+            let sugg = reattach_comments(cx, block.span, &[else_.span], sugg.into_owned(), &mut applicability);
             span_lint_and_sugg(
                 cx,
                 COLLAPSIBLE_ELSE_IF,
                 block.span,
                 "this `else { if .. }` block can be collapsed",
                 "collapse nested if block",
-                format!(
-                    "{}{}",
-                    if requires_space { " " } else { "" },
-                    snippet_block_with_applicability(cx, else_.span, "..", Some(block.span), &mut applicability)
-                ),
+                format!("{}{sugg}", if requires_space { " " } else { "" }),
                 applicability,
             );
         }
@@ -167,14 +165,16 @@ fn check_collapsible_no_if_let(cx: &EarlyContext<'_>, expr: &ast::Expr, check: &
                 let mut app = Applicability::MachineApplicable;
                 let lhs = Sugg::ast(cx, check, "..", ctxt, &mut app);
                 let rhs = Sugg::ast(cx, check_inner, "..", ctxt, &mut app);
+                let sugg = format!(
+                    "if {} {}",
+                    lhs.and(&rhs),
+                    snippet_block(cx, content.span, "..", Some(expr.span)),
+                );
+                let kept = [check.span, check_inner.span, content.span];
                 diag.span_suggestion(
                     expr.span,
                     "collapse nested if block",
-                    format!(
-                        "if {} {}",
-                        lhs.and(&rhs),
-                        snippet_block(cx, content.span, "..", Some(expr.span)),
-                    ),
+                    reattach_comments(cx, expr.span, &kept, sugg, &mut app),
                     app, // snippet
                 );
             });
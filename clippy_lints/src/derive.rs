@@ -1,25 +1,33 @@
+use clippy_utils::attrs::get_attr;
 use clippy_utils::diagnostics::{span_lint_and_help, span_lint_and_note, span_lint_and_sugg, span_lint_and_then};
 use clippy_utils::paths;
-use clippy_utils::ty::{implements_trait, implements_trait_with_env, is_copy};
-use clippy_utils::{is_lint_allowed, match_def_path};
+use clippy_utils::ty::{implements_trait, implements_trait_with_env, is_copy, match_type};
+use clippy_utils::visitors::for_each_expr;
+use clippy_utils::{get_parent_expr, get_parent_node, is_lint_allowed, match_def_path, path_to_local_id};
+use core::ops::ControlFlow;
 use if_chain::if_chain;
 use rustc_errors::Applicability;
+use rustc_hir::def::{DefKind, Res};
 use rustc_hir::def_id::DefId;
 use rustc_hir::intravisit::{walk_expr, walk_fn, walk_item, FnKind, Visitor};
+use rustc_hir::pat_util::EnumerateAndAdjustIterator;
 use rustc_hir::{
-    self as hir, BlockCheckMode, BodyId, Constness, Expr, ExprKind, FnDecl, HirId, Impl, Item, ItemKind, UnsafeSource,
-    Unsafety,
+    self as hir, BlockCheckMode, BodyId, Constness, Expr, ExprKind, FnDecl, HirId, Impl, Item, ItemKind, Node, Pat,
+    PatKind, UnOp, UnsafeSource, Unsafety,
 };
-use rustc_lint::{LateContext, LateLintPass};
+use rustc_lint::{LateContext, LateLintPass, LintContext};
 use rustc_middle::hir::nested_filter;
 use rustc_middle::traits::Reveal;
+use rustc_middle::ty::adjustment::Adjust;
 use rustc_middle::ty::{
-    self, Binder, BoundConstness, Clause, GenericArgKind, GenericParamDefKind, ImplPolarity, ParamEnv, PredicateKind,
-    TraitPredicate, Ty, TyCtxt,
+    self, AdtDef, AssocKind, Binder, BoundConstness, Clause, GenericArgKind, GenericParamDefKind, ImplPolarity,
+    ParamEnv, PredicateKind, TraitPredicate, Ty, TyCtxt, TypeckResults,
 };
 use rustc_session::{declare_lint_pass, declare_tool_lint};
 use rustc_span::source_map::Span;
-use rustc_span::sym;
+use rustc_span::symbol::kw;
+use rustc_span::{sym, Symbol};
+use std::collections::BTreeSet;
 
 declare_clippy_lint! {
     /// ### What it does
@@ -195,12 +203,121 @@ declare_clippy_lint! {
     "deriving `PartialEq` on a type that can implement `Eq`, without implementing `Eq`"
 }
 
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for manual implementations of `Hash`, `Ord` and `PartialOrd` which read other fields
+    /// of `self` than the manual implementation of `PartialEq` for the same type.
+    ///
+    /// ### Why is this bad?
+    /// Equal values must have the same hash, and `cmp` must return `Ordering::Equal` exactly for
+    /// the values which are equal. Hashing a field which `eq` ignores, or comparing other fields in
+    /// `cmp` than in `eq`, breaks this, e.g. a `HashSet` may then contain equal values.
+    ///
+    /// ### Known problems
+    /// Only the fields read through `self` are compared, whether they change the result or not.
+    /// Implementations which use `self` in other ways, e.g. by calling one of its methods, are
+    /// ignored.
+    ///
+    /// ### Example
+    /// ```rust
+    /// # use std::hash::{Hash, Hasher};
+    /// struct User {
+    ///     id: u32,
+    ///     name: String,
+    /// }
+    ///
+    /// impl PartialEq for User {
+    ///     fn eq(&self, other: &Self) -> bool {
+    ///         self.id == other.id
+    ///     }
+    /// }
+    ///
+    /// impl Hash for User {
+    ///     fn hash<H: Hasher>(&self, state: &mut H) {
+    ///         self.id.hash(state);
+    ///         self.name.hash(state);
+    ///     }
+    /// }
+    /// ```
+    /// Use instead:
+    /// ```rust
+    /// # use std::hash::{Hash, Hasher};
+    /// # struct User {
+    /// #     id: u32,
+    /// #     name: String,
+    /// # }
+    /// # impl PartialEq for User {
+    /// #     fn eq(&self, other: &Self) -> bool {
+    /// #         self.id == other.id
+    /// #     }
+    /// # }
+    /// impl Hash for User {
+    ///     fn hash<H: Hasher>(&self, state: &mut H) {
+    ///         self.id.hash(state);
+    ///     }
+    /// }
+    /// ```
+    #[clippy::version = "1.68.0"]
+    pub INCONSISTENT_IMPL_FIELDS,
+    suspicious,
+    "manual `Hash`, `Ord` or `PartialOrd` implementation reading other fields than the manual `PartialEq` implementation"
+}
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for manual implementations of `Debug`, `Clone` and `Default` for structs which
+    /// don't mention some of the fields: `fmt` and `clone` not reading them from `self`, `default`
+    /// not setting them in the struct expression.
+    ///
+    /// Fields whose name starts with `_` and fields with the `#[clippy::skip_in_manual_impls]`
+    /// attribute are not linted.
+    ///
+    /// ### Why is this bad?
+    /// Fields added to a struct are easily forgotten in its manual implementations, e.g. `Debug`
+    /// then doesn't show them, and `Clone` resets them with `Default::default()` instead of
+    /// cloning them.
+    ///
+    /// ### Example
+    /// ```rust
+    /// # use std::fmt;
+    /// struct Connection {
+    ///     host: String,
+    ///     port: u16,
+    /// }
+    ///
+    /// impl fmt::Debug for Connection {
+    ///     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    ///         f.debug_struct("Connection").field("host", &self.host).finish()
+    ///     }
+    /// }
+    /// ```
+    /// Use instead:
+    /// ```rust
+    /// # use std::fmt;
+    /// # struct Connection {
+    /// #     host: String,
+    /// #     port: u16,
+    /// # }
+    /// impl fmt::Debug for Connection {
+    ///     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    ///         f.debug_struct("Connection").field("host", &self.host).finish_non_exhaustive()
+    ///     }
+    /// }
+    /// ```
+    #[clippy::version = "1.68.0"]
+    pub MISSING_FIELDS_IN_MANUAL_IMPL,
+    pedantic,
+    "manual `Debug`, `Clone` or `Default` implementation which doesn't mention some fields"
+}
+
 declare_lint_pass!(Derive => [
     EXPL_IMPL_CLONE_ON_COPY,
     DERIVED_HASH_WITH_MANUAL_EQ,
     DERIVE_ORD_XOR_PARTIAL_ORD,
     UNSAFE_DERIVE_DESERIALIZE,
-    DERIVE_PARTIAL_EQ_WITHOUT_EQ
+    DERIVE_PARTIAL_EQ_WITHOUT_EQ,
+    INCONSISTENT_IMPL_FIELDS,
+    MISSING_FIELDS_IN_MANUAL_IMPL
 ]);
 
 impl<'tcx> LateLintPass<'tcx> for Derive {
@@ -221,6 +338,8 @@ impl<'tcx> LateLintPass<'tcx> for Derive {
                 check_partial_eq_without_eq(cx, item.span, trait_ref, ty);
             } else {
                 check_copy_clone(cx, item, trait_ref, ty);
+                check_impl_fields(cx, item, trait_ref, ty);
+                check_missing_fields(cx, item, trait_ref, ty);
             }
         }
     }
@@ -327,6 +446,433 @@ fn check_ord_partial_ord<'tcx>(
     }
 }
 
+/// The traits whose implementations must agree on the fields they read.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum ComparedTrait {
+    PartialEq,
+    PartialOrd,
+    Ord,
+    Hash,
+}
+
+impl ComparedTrait {
+    fn of(cx: &LateContext<'_>, def_id: DefId) -> Option<Self> {
+        let lang_items = cx.tcx.lang_items();
+        if lang_items.eq_trait() == Some(def_id) {
+            Some(Self::PartialEq)
+        } else if lang_items.partial_ord_trait() == Some(def_id) {
+            Some(Self::PartialOrd)
+        } else {
+            match cx.tcx.get_diagnostic_name(def_id)? {
+                sym::Ord => Some(Self::Ord),
+                sym::Hash => Some(Self::Hash),
+                _ => None,
+            }
+        }
+    }
+
+    fn trait_def_id(self, cx: &LateContext<'_>) -> Option<DefId> {
+        match self {
+            Self::PartialEq => cx.tcx.lang_items().eq_trait(),
+            Self::PartialOrd => cx.tcx.lang_items().partial_ord_trait(),
+            Self::Ord => cx.tcx.get_diagnostic_item(sym::Ord),
+            Self::Hash => cx.tcx.get_diagnostic_item(sym::Hash),
+        }
+    }
+
+    fn method(self) -> Symbol {
+        match self {
+            Self::PartialEq => sym::eq,
+            Self::PartialOrd => sym::partial_cmp,
+            Self::Ord => sym::cmp,
+            Self::Hash => sym::hash,
+        }
+    }
+}
+
+/// Implementation of the `INCONSISTENT_IMPL_FIELDS` lint.
+fn check_impl_fields<'tcx>(cx: &LateContext<'tcx>, item: &Item<'_>, trait_ref: &hir::TraitRef<'_>, ty: Ty<'tcx>) {
+    if_chain! {
+        if let ty::Adt(adt, _) = ty.kind();
+        if adt.is_struct();
+        if let Some(trait_def_id) = trait_ref.trait_def_id();
+        if let Some(compared) = ComparedTrait::of(cx, trait_def_id);
+        if compared != ComparedTrait::PartialEq;
+        if is_impl_for(cx, item.owner_id.to_def_id(), ty);
+        if let Some(eq_impl) = find_impl(cx, ComparedTrait::PartialEq, ty);
+        if !cx.tcx.has_attr(eq_impl, sym::automatically_derived);
+        if let Some(fields) = read_fields(cx, item.owner_id.to_def_id(), compared, ty, true);
+        if let Some(eq_fields) = read_fields(cx, eq_impl, ComparedTrait::PartialEq, ty, true);
+        then {
+            let only_here: Vec<_> = fields.difference(&eq_fields).copied().collect();
+            let only_eq: Vec<_> = match compared {
+                // Equal values must have the same hash, but unequal ones may as well
+                ComparedTrait::Hash => Vec::new(),
+                _ => eq_fields.difference(&fields).copied().collect(),
+            };
+            if only_here.is_empty() && only_eq.is_empty() {
+                return;
+            }
+            // Don't lint `PartialOrd` again if it reads the fields of the manual `Ord`, e.g. by calling `cmp`
+            if compared == ComparedTrait::PartialOrd
+                && let Some(ord_impl) = find_impl(cx, ComparedTrait::Ord, ty)
+                && !cx.tcx.has_attr(ord_impl, sym::automatically_derived)
+                && read_fields(cx, ord_impl, ComparedTrait::Ord, ty, true).as_ref() == Some(&fields)
+            {
+                return;
+            }
+
+            let (msg, read) = match compared {
+                ComparedTrait::Hash => ("this `Hash` implementation hashes fields which `PartialEq` doesn't compare", "hashes"),
+                ComparedTrait::Ord => ("this `Ord` implementation compares other fields than `PartialEq`", "compares"),
+                _ => ("this `PartialOrd` implementation compares other fields than `PartialEq`", "compares"),
+            };
+            let field_names = |fields: &[usize]| {
+                fields
+                    .iter()
+                    .map(|&field| format!("`{}`", adt.non_enum_variant().fields[field].name))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            };
+            span_lint_and_then(cx, INCONSISTENT_IMPL_FIELDS, item.span, msg, |diag| {
+                if !only_here.is_empty() {
+                    diag.note(format!("only `{}` {read} {}", compared.method(), field_names(&only_here)));
+                }
+                if !only_eq.is_empty() {
+                    diag.note(format!("only `eq` compares {}", field_names(&only_eq)));
+                }
+                if let Some(local_def_id) = eq_impl.as_local() {
+                    let hir_id = cx.tcx.hir().local_def_id_to_hir_id(local_def_id);
+                    diag.span_note(cx.tcx.hir().span(hir_id), "`PartialEq` implemented here");
+                }
+            });
+        }
+    }
+}
+
+/// Checks if `impl_id` implements its trait for `ty`, and compares it with `ty` for `PartialEq`
+/// and `PartialOrd`.
+fn is_impl_for<'tcx>(cx: &LateContext<'tcx>, impl_id: DefId, ty: Ty<'tcx>) -> bool {
+    // For `impl PartialEq<B> for A`, the substs are `[A, B]`
+    cx.tcx.impl_trait_ref(impl_id).map_or(false, |trait_ref| {
+        cx.tcx.type_of(impl_id) == ty && trait_ref.substs.types().skip(1).all(|rhs| rhs == ty)
+    })
+}
+
+/// Finds the implementation of `compared` for `ty`.
+fn find_impl<'tcx>(cx: &LateContext<'tcx>, compared: ComparedTrait, ty: Ty<'tcx>) -> Option<DefId> {
+    let mut found = None;
+    cx.tcx
+        .for_each_relevant_impl(compared.trait_def_id(cx)?, ty, |impl_id| {
+            if found.is_none() && is_impl_for(cx, impl_id, ty) {
+                found = Some(impl_id);
+            }
+        });
+    found
+}
+
+/// The indexes of the fields of `self` read by the method of `compared` in `impl_id`, including
+/// the fields read by the implementations of the other compared traits it calls, e.g. `partial_cmp`
+/// calling `cmp`, if `follow_calls` is set. Returns `None` if `self` is used otherwise.
+fn read_fields<'tcx>(
+    cx: &LateContext<'tcx>,
+    impl_id: DefId,
+    compared: ComparedTrait,
+    ty: Ty<'tcx>,
+    follow_calls: bool,
+) -> Option<BTreeSet<usize>> {
+    let ty::Adt(adt, _) = ty.kind() else {
+        return None;
+    };
+    if cx.tcx.has_attr(impl_id, sym::automatically_derived) {
+        return Some((0..adt.non_enum_variant().fields.len()).collect());
+    }
+    let body_id = impl_method_body(cx, impl_id, compared.method())?;
+    self_fields(cx, body_id, *adt, |called| {
+        // e.g. `partial_cmp` returning `Some(self.cmp(other))`
+        let called = ComparedTrait::of(cx, cx.tcx.trait_of_item(called)?)?;
+        if !follow_calls || called == compared {
+            return None;
+        }
+        read_fields(cx, find_impl(cx, called, ty)?, called, ty, false)
+    })
+}
+
+/// The body of the method `name` in the local implementation `impl_id`.
+fn impl_method_body(cx: &LateContext<'_>, impl_id: DefId, name: Symbol) -> Option<BodyId> {
+    let method = cx
+        .tcx
+        .associated_items(impl_id)
+        .filter_by_name_unhygienic(name)
+        .find(|item| item.kind == AssocKind::Fn)?;
+    Some(cx.tcx.hir().body_owned_by(method.def_id.as_local()?))
+}
+
+/// The indexes of the fields of `self`, the first parameter of `body_id`, which it reads. The
+/// functions and methods it passes `self` to are given to `on_call`, which returns the fields they
+/// read. Returns `None` if `self` is used otherwise, e.g. through a `Deref` implementation.
+fn self_fields(
+    cx: &LateContext<'_>,
+    body_id: BodyId,
+    adt: AdtDef<'_>,
+    mut on_call: impl FnMut(DefId) -> Option<BTreeSet<usize>>,
+) -> Option<BTreeSet<usize>> {
+    let body = cx.tcx.hir().body(body_id);
+    let PatKind::Binding(_, self_id, ..) = body.params.first()?.pat.kind else {
+        return None;
+    };
+    let typeck = cx.tcx.typeck_body(body_id);
+
+    let mut fields = BTreeSet::new();
+    let mut add_use = |e: &Expr<'_>| -> Option<()> {
+        let mut child = e;
+        let mut parent = get_parent_expr(cx, e);
+        if let Some(deref) = parent
+            && let ExprKind::Unary(UnOp::Deref, _) = deref.kind
+        {
+            child = deref;
+            parent = get_parent_expr(cx, deref);
+        }
+        // Through a `Deref` implementation, the fields and methods are those of another type
+        if typeck
+            .expr_adjustments(child)
+            .iter()
+            .any(|adjustment| matches!(adjustment.kind, Adjust::Deref(Some(_))))
+        {
+            return None;
+        }
+        let called = match parent.map(|parent| (parent, &parent.kind)) {
+            Some((parent, ExprKind::Field(..))) => {
+                fields.insert(typeck.field_index(parent.hir_id));
+                return Some(());
+            },
+            Some((_, &ExprKind::Match(scrutinee, arms, _))) if scrutinee.hir_id == child.hir_id => {
+                for arm in arms {
+                    add_pat_fields(typeck, adt, arm.pat, &mut fields)?;
+                }
+                return Some(());
+            },
+            Some((parent, &ExprKind::MethodCall(_, receiver, ..))) if receiver.hir_id == child.hir_id => {
+                typeck.type_dependent_def_id(parent.hir_id)?
+            },
+            Some((_, &ExprKind::Call(func, [first, ..]))) if first.hir_id == child.hir_id => {
+                let ExprKind::Path(ref qpath) = func.kind else {
+                    return None;
+                };
+                typeck.qpath_res(qpath, func.hir_id).opt_def_id()?
+            },
+            _ => {
+                let Some(Node::Local(local)) = get_parent_node(cx.tcx, child.hir_id) else {
+                    return None;
+                };
+                return add_pat_fields(typeck, adt, local.pat, &mut fields);
+            },
+        };
+        fields.extend(on_call(called)?);
+        Some(())
+    };
+    let unknown = for_each_expr(body.value, |e| {
+        if path_to_local_id(e, self_id) && add_use(e).is_none() {
+            ControlFlow::Break(())
+        } else {
+            ControlFlow::Continue(())
+        }
+    });
+    unknown.is_none().then_some(fields)
+}
+
+/// Adds the fields bound by `pat`, which destructures `self`.
+fn add_pat_fields(
+    typeck: &TypeckResults<'_>,
+    adt: AdtDef<'_>,
+    pat: &Pat<'_>,
+    fields: &mut BTreeSet<usize>,
+) -> Option<()> {
+    match pat.kind {
+        PatKind::Ref(pat, _) => add_pat_fields(typeck, adt, pat, fields),
+        PatKind::Wild => Some(()),
+        PatKind::Struct(_, pat_fields, _) => {
+            fields.extend(
+                pat_fields
+                    .iter()
+                    .filter(|field| !matches!(field.pat.kind, PatKind::Wild))
+                    .map(|field| typeck.field_index(field.hir_id)),
+            );
+            Some(())
+        },
+        PatKind::TupleStruct(_, pats, dotdot) => {
+            fields.extend(
+                pats.iter()
+                    .enumerate_and_adjust(adt.non_enum_variant().fields.len(), dotdot)
+                    .filter(|(_, pat)| !matches!(pat.kind, PatKind::Wild))
+                    .map(|(field, _)| field),
+            );
+            Some(())
+        },
+        _ => None,
+    }
+}
+
+/// Implementation of the `MISSING_FIELDS_IN_MANUAL_IMPL` lint.
+fn check_missing_fields<'tcx>(cx: &LateContext<'tcx>, item: &Item<'_>, trait_ref: &hir::TraitRef<'_>, ty: Ty<'tcx>) {
+    let ty::Adt(adt, _) = ty.kind() else {
+        return;
+    };
+    let Some(trait_def_id) = trait_ref.trait_def_id() else {
+        return;
+    };
+    if !adt.is_struct() || !adt.did().is_local() || is_lint_allowed(cx, MISSING_FIELDS_IN_MANUAL_IMPL, item.hir_id()) {
+        return;
+    }
+    let impl_id = item.owner_id.to_def_id();
+    let (trait_name, body_id, mentioned) = match cx.tcx.get_diagnostic_name(trait_def_id) {
+        Some(sym::Debug) => {
+            let Some(body_id) = impl_method_body(cx, impl_id, sym::fmt) else {
+                return;
+            };
+            ("Debug", body_id, self_fields(cx, body_id, *adt, |_| None))
+        },
+        Some(sym::Clone) => {
+            let Some(body_id) = impl_method_body(cx, impl_id, sym::clone) else {
+                return;
+            };
+            ("Clone", body_id, self_fields(cx, body_id, *adt, |_| None))
+        },
+        Some(sym::Default) => {
+            let Some(body_id) = impl_method_body(cx, impl_id, kw::Default) else {
+                return;
+            };
+            ("Default", body_id, initialized_fields(cx, body_id, *adt))
+        },
+        _ => return,
+    };
+    let Some(mentioned) = mentioned else {
+        return;
+    };
+
+    let missing = unmentioned_fields(cx, *adt, &mentioned);
+    if missing.is_empty() {
+        return;
+    }
+
+    let body = cx.tcx.hir().body(body_id);
+    let typeck = cx.tcx.typeck_body(body_id);
+    let mut finish = None;
+    let mut saw_non_exhaustive = false;
+    let mut struct_fields = Vec::new();
+    let _: Option<!> = for_each_expr(body.value, |e| {
+        match e.kind {
+            ExprKind::MethodCall(method, receiver, [], _)
+                if matches!(method.ident.as_str(), "finish" | "finish_non_exhaustive")
+                    && match_type(cx, typeck.expr_ty(receiver).peel_refs(), &paths::DEBUG_STRUCT) =>
+            {
+                if method.ident.as_str() == "finish" {
+                    finish.get_or_insert(method.ident.span);
+                } else {
+                    saw_non_exhaustive = true;
+                }
+            },
+            ExprKind::Struct(_, fields, _) if typeck.expr_ty(e) == ty => struct_fields.extend(fields.iter()),
+            _ => {},
+        }
+        ControlFlow::Continue(())
+    });
+    // `finish_non_exhaustive` already tells that some fields are not shown
+    if saw_non_exhaustive && trait_name == "Debug" {
+        return;
+    }
+
+    let names = missing
+        .iter()
+        .map(|(_, name)| format!("`{name}`"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let (msg, note) = match trait_name {
+        "Debug" => ("this `Debug` implementation doesn't show all the fields", "not shown"),
+        "Clone" => ("this `Clone` implementation doesn't clone all the fields", "not cloned"),
+        _ => (
+            "this `Default` implementation doesn't set all the fields",
+            "not set explicitly",
+        ),
+    };
+    span_lint_and_then(cx, MISSING_FIELDS_IN_MANUAL_IMPL, item.span, msg, |diag| {
+        if missing.len() == 1 {
+            diag.note(format!("the field {names} is {note}"));
+        } else {
+            diag.note(format!("the fields {names} are {note}"));
+        }
+        if let Some(span) = finish {
+            diag.span_suggestion(
+                span,
+                "if they are left out on purpose, use",
+                "finish_non_exhaustive",
+                Applicability::MaybeIncorrect,
+            );
+        }
+        for field in struct_fields {
+            if trait_name == "Clone"
+                && missing
+                    .iter()
+                    .any(|&(index, _)| typeck.field_index(field.hir_id) == index)
+            {
+                diag.span_note(field.span, "this field is not cloned from `self`");
+            }
+        }
+    });
+}
+
+/// The fields of `adt` which are not in `mentioned`, but should be.
+fn unmentioned_fields(cx: &LateContext<'_>, adt: AdtDef<'_>, mentioned: &BTreeSet<usize>) -> Vec<(usize, Symbol)> {
+    adt.non_enum_variant()
+        .fields
+        .iter()
+        .enumerate()
+        .filter(|&(index, field)| {
+            !mentioned.contains(&index)
+                && !field.name.as_str().starts_with('_')
+                && get_attr(cx.sess(), cx.tcx.get_attrs_unchecked(field.did), "skip_in_manual_impls")
+                    .next()
+                    .is_none()
+        })
+        .map(|(index, field)| (index, field.name))
+        .collect()
+}
+
+/// The indexes of the fields of `adt` set by the struct expressions in `body_id`, including their
+/// bases and the calls of the constructor of a tuple struct. Returns `None` if there is none.
+fn initialized_fields(cx: &LateContext<'_>, body_id: BodyId, adt: AdtDef<'_>) -> Option<BTreeSet<usize>> {
+    let body = cx.tcx.hir().body(body_id);
+    let typeck = cx.tcx.typeck_body(body_id);
+    let mut fields = None;
+    let _: Option<!> = for_each_expr(body.value, |e| {
+        match e.kind {
+            // The base sets the remaining fields
+            ExprKind::Struct(_, _, Some(_)) if typeck.expr_ty(e).ty_adt_def() == Some(adt) => {
+                fields
+                    .get_or_insert_with(BTreeSet::new)
+                    .extend(0..adt.non_enum_variant().fields.len());
+            },
+            ExprKind::Struct(_, struct_fields, None) if typeck.expr_ty(e).ty_adt_def() == Some(adt) => {
+                fields
+                    .get_or_insert_with(BTreeSet::new)
+                    .extend(struct_fields.iter().map(|field| typeck.field_index(field.hir_id)));
+            },
+            ExprKind::Call(func, _)
+                if typeck.expr_ty(e).ty_adt_def() == Some(adt)
+                    && matches!(func.kind, ExprKind::Path(ref qpath)
+                        if matches!(typeck.qpath_res(qpath, func.hir_id), Res::Def(DefKind::Ctor(..), _))) =>
+            {
+                fields
+                    .get_or_insert_with(BTreeSet::new)
+                    .extend(0..adt.non_enum_variant().fields.len());
+            },
+            _ => {},
+        }
+        ControlFlow::Continue(())
+    });
+    fields
+}
+
 /// Implementation of the `EXPL_IMPL_CLONE_ON_COPY` lint.
 fn check_copy_clone<'tcx>(cx: &LateContext<'tcx>, item: &Item<'_>, trait_ref: &hir::TraitRef<'_>, ty: Ty<'tcx>) {
     let clone_id = match cx.tcx.lang_items().clone_trait() {
@@ -0,0 +1,520 @@
+use clippy_utils::diagnostics::span_lint_and_then;
+use clippy_utils::source::snippet_with_applicability;
+use clippy_utils::{def_path_res, path_res, SpanlessEq};
+use rustc_data_structures::fx::FxHashMap;
+use rustc_errors::Applicability;
+use rustc_hir::def::{DefKind, Res};
+use rustc_hir::{AsyncGeneratorKind, Closure, Expr, ExprKind, GeneratorKind, Node};
+use rustc_lint::{LateContext, LateLintPass, LintContext};
+use rustc_middle::ty;
+use rustc_session::{declare_tool_lint, impl_lint_pass};
+use std::iter;
+
+use crate::utils::conf::{DisallowedPattern, PatternContext};
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for expressions matching the patterns in the `disallowed-patterns` configuration.
+    ///
+    /// A pattern is a path, a function call or a chain of method calls:
+    /// * `path::to::item` matches a path to that constant, static or function.
+    /// * `path::to::function(args)` matches a call of that function, including method calls
+    ///   of it with the receiver as the first argument.
+    /// * `receiver.method(args)` matches a call of a method with that name.
+    /// * `_` matches any expression, and `..` as the last argument any remaining arguments.
+    /// * `$name` matches any expression and names it for the replacement. A placeholder used
+    ///   twice has to match the same expression twice.
+    /// * `$name: path::to::Type` matches an expression of that type, or of a reference to it.
+    ///
+    /// Paths have to be fully qualified and can't have generic arguments.
+    ///
+    /// ### Why is this bad?
+    /// Some code bases have rules about patterns which are fine in general, but not for
+    /// them.
+    ///
+    /// ### Example
+    /// An example clippy.toml configuration:
+    /// ```toml
+    /// # clippy.toml
+    /// disallowed-patterns = [
+    ///     { pattern = "std::fs::read_to_string(_).unwrap()", message = "handle IO errors" },
+    ///     { pattern = "std::time::Instant::now()", inside = "loop", message = "hoist the clock read" },
+    ///     { pattern = "$v: std::vec::Vec.iter().count()", replacement = "$v.len()" },
+    /// ]
+    /// ```
+    ///
+    /// ```rust,ignore
+    /// let config = std::fs::read_to_string("config.toml").unwrap();
+    /// ```
+    ///
+    /// Use instead:
+    /// ```rust,ignore
+    /// let config = std::fs::read_to_string("config.toml")?;
+    /// ```
+    #[clippy::version = "1.68.0"]
+    pub DISALLOWED_PATTERNS,
+    style,
+    "use of an expression matching a pattern in the `disallowed-patterns` configuration"
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Pattern {
+    /// `_`
+    Any,
+    /// `$name` or `$name: path::to::Type`
+    Placeholder { name: String, ty: Option<String> },
+    /// `path::to::item`
+    Path(String),
+    /// `path::to::function(args)`
+    Call { path: String, args: Args },
+    /// `receiver.method(args)`
+    MethodCall {
+        receiver: Box<Pattern>,
+        method: String,
+        args: Args,
+    },
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct Args {
+    patterns: Vec<Pattern>,
+    /// Whether the arguments end with `..`
+    rest: bool,
+}
+
+impl Pattern {
+    fn parse(src: &str) -> Result<Self, String> {
+        let mut parser = Parser { rest: src };
+        let pattern = parser.expr()?;
+        parser.skip_whitespace();
+        if !parser.rest.is_empty() {
+            return Err(format!("unexpected `{}`", parser.rest));
+        }
+        if matches!(pattern, Self::Any | Self::Placeholder { .. }) {
+            return Err("the pattern has to be a path, a call or a method call".into());
+        }
+        Ok(pattern)
+    }
+
+    fn for_each_path<'a>(&'a self, f: &mut impl FnMut(&'a str)) {
+        match self {
+            Self::Any | Self::Placeholder { ty: None, .. } => {},
+            Self::Placeholder { ty: Some(path), .. } | Self::Path(path) => f(path),
+            Self::Call { path, args } => {
+                f(path);
+                args.patterns.iter().for_each(|arg| arg.for_each_path(f));
+            },
+            Self::MethodCall { receiver, args, .. } => {
+                receiver.for_each_path(f);
+                args.patterns.iter().for_each(|arg| arg.for_each_path(f));
+            },
+        }
+    }
+
+    fn has_placeholder(&self, placeholder: &str) -> bool {
+        match self {
+            Self::Any | Self::Path(_) => false,
+            Self::Placeholder { name, .. } => name == placeholder,
+            Self::Call { args, .. } => args.patterns.iter().any(|arg| arg.has_placeholder(placeholder)),
+            Self::MethodCall { receiver, args, .. } => {
+                receiver.has_placeholder(placeholder)
+                    || args.patterns.iter().any(|arg| arg.has_placeholder(placeholder))
+            },
+        }
+    }
+}
+
+struct Parser<'a> {
+    rest: &'a str,
+}
+
+impl<'a> Parser<'a> {
+    fn skip_whitespace(&mut self) {
+        self.rest = self.rest.trim_start();
+    }
+
+    fn eat(&mut self, token: &str) -> bool {
+        self.skip_whitespace();
+        if let Some(rest) = self.rest.strip_prefix(token) {
+            self.rest = rest;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, token: &str) -> Result<(), String> {
+        if self.eat(token) {
+            Ok(())
+        } else if self.rest.is_empty() {
+            Err(format!("expected `{token}`, found the end of the pattern"))
+        } else {
+            Err(format!("expected `{token}`, found `{}`", self.rest))
+        }
+    }
+
+    fn ident(&mut self) -> Result<&'a str, String> {
+        self.skip_whitespace();
+        let len = self
+            .rest
+            .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+            .unwrap_or(self.rest.len());
+        if len == 0 || self.rest.starts_with(|c: char| c.is_ascii_digit()) {
+            return Err(format!("expected an identifier, found `{}`", self.rest));
+        }
+        let (ident, rest) = self.rest.split_at(len);
+        self.rest = rest;
+        Ok(ident)
+    }
+
+    fn path(&mut self) -> Result<String, String> {
+        let mut path = self.ident()?.to_string();
+        while self.eat("::") {
+            path.push_str("::");
+            path.push_str(self.ident()?);
+        }
+        Ok(path)
+    }
+
+    /// Parses the arguments after the opening parenthesis.
+    fn args(&mut self) -> Result<Args, String> {
+        let mut args = Args {
+            patterns: Vec::new(),
+            rest: false,
+        };
+        while !self.eat(")") {
+            if self.eat("..") {
+                args.rest = true;
+                self.expect(")")?;
+                break;
+            }
+            args.patterns.push(self.expr()?);
+            if !self.eat(",") {
+                self.expect(")")?;
+                break;
+            }
+        }
+        Ok(args)
+    }
+
+    fn expr(&mut self) -> Result<Pattern, String> {
+        let mut pattern = self.atom()?;
+        while self.eat(".") {
+            let method = self.ident()?.to_string();
+            self.expect("(")?;
+            pattern = Pattern::MethodCall {
+                receiver: Box::new(pattern),
+                method,
+                args: self.args()?,
+            };
+        }
+        Ok(pattern)
+    }
+
+    fn atom(&mut self) -> Result<Pattern, String> {
+        if self.eat("(") {
+            let pattern = self.expr()?;
+            self.expect(")")?;
+            return Ok(pattern);
+        }
+        if self.eat("$") {
+            let name = self.ident()?.to_string();
+            let ty = if self.eat(":") { Some(self.path()?) } else { None };
+            return Ok(Pattern::Placeholder { name, ty });
+        }
+        let path = self.path()?;
+        if path == "_" {
+            Ok(Pattern::Any)
+        } else if self.eat("(") {
+            Ok(Pattern::Call {
+                path,
+                args: self.args()?,
+            })
+        } else {
+            Ok(Pattern::Path(path))
+        }
+    }
+}
+
+/// Checks if `template` only uses placeholders of `pattern`.
+fn check_replacement(pattern: &Pattern, template: &str) -> Result<(), String> {
+    for (_, placeholder) in placeholders(template) {
+        if !pattern.has_placeholder(placeholder) {
+            return Err(format!(
+                "the replacement uses `${placeholder}`, which is not in the pattern"
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// The placeholders in `template` with their byte offsets.
+fn placeholders(template: &str) -> impl Iterator<Item = (usize, &str)> {
+    template.match_indices('$').map(move |(start, _)| {
+        let name = &template[start + 1..];
+        let len = name
+            .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+            .unwrap_or(name.len());
+        (start, &name[..len])
+    })
+}
+
+struct Matcher<'a, 'tcx> {
+    cx: &'a LateContext<'tcx>,
+    paths: &'a FxHashMap<String, Vec<Res>>,
+    bindings: FxHashMap<&'a str, &'tcx Expr<'tcx>>,
+}
+
+impl<'a, 'tcx> Matcher<'a, 'tcx> {
+    fn is_path(&self, path: &str, res: Res) -> bool {
+        res.opt_def_id().map_or(false, |def_id| {
+            self.paths[path].iter().any(|res| res.opt_def_id() == Some(def_id))
+        })
+    }
+
+    fn has_type(&self, path: &str, expr: &Expr<'_>) -> bool {
+        let ty = self.cx.typeck_results().expr_ty(expr).peel_refs();
+        self.paths[path].iter().any(|res| match (res, ty.kind()) {
+            (Res::Def(_, def_id), ty::Adt(adt, _)) => adt.did() == *def_id,
+            (Res::PrimTy(prim), _) => ty.to_string() == prim.name_str(),
+            _ => false,
+        })
+    }
+
+    fn matches_args(&mut self, args: &'a Args, len: usize, mut exprs: impl Iterator<Item = &'tcx Expr<'tcx>>) -> bool {
+        let len_matches = if args.rest {
+            len >= args.patterns.len()
+        } else {
+            len == args.patterns.len()
+        };
+        len_matches
+            && args
+                .patterns
+                .iter()
+                .zip(&mut exprs)
+                .all(|(arg, expr)| self.matches(arg, expr))
+    }
+
+    fn matches(&mut self, pattern: &'a Pattern, expr: &'tcx Expr<'tcx>) -> bool {
+        match pattern {
+            Pattern::Any => true,
+            Pattern::Placeholder { name, ty } => {
+                if ty.as_ref().map_or(false, |ty| !self.has_type(ty, expr)) {
+                    return false;
+                }
+                if let Some(bound) = self.bindings.get(name.as_str()) {
+                    SpanlessEq::new(self.cx).eq_expr(bound, expr)
+                } else {
+                    self.bindings.insert(name, expr);
+                    true
+                }
+            },
+            Pattern::Path(path) => self.is_path(path, path_res(self.cx, expr)),
+            Pattern::Call { path, args } => match expr.kind {
+                ExprKind::Call(callee, exprs) => {
+                    self.is_path(path, path_res(self.cx, callee)) && self.matches_args(args, exprs.len(), exprs.iter())
+                },
+                // A method call of the function, with the receiver as the first argument
+                ExprKind::MethodCall(_, receiver, exprs, _) => {
+                    self.cx
+                        .typeck_results()
+                        .type_dependent_def_id(expr.hir_id)
+                        .map_or(false, |def_id| self.is_path(path, Res::Def(DefKind::AssocFn, def_id)))
+                        && self.matches_args(args, exprs.len() + 1, iter::once(receiver).chain(exprs))
+                },
+                _ => false,
+            },
+            Pattern::MethodCall { receiver, method, args } => {
+                if let ExprKind::MethodCall(segment, receiver_expr, exprs, _) = expr.kind {
+                    segment.ident.as_str() == method
+                        && self.matches(receiver, receiver_expr)
+                        && self.matches_args(args, exprs.len(), exprs.iter())
+                } else {
+                    false
+                }
+            },
+        }
+    }
+}
+
+fn is_inside(cx: &LateContext<'_>, expr: &Expr<'_>, context: PatternContext) -> bool {
+    for (_, node) in cx.tcx.hir().parent_iter(expr.hir_id) {
+        match node {
+            Node::Expr(Expr {
+                kind: ExprKind::Loop(..),
+                ..
+            }) if context == PatternContext::Loop => return true,
+            Node::Expr(Expr {
+                kind: ExprKind::Closure(Closure { body, .. }),
+                ..
+            }) => {
+                return context == PatternContext::Async
+                    && matches!(
+                        cx.tcx.hir().body(*body).generator_kind,
+                        Some(GeneratorKind::Async(AsyncGeneratorKind::Block | AsyncGeneratorKind::Fn))
+                    );
+            },
+            Node::Item(_) | Node::ImplItem(_) | Node::TraitItem(_) => return false,
+            _ => {},
+        }
+    }
+    false
+}
+
+pub struct DisallowedPatterns {
+    conf_disallowed: Vec<DisallowedPattern>,
+    /// The parsed patterns with their index in `conf_disallowed`.
+    patterns: Vec<(Pattern, usize)>,
+    /// The resolutions of the paths in the patterns.
+    paths: FxHashMap<String, Vec<Res>>,
+}
+
+impl DisallowedPatterns {
+    pub fn new(conf_disallowed: Vec<DisallowedPattern>) -> Self {
+        Self {
+            conf_disallowed,
+            patterns: Vec::new(),
+            paths: FxHashMap::default(),
+        }
+    }
+}
+
+impl_lint_pass!(DisallowedPatterns => [DISALLOWED_PATTERNS]);
+
+impl<'tcx> LateLintPass<'tcx> for DisallowedPatterns {
+    fn check_crate(&mut self, cx: &LateContext<'_>) {
+        for (index, conf) in self.conf_disallowed.iter().enumerate() {
+            let parsed = Pattern::parse(&conf.pattern).and_then(|pattern| {
+                if let Some(replacement) = &conf.replacement {
+                    check_replacement(&pattern, replacement)?;
+                }
+                Ok(pattern)
+            });
+            match parsed {
+                Ok(pattern) => {
+                    pattern.for_each_path(&mut |path| {
+                        self.paths.entry(path.to_string()).or_insert_with(|| {
+                            let segments: Vec<_> = path.split("::").collect();
+                            let resolutions = def_path_res(cx, &segments);
+                            if resolutions.iter().all(|res| *res == Res::Err) {
+                                cx.sess().warn(format!(
+                                    "`{path}` does not refer to an existing item, the pattern `{}` in \
+                                    `disallowed-patterns` never matches",
+                                    conf.pattern
+                                ));
+                            }
+                            resolutions
+                        });
+                    });
+                    self.patterns.push((pattern, index));
+                },
+                Err(error) => {
+                    cx.sess().err(format!(
+                        "invalid pattern `{}` in `disallowed-patterns`: {error}",
+                        conf.pattern
+                    ));
+                },
+            }
+        }
+    }
+
+    fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'tcx>) {
+        if expr.span.from_expansion() {
+            return;
+        }
+        for (pattern, index) in &self.patterns {
+            let conf = &self.conf_disallowed[*index];
+            let mut matcher = Matcher {
+                cx,
+                paths: &self.paths,
+                bindings: FxHashMap::default(),
+            };
+            if !matcher.matches(pattern, expr) || conf.inside.map_or(false, |context| !is_inside(cx, expr, context)) {
+                continue;
+            }
+
+            let msg = conf
+                .message
+                .clone()
+                .unwrap_or_else(|| format!("use of a disallowed pattern `{}`", conf.pattern));
+            span_lint_and_then(cx, DISALLOWED_PATTERNS, expr.span, &msg, |diag| {
+                if let Some(template) = &conf.replacement {
+                    let mut applicability = Applicability::MachineApplicable;
+                    let mut sugg = String::new();
+                    let mut last = 0;
+                    for (start, name) in placeholders(template) {
+                        sugg.push_str(&template[last..start]);
+                        let bound = matcher.bindings[name];
+                        sugg.push_str(&snippet_with_applicability(cx, bound.span, "..", &mut applicability));
+                        last = start + 1 + name.len();
+                    }
+                    sugg.push_str(&template[last..]);
+                    diag.span_suggestion(expr.span, "try", sugg, applicability);
+                }
+            });
+            return;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Args, Pattern};
+
+    fn placeholder(name: &str, ty: Option<&str>) -> Pattern {
+        Pattern::Placeholder {
+            name: name.into(),
+            ty: ty.map(Into::into),
+        }
+    }
+
+    #[test]
+    fn parse() {
+        assert_eq!(
+            Pattern::parse("std::fs::read_to_string(_).unwrap()"),
+            Ok(Pattern::MethodCall {
+                receiver: Box::new(Pattern::Call {
+                    path: "std::fs::read_to_string".into(),
+                    args: Args {
+                        patterns: vec![Pattern::Any],
+                        rest: false,
+                    },
+                }),
+                method: "unwrap".into(),
+                args: Args {
+                    patterns: vec![],
+                    rest: false,
+                },
+            })
+        );
+        assert_eq!(
+            Pattern::parse("$x: std::time::Instant.duration_since($x, ..)"),
+            Ok(Pattern::MethodCall {
+                receiver: Box::new(placeholder("x", Some("std::time::Instant"))),
+                method: "duration_since".into(),
+                args: Args {
+                    patterns: vec![placeholder("x", None)],
+                    rest: true,
+                },
+            })
+        );
+        assert_eq!(
+            Pattern::parse(" std :: f64 :: consts :: PI "),
+            Ok(Pattern::Path("std::f64::consts::PI".into()))
+        );
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(
+            Pattern::parse("$x"),
+            Err("the pattern has to be a path, a call or a method call".into())
+        );
+        assert_eq!(
+            Pattern::parse("std::env::var(_"),
+            Err("expected `)`, found the end of the pattern".into())
+        );
+        assert_eq!(Pattern::parse("x.unwrap() + 1"), Err("unexpected `+ 1`".into()));
+        assert_eq!(Pattern::parse("x.0"), Err("expected an identifier, found `0`".into()));
+    }
+}
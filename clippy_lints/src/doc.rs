@@ -1,9 +1,8 @@
 use clippy_utils::attrs::is_doc_hidden;
 use clippy_utils::diagnostics::{span_lint, span_lint_and_help, span_lint_and_note, span_lint_and_then};
-use clippy_utils::macros::{is_panic, root_macro_call_first_node};
 use clippy_utils::source::{first_line_of_span, snippet_with_applicability};
 use clippy_utils::ty::{implements_trait, is_type_diagnostic_item};
-use clippy_utils::{is_entrypoint_fn, method_chain_args, return_ty};
+use clippy_utils::{is_entrypoint_fn, method_chain_args, panic_source, return_ty, PanicSource};
 use if_chain::if_chain;
 use itertools::Itertools;
 use rustc_ast::ast::{Async, AttrKind, Attribute, Fn, FnRetTy, ItemKind};
@@ -557,6 +556,46 @@ fn check_attrs(cx: &LateContext<'_>, valid_idents: &FxHashSet<String>, attrs: &[
     Some(check_doc(cx, valid_idents, events, &spans))
 }
 
+/// Returns the markdown source of the `# Safety` section of the given documentation, if any.
+///
+/// This also works for items from other crates, as long as their attributes are passed in.
+pub(crate) fn safety_section(attrs: &[Attribute]) -> Option<String> {
+    use pulldown_cmark::Event::{End, Start, Text};
+    use pulldown_cmark::{Options, Parser, Tag};
+
+    let mut doc = String::new();
+    for attr in attrs {
+        if let Some((comment, comment_kind)) = attr.doc_str_and_comment_kind() {
+            let (comment, _) = strip_doc_comment_decoration(comment.as_str(), comment_kind, attr.span);
+            for line in comment.lines() {
+                doc.push_str(line.strip_prefix(' ').unwrap_or(line));
+                doc.push('\n');
+            }
+        }
+    }
+
+    let mut heading_text = String::new();
+    let mut section: Option<(u32, usize)> = None;
+    for (event, range) in Parser::new_ext(&doc, Options::empty()).into_offset_iter() {
+        match event {
+            Start(Tag::Heading(level, ..)) => {
+                if let Some((section_level, start)) = section
+                    && level as u32 <= section_level
+                {
+                    return Some(doc[start..range.start].trim().to_owned());
+                }
+                heading_text.clear();
+            },
+            Text(text) => heading_text.push_str(&text),
+            End(Tag::Heading(level, ..)) if section.is_none() && heading_text.trim() == "Safety" => {
+                section = Some((level as u32, range.end));
+            },
+            _ => {},
+        }
+    }
+    section.map(|(_, start)| doc[start..].trim().to_owned())
+}
+
 const RUST_CODE: &[&str] = &["rust", "no_run", "should_panic", "compile_fail"];
 
 fn check_doc<'a, Events: Iterator<Item = (pulldown_cmark::Event<'a>, Range<usize>)>>(
@@ -782,7 +821,7 @@ fn check_code(cx: &LateContext<'_>, text: &str, edition: Edition, span: Span) {
     let text = text.to_owned();
     if thread::spawn(move || has_needless_main(text, edition))
         .join()
-        .expect("thread::spawn failed")
+        .unwrap_or_else(|payload| std::panic::resume_unwind(payload))
     {
         span_lint(cx, NEEDLESS_DOCTEST_MAIN, span, "needless `fn main` in doctest");
     }
@@ -901,25 +940,14 @@ impl<'a, 'tcx> Visitor<'tcx> for FindPanicUnwrap<'a, 'tcx> {
             return;
         }
 
-        if let Some(macro_call) = root_macro_call_first_node(self.cx, expr) {
-            if is_panic(self.cx, macro_call.def_id)
-                || matches!(
-                    self.cx.tcx.item_name(macro_call.def_id).as_str(),
-                    "assert" | "assert_eq" | "assert_ne" | "todo"
-                )
-            {
-                self.panic_span = Some(macro_call.span);
-            }
-        }
-
-        // check for `unwrap`
-        if let Some(arglists) = method_chain_args(expr, &["unwrap"]) {
-            let receiver_ty = self.typeck_results.expr_ty(arglists[0].0).peel_refs();
-            if is_type_diagnostic_item(self.cx, receiver_ty, sym::Option)
-                || is_type_diagnostic_item(self.cx, receiver_ty, sym::Result)
-            {
-                self.panic_span = Some(expr.span);
-            }
+        match panic_source(self.cx, self.typeck_results, expr) {
+            Some((span, PanicSource::Macro(name))) if !matches!(name.as_str(), "unimplemented" | "unreachable") => {
+                self.panic_span = Some(span);
+            },
+            Some((span, PanicSource::Unwrap)) if method_chain_args(expr, &["unwrap"]).is_some() => {
+                self.panic_span = Some(span);
+            },
+            _ => {},
         }
 
         // and check sub-expressions
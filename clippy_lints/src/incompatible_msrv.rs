@@ -0,0 +1,147 @@
+use clippy_utils::diagnostics::span_lint;
+use clippy_utils::msrvs::{self, Msrv};
+use rustc_attr::{Stability, StabilityLevel};
+use rustc_data_structures::fx::FxHashMap;
+use rustc_hir::def_id::DefId;
+use rustc_hir::{Expr, ExprKind, Stmt, StmtKind};
+use rustc_lint::{LateContext, LateLintPass};
+use rustc_middle::ty::{DefIdTree, TyCtxt};
+use rustc_semver::RustcVersion;
+use rustc_session::{declare_tool_lint, impl_lint_pass};
+use rustc_span::Span;
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for uses of items from `std`, `core` and `alloc` which were stabilised in a
+    /// later Rust version than the configured MSRV, as well as `let ... else` statements
+    /// before Rust 1.65.
+    ///
+    /// The MSRV is read from the `msrv` configuration, the `rust-version` field of `Cargo.toml`
+    /// or a `#[clippy::msrv]` attribute on an enclosing item.
+    ///
+    /// ### Why is this bad?
+    /// The crate doesn't compile with the Rust version it claims to support, which is usually
+    /// only noticed once someone tries to build it with that version.
+    ///
+    /// ### Known problems
+    /// Only the stability of the referenced item itself is checked, not that of the traits
+    /// or language features it relies on. Uses inside of macro expansions are ignored.
+    ///
+    /// Only paths and method calls in expressions are checked. Items which are only named in
+    /// types, trait bounds or `impl` headers, as well as associated constants used in patterns,
+    /// are not.
+    ///
+    /// ### Example
+    /// With an MSRV of 1.59:
+    /// ```rust
+    /// fn f(b: bool) -> Option<i32> {
+    ///     b.then_some(1)
+    /// }
+    /// ```
+    ///
+    /// Use instead:
+    /// ```rust
+    /// fn f(b: bool) -> Option<i32> {
+    ///     b.then(|| 1)
+    /// }
+    /// ```
+    #[clippy::version = "1.68.0"]
+    pub INCOMPATIBLE_MSRV,
+    nursery,
+    "use of an item stabilised after the configured MSRV"
+}
+
+pub struct IncompatibleMsrv {
+    msrv: Msrv,
+    /// The version each item was stabilised in, `None` for unstable items.
+    stable_since: FxHashMap<DefId, Option<RustcVersion>>,
+}
+
+impl IncompatibleMsrv {
+    #[must_use]
+    pub fn new(msrv: Msrv) -> Self {
+        Self {
+            msrv,
+            stable_since: FxHashMap::default(),
+        }
+    }
+
+    /// Looks up the `#[stable(since = ...)]` attribute of `def_id`, or of its closest parent
+    /// which has one, e.g. for the items of a trait implementation.
+    fn stable_since(&mut self, tcx: TyCtxt<'_>, def_id: DefId) -> Option<RustcVersion> {
+        if let Some(&version) = self.stable_since.get(&def_id) {
+            return version;
+        }
+        let version = match tcx.lookup_stability(def_id) {
+            Some(Stability {
+                level: StabilityLevel::Stable { since, .. },
+                ..
+            }) => RustcVersion::parse(since.as_str()).ok(),
+            Some(_) => None,
+            None => tcx.opt_parent(def_id).and_then(|parent| self.stable_since(tcx, parent)),
+        };
+        self.stable_since.insert(def_id, version);
+        version
+    }
+
+    fn check_item_use(&mut self, cx: &LateContext<'_>, def_id: DefId, span: Span) {
+        if def_id.is_local() || span.from_expansion() {
+            return;
+        }
+        if let Some(current) = self.msrv.current()
+            && let Some(version) = self.stable_since(cx.tcx, def_id)
+            && !current.meets(version)
+        {
+            span_lint(
+                cx,
+                INCOMPATIBLE_MSRV,
+                span,
+                &format!(
+                    "`{}` is stable since Rust {version}, but the MSRV is {current}",
+                    cx.tcx.item_name(def_id)
+                ),
+            );
+        }
+    }
+}
+
+impl_lint_pass!(IncompatibleMsrv => [INCOMPATIBLE_MSRV]);
+
+impl<'tcx> LateLintPass<'tcx> for IncompatibleMsrv {
+    fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'_>) {
+        if self.msrv.current().is_none() {
+            return;
+        }
+        match expr.kind {
+            ExprKind::MethodCall(path, ..) => {
+                if let Some(def_id) = cx.typeck_results().type_dependent_def_id(expr.hir_id) {
+                    self.check_item_use(cx, def_id, path.ident.span);
+                }
+            },
+            ExprKind::Path(ref qpath) => {
+                if let Some(def_id) = cx.qpath_res(qpath, expr.hir_id).opt_def_id() {
+                    self.check_item_use(cx, def_id, expr.span);
+                }
+            },
+            _ => {},
+        }
+    }
+
+    fn check_stmt(&mut self, cx: &LateContext<'tcx>, stmt: &'tcx Stmt<'_>) {
+        if let StmtKind::Local(local) = stmt.kind
+            && local.els.is_some()
+            && !stmt.span.from_expansion()
+            && let Some(current) = self.msrv.current()
+            && !self.msrv.meets(msrvs::LET_ELSE)
+        {
+            span_lint(
+                cx,
+                INCOMPATIBLE_MSRV,
+                stmt.span,
+                &format!("`let ... else` is stable since Rust {}, but the MSRV is {current}", msrvs::LET_ELSE),
+            );
+        }
+    }
+
+    extract_msrv_attr!(LateContext);
+}
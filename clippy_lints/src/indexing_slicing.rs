@@ -1,19 +1,30 @@
 //! lint on indexing and slicing operations
 
+use clippy_utils::comparisons::{normalize_comparison, Rel};
 use clippy_utils::consts::{constant, Constant};
 use clippy_utils::diagnostics::{span_lint, span_lint_and_then};
-use clippy_utils::higher;
+use clippy_utils::ty::is_type_diagnostic_item;
+use clippy_utils::value_range::{for_each_dominating_condition, DominatingCondition};
+use clippy_utils::visitors::{for_each_expr_with_closures, Descend};
+use clippy_utils::{eq_expr_value, expr_or_init, higher, is_res_lang_ctor, path_to_local};
+use core::ops::ControlFlow;
 use rustc_ast::ast::RangeLimits;
-use rustc_hir::{Expr, ExprKind};
+use rustc_errors::Diagnostic;
+use rustc_hir::{BinOpKind, Expr, ExprKind, HirId, LangItem, Mutability, Node, PatKind, UnOp};
 use rustc_lint::{LateContext, LateLintPass};
-use rustc_middle::ty;
+use rustc_middle::ty::{self, Ty};
+use rustc_middle::ty::adjustment::{Adjust, AutoBorrow, AutoBorrowMutability};
 use rustc_session::{declare_tool_lint, impl_lint_pass};
+use rustc_span::{sym, BytePos, Span};
 
 declare_clippy_lint! {
     /// ### What it does
     /// Checks for out of bounds array indexing with a constant
     /// index.
     ///
+    /// Constant indexes into slices and vectors are checked as well when a
+    /// preceding check bounds their length, e.g. `if v.len() == 2 { v[2] }`.
+    ///
     /// ### Why is this bad?
     /// This will always panic at runtime.
     ///
@@ -45,6 +56,11 @@ declare_clippy_lint! {
     /// does report on arrays if we can tell that slicing operations are in bounds and does not
     /// lint on constant `usize` indexing on arrays because that is handled by rustc's `const_err` lint.
     ///
+    /// Indexes which are checked to be in bounds beforehand are not linted either, e.g. `v[i]` after
+    /// `if i < v.len()`, `assert!(i < v.len())` or in `for i in 0..v.len()`, and `v[1]` after
+    /// `if v.len() >= 2`, as long as neither `i` nor the length of `v` changes in between.
+    /// `debug_assert!`s are only accepted with the `allow-debug-assert-bounds-checks` configuration.
+    ///
     /// ### Why is this bad?
     /// Indexing and slicing can panic at runtime and there are
     /// safe alternatives.
@@ -87,18 +103,20 @@ impl_lint_pass!(IndexingSlicing => [INDEXING_SLICING, OUT_OF_BOUNDS_INDEXING]);
 #[derive(Copy, Clone)]
 pub struct IndexingSlicing {
     suppress_restriction_lint_in_const: bool,
+    allow_debug_assert_bounds_checks: bool,
 }
 
 impl IndexingSlicing {
-    pub fn new(suppress_restriction_lint_in_const: bool) -> Self {
+    pub fn new(suppress_restriction_lint_in_const: bool, allow_debug_assert_bounds_checks: bool) -> Self {
         Self {
             suppress_restriction_lint_in_const,
+            allow_debug_assert_bounds_checks,
         }
     }
 }
 
 impl<'tcx> LateLintPass<'tcx> for IndexingSlicing {
-    fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'_>) {
+    fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'tcx>) {
         if self.suppress_restriction_lint_in_const && cx.tcx.hir().is_inside_const_context(expr.hir_id) {
             return;
         }
@@ -155,12 +173,23 @@ impl<'tcx> LateLintPass<'tcx> for IndexingSlicing {
                     (None, None) => return, // [..] is ok.
                 };
 
+                let proof =
+                    Bound::of_range(range).map_or_else(Proof::default, |bound| self.prove(cx, expr, array, bound));
+                if proof.in_bounds {
+                    return;
+                }
+                if let Some(guard) = proof.out_of_bounds {
+                    lint_out_of_bounds(cx, expr, "range is out of bounds", guard);
+                    return;
+                }
+
                 span_lint_and_then(cx, INDEXING_SLICING, expr.span, "slicing may panic", |diag| {
                     diag.help(help_msg);
 
                     if cx.tcx.hir().is_inside_const_context(expr.hir_id) {
                         diag.note(note);
                     }
+                    proof.add_notes(diag);
                 });
             } else {
                 // Catchall non-range index, i.e., [n] or [n << m]
@@ -176,18 +205,521 @@ impl<'tcx> LateLintPass<'tcx> for IndexingSlicing {
                     }
                 }
 
+                let proof = self.prove(cx, expr, array, Bound::Below(index));
+                if proof.in_bounds {
+                    return;
+                }
+                if let Some(guard) = proof.out_of_bounds {
+                    lint_out_of_bounds(cx, expr, "index is out of bounds", guard);
+                    return;
+                }
+
                 span_lint_and_then(cx, INDEXING_SLICING, expr.span, "indexing may panic", |diag| {
                     diag.help("consider using `.get(n)` or `.get_mut(n)` instead");
 
                     if cx.tcx.hir().is_inside_const_context(expr.hir_id) {
                         diag.note(note);
                     }
+                    proof.add_notes(diag);
                 });
             }
         }
     }
 }
 
+fn lint_out_of_bounds(cx: &LateContext<'_>, expr: &Expr<'_>, msg: &str, guard: Span) {
+    span_lint_and_then(cx, OUT_OF_BOUNDS_INDEXING, expr.span, msg, |diag| {
+        diag.span_note(guard, "the length is bounded by this check");
+    });
+}
+
+/// Checks if the indexing or slicing `expr` may panic, as `INDEXING_SLICING` would lint it, but
+/// ignoring its configuration.
+pub(crate) fn index_may_panic<'tcx>(cx: &LateContext<'tcx>, expr: &'tcx Expr<'tcx>) -> bool {
+    let ExprKind::Index(array, index) = expr.kind else {
+        return false;
+    };
+    let pass = IndexingSlicing::new(false, false);
+    let ty = cx.typeck_results().expr_ty(array).peel_refs();
+    if let Some(range) = higher::Range::hir(index) {
+        if let ty::Array(_, s) = ty.kind()
+            && let Some(size) = s.try_eval_usize(cx.tcx, cx.param_env)
+            && let (Some(start), Some(end)) = to_const_range(cx, range, size.into())
+        {
+            return start > end || end > size.into();
+        }
+        if range.start.is_none() && range.end.is_none() {
+            return false;
+        }
+        !Bound::of_range(range).map_or(false, |bound| pass.prove(cx, expr, array, bound).in_bounds)
+    } else {
+        if matches!(ty.kind(), ty::Array(..))
+            && (matches!(index.kind, ExprKind::ConstBlock(..)) || constant(cx, cx.typeck_results(), index).is_some())
+        {
+            // Out of bounds constant indexes into arrays are rejected by rustc
+            return false;
+        }
+        !pass.prove(cx, expr, array, Bound::Below(index)).in_bounds
+    }
+}
+
+/// Checks if a check preceding `expr` proves that `array` isn't empty, e.g. `!array.is_empty()`,
+/// `array.len() > i`, `array.ends_with('_')` or `if let Some(x) = array.last()`, and the length
+/// isn't changed in between.
+pub(crate) fn is_proven_non_empty<'tcx>(
+    cx: &LateContext<'tcx>,
+    expr: &'tcx Expr<'tcx>,
+    array: &'tcx Expr<'tcx>,
+) -> bool {
+    let mut conditions = Vec::new();
+    for_each_dominating_condition(cx, expr, |condition| conditions.push(condition));
+    conditions.into_iter().any(|condition| {
+        // `s.ends_with('_')`
+        if let ExprKind::MethodCall(method, receiver, [pat], _) = condition.cond.kind
+            && matches!(method.ident.as_str(), "starts_with" | "ends_with")
+            && condition.truth
+            && cx.typeck_results().expr_ty(pat).is_char()
+            && is_same_place(cx, receiver, array)
+        {
+            return !condition.debug_assertion && is_unchanged(cx, expr, array, None, condition.cond.span.lo());
+        }
+        // `if let Some(x) = array.last()`
+        if let ExprKind::Let(let_expr) = condition.cond.kind
+            && condition.truth
+            && let PatKind::TupleStruct(ref qpath, ..) = let_expr.pat.kind
+            && is_res_lang_ctor(cx, cx.qpath_res(qpath, let_expr.pat.hir_id), LangItem::OptionSome)
+            && let ExprKind::MethodCall(method, receiver, [], _) = let_expr.init.kind
+            && matches!(method.ident.as_str(), "first" | "last")
+            && is_same_place(cx, peel_iter(receiver), array)
+        {
+            return is_unchanged(cx, expr, array, None, condition.cond.span.lo());
+        }
+        let Some((fact, len)) = len_fact(cx, condition, array) else {
+            return false;
+        };
+        let non_empty = match fact {
+            LenFact::NonEmpty | LenFact::Greater(_) => true,
+            LenFact::AtLeast(e) | LenFact::Equal(e) => const_int(cx, e).map_or(false, |e| e > 0),
+            _ => false,
+        };
+        let from = condition
+            .cond
+            .span
+            .lo()
+            .min(len.map_or(condition.cond.span.lo(), |len| len.span.lo()));
+        non_empty && !condition.debug_assertion && is_unchanged(cx, expr, array, None, from)
+    })
+}
+
+/// What the index must be compared with for the indexing to be in bounds.
+#[derive(Clone, Copy)]
+enum Bound<'tcx> {
+    /// `index < len`, for `x[index]` and `x[..=index]`.
+    Below(&'tcx Expr<'tcx>),
+    /// `index <= len`, for `x[..index]` and `x[index..]`.
+    AtMost(&'tcx Expr<'tcx>),
+}
+
+impl<'tcx> Bound<'tcx> {
+    /// `&x[..n]` and `&x[n..]` are in bounds if `n <= x.len()`, `&x[n..m]` also needs `n <= m`.
+    fn of_range(range: higher::Range<'tcx>) -> Option<Self> {
+        match (range.start, range.end, range.limits) {
+            (None, Some(bound), RangeLimits::HalfOpen) | (Some(bound), None, _) => Some(Self::AtMost(bound)),
+            (None, Some(end), RangeLimits::Closed) => Some(Self::Below(end)),
+            _ => None,
+        }
+    }
+
+    fn index(self) -> &'tcx Expr<'tcx> {
+        match self {
+            Self::Below(index) | Self::AtMost(index) => index,
+        }
+    }
+
+    /// The smallest length for which the constant `index` is in bounds.
+    fn min_len(self, index: u128) -> u128 {
+        match self {
+            Self::Below(_) => index.saturating_add(1),
+            Self::AtMost(_) => index,
+        }
+    }
+}
+
+/// What is known about the length of the indexed expression from a condition.
+#[derive(Clone, Copy)]
+enum LenFact<'tcx> {
+    /// `len > e`
+    Greater(&'tcx Expr<'tcx>),
+    /// `len >= e`
+    AtLeast(&'tcx Expr<'tcx>),
+    /// `len < e`
+    Less(&'tcx Expr<'tcx>),
+    /// `len <= e`
+    AtMost(&'tcx Expr<'tcx>),
+    /// `len == e`
+    Equal(&'tcx Expr<'tcx>),
+    /// `!x.is_empty()`
+    NonEmpty,
+    /// `x.is_empty()`
+    Empty,
+}
+
+/// What the checks preceding an indexing prove.
+#[derive(Default)]
+struct Proof {
+    in_bounds: bool,
+    /// The check proving that the constant index is out of bounds.
+    out_of_bounds: Option<Span>,
+    /// The checks which are related to the index, but which are not used, and why.
+    notes: Vec<(Span, &'static str)>,
+    /// Whether only a `debug_assert!` proves the index to be in bounds.
+    debug_assertion: bool,
+}
+
+impl Proof {
+    fn add_notes(&self, diag: &mut Diagnostic) {
+        for &(span, note) in &self.notes {
+            diag.span_note(span, note);
+        }
+        if self.debug_assertion {
+            diag.help("set `allow-debug-assert-bounds-checks = true` in `clippy.toml` to accept `debug_assert!`s");
+        }
+    }
+}
+
+impl IndexingSlicing {
+    /// Looks for a check of `bound` against the length of `array` preceding the indexing `expr`.
+    fn prove<'tcx>(
+        self,
+        cx: &LateContext<'tcx>,
+        expr: &'tcx Expr<'tcx>,
+        array: &'tcx Expr<'tcx>,
+        bound: Bound<'tcx>,
+    ) -> Proof {
+        let mut proof = Proof::default();
+        if !has_len_bound(cx, cx.typeck_results().expr_ty_adjusted(array)) {
+            return proof;
+        }
+        let index = bound.index();
+        let const_index = const_int(cx, index);
+
+        // `for i in 0..x.len() { x[i] }`
+        if const_index.is_none()
+            && let Some(id) = path_to_local(index)
+            && let Some((for_expr, for_loop)) = enclosing_for_loop(cx, id)
+            && let Some(range) = higher::Range::hir(peel_rev(for_loop.arg))
+            && let Some(end) = range.end
+            && is_len_of(cx, end, array)
+        {
+            if matches!((bound, range.limits), (Bound::Below(_), RangeLimits::Closed)) {
+                proof.notes.push((for_loop.arg.span, "this range doesn't prove that the index is in bounds"));
+            } else if is_unchanged(cx, expr, array, None, for_expr.span.lo()) {
+                proof.in_bounds = true;
+                return proof;
+            } else {
+                proof.notes.push((for_loop.arg.span, "this range is not used because the length may change in the loop"));
+            }
+        }
+
+        let mut conditions = Vec::new();
+        for_each_dominating_condition(cx, expr, |condition| conditions.push(condition));
+        for condition in conditions {
+            let Some((fact, len)) = len_fact(cx, condition, array) else {
+                continue;
+            };
+            // The length may be computed before the check, e.g. `let n = x.len(); if i < n {}`
+            let from = condition
+                .cond
+                .span
+                .lo()
+                .min(len.map_or(condition.cond.span.lo(), |len| len.span.lo()));
+
+            let (proves, disproves) = match const_index {
+                Some(const_index) => {
+                    let min_len = bound.min_len(const_index);
+                    let min = match fact {
+                        LenFact::Greater(e) => const_int(cx, e).map(|e| e.saturating_add(1)),
+                        LenFact::AtLeast(e) | LenFact::Equal(e) => const_int(cx, e),
+                        LenFact::NonEmpty => Some(1),
+                        _ => None,
+                    };
+                    let max = match fact {
+                        LenFact::Less(e) => const_int(cx, e).and_then(|e| e.checked_sub(1)),
+                        LenFact::AtMost(e) | LenFact::Equal(e) => const_int(cx, e),
+                        LenFact::Empty => Some(0),
+                        _ => None,
+                    };
+                    if min.map_or(false, |min| min < min_len) {
+                        proof.notes.push((
+                            condition.cond.span,
+                            "this check doesn't prove that the index is in bounds",
+                        ));
+                    }
+                    (
+                        min.map_or(false, |min| min >= min_len),
+                        max.map_or(false, |max| max < min_len),
+                    )
+                },
+                None => match (fact, bound) {
+                    (LenFact::Greater(e), _) | (LenFact::AtLeast(e), Bound::AtMost(_))
+                        if eq_expr_value(cx, e, index) =>
+                    {
+                        (true, false)
+                    },
+                    (LenFact::AtLeast(e), Bound::Below(_)) if eq_expr_value(cx, e, index) => {
+                        proof.notes.push((
+                            condition.cond.span,
+                            "this check doesn't prove that the index is in bounds",
+                        ));
+                        (false, false)
+                    },
+                    _ => (false, false),
+                },
+            };
+            if !proves && !disproves {
+                continue;
+            }
+
+            if condition.debug_assertion && !self.allow_debug_assert_bounds_checks {
+                proof.notes.push((
+                    condition.cond.span,
+                    "this `debug_assert!` isn't checked in release builds",
+                ));
+                proof.debug_assertion |= proves;
+            } else if !is_unchanged(cx, expr, array, const_index.is_none().then_some(index), from) {
+                proof.notes.push((
+                    condition.cond.span,
+                    "this check is not used because the index or the length may change before the indexing",
+                ));
+            } else if proves {
+                proof.in_bounds = true;
+                return proof;
+            } else {
+                proof.out_of_bounds = Some(condition.cond.span);
+                return proof;
+            }
+        }
+        proof
+    }
+}
+
+/// What `condition` tells about the length of `array`, and the call to `len` it uses, if any.
+fn len_fact<'tcx>(
+    cx: &LateContext<'tcx>,
+    condition: DominatingCondition<'tcx>,
+    array: &Expr<'_>,
+) -> Option<(LenFact<'tcx>, Option<&'tcx Expr<'tcx>>)> {
+    let cond = condition.cond;
+    if let ExprKind::MethodCall(method, receiver, [], _) = cond.kind
+        && method.ident.name.as_str() == "is_empty"
+        && is_same_place(cx, receiver, array)
+    {
+        let fact = if condition.truth { LenFact::Empty } else { LenFact::NonEmpty };
+        return Some((fact, None));
+    }
+
+    let ExprKind::Binary(op, lhs, rhs) = cond.kind else {
+        return None;
+    };
+    let op = if condition.truth { op.node } else { negate(op.node)? };
+    let (rel, lhs, rhs) = normalize_comparison(op, lhs, rhs)?;
+    let (len, other, len_on_left) = if is_len_of(cx, lhs, array) {
+        (lhs, rhs, true)
+    } else if is_len_of(cx, rhs, array) {
+        (rhs, lhs, false)
+    } else {
+        return None;
+    };
+    let fact = match (rel, len_on_left) {
+        (Rel::Lt, true) => LenFact::Less(other),
+        (Rel::Le, true) => LenFact::AtMost(other),
+        (Rel::Lt, false) => LenFact::Greater(other),
+        (Rel::Le, false) => LenFact::AtLeast(other),
+        (Rel::Eq, _) => LenFact::Equal(other),
+        (Rel::Ne, _) if const_int(cx, other) == Some(0) => LenFact::NonEmpty,
+        (Rel::Ne, _) => return None,
+    };
+    Some((fact, Some(expr_or_init(cx, len))))
+}
+
+/// The comparison which is true when `op` is false.
+fn negate(op: BinOpKind) -> Option<BinOpKind> {
+    Some(match op {
+        BinOpKind::Lt => BinOpKind::Ge,
+        BinOpKind::Le => BinOpKind::Gt,
+        BinOpKind::Gt => BinOpKind::Le,
+        BinOpKind::Ge => BinOpKind::Lt,
+        BinOpKind::Eq => BinOpKind::Ne,
+        BinOpKind::Ne => BinOpKind::Eq,
+        _ => return None,
+    })
+}
+
+fn const_int(cx: &LateContext<'_>, expr: &Expr<'_>) -> Option<u128> {
+    match constant(cx, cx.typeck_results(), expr) {
+        Some((Constant::Int(value), _)) => Some(value),
+        _ => None,
+    }
+}
+
+/// Checks if the indexes of `ty` are bounded by its `len()`, which is only known for slices,
+/// arrays, `str`, `Vec`, `VecDeque` and `String`. Other types may have a `len` method unrelated to
+/// their `Index` implementation.
+fn has_len_bound<'tcx>(cx: &LateContext<'tcx>, ty: Ty<'tcx>) -> bool {
+    let ty = ty.peel_refs();
+    matches!(ty.kind(), ty::Slice(_) | ty::Array(..) | ty::Str)
+        || [sym::Vec, sym::VecDeque, sym::String]
+            .into_iter()
+            .any(|name| is_type_diagnostic_item(cx, ty, name))
+}
+
+/// Checks if `expr` is `array.len()`, or a local initialized with it.
+fn is_len_of(cx: &LateContext<'_>, expr: &Expr<'_>, array: &Expr<'_>) -> bool {
+    if let ExprKind::MethodCall(method, receiver, [], _) = expr_or_init(cx, expr).kind
+        && method.ident.name == sym::len
+        && has_len_bound(cx, cx.typeck_results().expr_ty_adjusted(receiver))
+    {
+        is_same_place(cx, receiver, array)
+    } else {
+        false
+    }
+}
+
+/// Checks if `a` and `b` are the same place, ignoring borrows and dereferences.
+fn is_same_place(cx: &LateContext<'_>, a: &Expr<'_>, b: &Expr<'_>) -> bool {
+    eq_expr_value(cx, peel_borrows(a), peel_borrows(b))
+}
+
+/// Peels `.iter()` off `expr`, the first and last items of the iterator are those of the
+/// collection.
+fn peel_iter<'tcx>(expr: &'tcx Expr<'tcx>) -> &'tcx Expr<'tcx> {
+    match expr.kind {
+        ExprKind::MethodCall(method, receiver, [], _) if method.ident.name == sym::iter => receiver,
+        _ => expr,
+    }
+}
+
+fn peel_borrows<'tcx>(mut expr: &'tcx Expr<'tcx>) -> &'tcx Expr<'tcx> {
+    while let ExprKind::AddrOf(_, _, inner) | ExprKind::Unary(UnOp::Deref, inner) = expr.kind {
+        expr = inner;
+    }
+    expr
+}
+
+/// Removes the `.rev()` from the iterator of a `for` loop.
+fn peel_rev<'tcx>(expr: &'tcx Expr<'tcx>) -> &'tcx Expr<'tcx> {
+    match expr.kind {
+        ExprKind::MethodCall(method, receiver, [], _) if method.ident.name.as_str() == "rev" => receiver,
+        _ => expr,
+    }
+}
+
+/// The `for` loop binding the local `id` as its pattern.
+fn enclosing_for_loop<'tcx>(cx: &LateContext<'tcx>, id: HirId) -> Option<(&'tcx Expr<'tcx>, higher::ForLoop<'tcx>)> {
+    let (expr, for_loop) = cx.tcx.hir().parent_iter(id).find_map(|(_, node)| match node {
+        Node::Expr(expr) => higher::ForLoop::hir(expr).map(|for_loop| (expr, for_loop)),
+        _ => None,
+    })?;
+    (for_loop.pat.hir_id == id).then_some((expr, for_loop))
+}
+
+/// Checks that the length of `array` and the places `index` depends on aren't changed after
+/// `from` and before the indexing `expr`, nor in a loop starting after `from` and containing
+/// `expr`.
+fn is_unchanged<'tcx>(
+    cx: &LateContext<'tcx>,
+    expr: &'tcx Expr<'tcx>,
+    array: &'tcx Expr<'tcx>,
+    index: Option<&'tcx Expr<'tcx>>,
+    from: BytePos,
+) -> bool {
+    let Some(body) = cx.enclosing_body else {
+        return true;
+    };
+    let body = cx.tcx.hir().body(body);
+    let array = peel_borrows(array);
+    // Only the length of growable collections may change through a `&mut`
+    let growable = !matches!(
+        cx.typeck_results().expr_ty(array).peel_refs().kind(),
+        ty::Slice(_) | ty::Array(..) | ty::Str
+    );
+    let mut places = Vec::new();
+    if let Some(index) = index {
+        let _: Option<!> = for_each_expr_with_closures(cx, index, |e| match e.kind {
+            ExprKind::Path(_) | ExprKind::Field(..) => {
+                places.push(e);
+                ControlFlow::Continue(Descend::No)
+            },
+            _ => ControlFlow::Continue(Descend::Yes),
+        });
+    }
+
+    let mut loops = Vec::new();
+    let mut mutations = Vec::new();
+    let _: Option<!> = for_each_expr_with_closures(cx, body.value, |e| {
+        match e.kind {
+            ExprKind::Loop(..) if e.span.lo() >= from && e.span.contains(expr.span) => loops.push(e.span),
+            // The place is written after the right-hand side is evaluated
+            ExprKind::Assign(place, ..) | ExprKind::AssignOp(_, place, _) => mutations.push((place, true, e.span)),
+            ExprKind::AddrOf(_, Mutability::Mut, place) => mutations.push((place, false, place.span)),
+            _ => {},
+        }
+        if is_mut_borrowed(cx, e) {
+            mutations.push((e, false, e.span));
+        }
+        ControlFlow::Continue(())
+    });
+
+    !mutations.into_iter().any(|(place, assigned, span)| {
+        // Changing an element doesn't change the length
+        let changes_array = (assigned || growable) && is_prefix(cx, place, array);
+        (changes_array || places.iter().any(|index_place| overlaps(cx, place, index_place)))
+            && ((span.lo() >= from && span.hi() <= expr.span.lo()) || loops.iter().any(|l| l.contains(span)))
+    })
+}
+
+/// Checks if `expr` is implicitly borrowed mutably, as the receiver of a method which isn't
+/// reached through `Deref`, e.g. `v.push(x)` but not `v.sort()` on a `Vec`, or as the base of an
+/// indexing expression.
+fn is_mut_borrowed(cx: &LateContext<'_>, expr: &Expr<'_>) -> bool {
+    if let Some(Node::Expr(parent)) = cx.tcx.hir().find_parent(expr.hir_id)
+        && let ExprKind::Index(base, _) = parent.kind
+        && base.hir_id == expr.hir_id
+    {
+        return false;
+    }
+    let adjustments = cx.typeck_results().expr_adjustments(expr);
+    adjustments.iter().any(|adjustment| {
+        matches!(
+            adjustment.kind,
+            Adjust::Borrow(AutoBorrow::Ref(_, AutoBorrowMutability::Mut { .. }))
+        )
+    }) && !adjustments
+        .iter()
+        .any(|adjustment| matches!(adjustment.kind, Adjust::Deref(Some(_))))
+}
+
+/// Checks if changing `a` may change `b` or the other way around, e.g. `x` and `x.y`.
+fn overlaps(cx: &LateContext<'_>, a: &Expr<'_>, b: &Expr<'_>) -> bool {
+    is_prefix(cx, a, b) || is_prefix(cx, b, a)
+}
+
+fn is_prefix(cx: &LateContext<'_>, prefix: &Expr<'_>, mut place: &Expr<'_>) -> bool {
+    let prefix = peel_borrows(prefix);
+    loop {
+        if eq_expr_value(cx, prefix, place) {
+            return true;
+        }
+        match place.kind {
+            ExprKind::Field(base, _)
+            | ExprKind::Index(base, _)
+            | ExprKind::Unary(UnOp::Deref, base)
+            | ExprKind::AddrOf(_, _, base) => place = base,
+            _ => return false,
+        }
+    }
+}
+
 /// Returns a tuple of options with the start and end (exclusive) values of
 /// the range. If the start or end is not constant, None is returned.
 fn to_const_range(cx: &LateContext<'_>, range: higher::Range<'_>, array_size: u128) -> (Option<u128>, Option<u128>) {
@@ -0,0 +1,175 @@
+use clippy_utils::diagnostics::span_lint_and_then;
+use clippy_utils::get_parent_expr;
+use rustc_data_structures::fx::FxHashSet;
+use rustc_hir::{AsyncGeneratorKind, Closure, Expr, ExprKind, GeneratorKind, QPath};
+use rustc_lint::{LateContext, LateLintPass, LintContext};
+use rustc_middle::lint::in_external_macro;
+use rustc_middle::mir::{GeneratorLayout, GeneratorSavedLocal, Place, ProjectionElem, VarDebugInfoContents};
+use rustc_middle::ty::layout::LayoutOf;
+use rustc_session::{declare_tool_lint, impl_lint_pass};
+use rustc_span::{sym, Span};
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for `async fn`s, and `async` blocks passed to spawn-like functions (`spawn`,
+    /// `spawn_local`, `spawn_on` and `block_on`), whose future is larger than the
+    /// `future-size-threshold` configuration, 16 KiB by default.
+    ///
+    /// ### Why is this bad?
+    /// The future of an `async fn` holds all the values that live across an `.await`, including
+    /// the futures it awaits. Large futures are expensive to move, and awaiting them in a deep
+    /// chain can overflow the stack.
+    ///
+    /// ### Example
+    /// ```rust
+    /// async fn wait(buffer: [u8; 32 * 1024]) {
+    ///     std::future::ready(()).await;
+    ///     drop(buffer);
+    /// }
+    /// ```
+    ///
+    /// Use instead:
+    /// ```rust
+    /// async fn wait(buffer: Box<[u8; 32 * 1024]>) {
+    ///     std::future::ready(()).await;
+    ///     drop(buffer);
+    /// }
+    /// ```
+    #[clippy::version = "1.68.0"]
+    pub LARGE_FUTURES,
+    pedantic,
+    "futures of `async fn`s or spawned `async` blocks that are too large"
+}
+
+/// The functions `async` blocks are checked as arguments of.
+const SPAWN_FNS: &[&str] = &["spawn", "spawn_local", "spawn_on", "block_on"];
+
+/// The number of values held across await points pointed out by the lint.
+const MAX_NOTES: usize = 3;
+
+pub struct LargeFutures {
+    future_size_threshold: u64,
+}
+
+impl LargeFutures {
+    #[must_use]
+    pub fn new(future_size_threshold: u64) -> Self {
+        Self { future_size_threshold }
+    }
+}
+
+impl_lint_pass!(LargeFutures => [LARGE_FUTURES]);
+
+impl<'tcx> LateLintPass<'tcx> for LargeFutures {
+    fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'tcx>) {
+        let ExprKind::Closure(&Closure { body, .. }) = expr.kind else {
+            return;
+        };
+        let body = cx.tcx.hir().body(body);
+        let span = match body.generator_kind {
+            Some(GeneratorKind::Async(AsyncGeneratorKind::Fn)) => {
+                // Point at the signature of the `async fn`.
+                let owner = cx.tcx.hir().get_parent_item(expr.hir_id);
+                cx.tcx.def_span(owner)
+            },
+            Some(GeneratorKind::Async(AsyncGeneratorKind::Block)) if is_spawned(cx, expr) => expr.span,
+            _ => return,
+        };
+        if in_external_macro(cx.sess(), span) {
+            return;
+        }
+        let Ok(layout) = cx.layout_of(cx.typeck_results().expr_ty(expr)) else {
+            return;
+        };
+        let size = layout.size.bytes();
+        if size <= self.future_size_threshold {
+            return;
+        }
+
+        // The values held across await points, largest first. The debug info of the generator's
+        // MIR tells which of the stored values are bindings of the body.
+        let def_id = cx.tcx.hir().local_def_id(expr.hir_id).to_def_id();
+        let mir = cx.tcx.optimized_mir(def_id);
+        let Some(generator_layout) = mir.generator_layout() else {
+            return;
+        };
+        let mut seen = FxHashSet::default();
+        let mut held: Vec<(Span, String, u64)> = Vec::new();
+        for info in &mir.var_debug_info {
+            // The future being awaited, which is stored by every `.await`.
+            if info.name == sym::__awaitee {
+                continue;
+            }
+            if let VarDebugInfoContents::Place(place) = info.value
+                && let Some(local) = saved_local(generator_layout, place)
+                && seen.insert(local)
+                && let ty = cx.tcx.erase_regions(generator_layout.field_tys[local])
+                && let Ok(layout) = cx.layout_of(ty)
+                && !layout.is_zst()
+            {
+                held.push((info.source_info.span, ty.to_string(), layout.size.bytes()));
+            }
+        }
+        held.sort_by(|a, b| b.2.cmp(&a.2));
+
+        span_lint_and_then(
+            cx,
+            LARGE_FUTURES,
+            span,
+            &format!(
+                "this future is {size} bytes, larger than the limit of {} bytes",
+                self.future_size_threshold
+            ),
+            |diag| {
+                // Values that are small compared to the future aren't worth pointing out.
+                let large = held.into_iter().filter(|&(.., held_size)| held_size * 10 >= size);
+                for (span, ty, size) in large.take(MAX_NOTES) {
+                    diag.span_note(
+                        span,
+                        &format!("this value of type `{ty}` is {size} bytes and is held across an await point"),
+                    );
+                }
+                diag.help("consider moving large values to the heap, or boxing large futures with `Box::pin`");
+            },
+        );
+    }
+}
+
+/// Checks if the expression is an argument of a spawn-like function or method.
+fn is_spawned<'tcx>(cx: &LateContext<'tcx>, mut expr: &'tcx Expr<'tcx>) -> bool {
+    // `async` blocks are lowered to a closure wrapped in another expression with the same span.
+    let Some(mut parent) = get_parent_expr(cx, expr) else {
+        return false;
+    };
+    while parent.span == expr.span {
+        expr = parent;
+        let Some(next) = get_parent_expr(cx, expr) else {
+            return false;
+        };
+        parent = next;
+    }
+    let name = match parent.kind {
+        ExprKind::Call(callee, args) if args.iter().any(|arg| arg.hir_id == expr.hir_id) => match callee.kind {
+            ExprKind::Path(QPath::Resolved(_, path)) => path.segments.last().map(|seg| seg.ident.name),
+            ExprKind::Path(QPath::TypeRelative(_, seg)) => Some(seg.ident.name),
+            _ => None,
+        },
+        ExprKind::MethodCall(seg, _, args, _) if args.iter().any(|arg| arg.hir_id == expr.hir_id) => {
+            Some(seg.ident.name)
+        },
+        _ => None,
+    };
+    name.map_or(false, |name| SPAWN_FNS.contains(&name.as_str()))
+}
+
+/// Returns the value stored in the generator that the place, from the generator's MIR, refers to.
+fn saved_local(layout: &GeneratorLayout<'_>, place: Place<'_>) -> Option<GeneratorSavedLocal> {
+    match place.projection.as_slice() {
+        &[
+            ..,
+            ProjectionElem::Downcast(_, variant),
+            ProjectionElem::Field(field, _),
+        ] => layout.variant_fields.get(variant)?.get(field).copied(),
+        _ => None,
+    }
+}
@@ -26,6 +26,7 @@
 extern crate rustc_arena;
 extern crate rustc_ast;
 extern crate rustc_ast_pretty;
+extern crate rustc_attr;
 extern crate rustc_data_structures;
 extern crate rustc_driver;
 extern crate rustc_errors;
@@ -50,7 +51,7 @@ extern crate clippy_utils;
 extern crate declare_clippy_lint;
 
 use std::io;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use clippy_utils::msrvs::Msrv;
 use rustc_data_structures::fx::FxHashSet;
@@ -63,6 +64,7 @@ pub mod deprecated_lints;
 mod utils;
 
 mod declared_lints;
+mod pass_lints;
 mod renamed_lints;
 
 // begin lints modules, do not remove this comment, it’s used in `update_lints`
@@ -73,8 +75,10 @@ mod asm_syntax;
 mod assertions_on_constants;
 mod assertions_on_result_states;
 mod async_yields_async;
+mod atomic_ordering;
 mod attrs;
 mod await_holding_invalid;
+mod blocking_in_async;
 mod blocks_in_if_conditions;
 mod bool_assert_comparison;
 mod bool_to_int_with_if;
@@ -102,6 +106,7 @@ mod derive;
 mod disallowed_macros;
 mod disallowed_methods;
 mod disallowed_names;
+mod disallowed_patterns;
 mod disallowed_script_idents;
 mod disallowed_types;
 mod doc;
@@ -143,6 +148,7 @@ mod implicit_hasher;
 mod implicit_return;
 mod implicit_saturating_add;
 mod implicit_saturating_sub;
+mod incompatible_msrv;
 mod inconsistent_struct_constructor;
 mod index_refutable_slice;
 mod indexing_slicing;
@@ -159,6 +165,7 @@ mod items_after_statements;
 mod iter_not_returning_iterator;
 mod large_const_arrays;
 mod large_enum_variant;
+mod large_futures;
 mod large_include_file;
 mod large_stack_arrays;
 mod len_zero;
@@ -229,6 +236,7 @@ mod operators;
 mod option_env_unwrap;
 mod option_if_let_else;
 mod overflow_check_conditional;
+mod panic_in_extern_fn;
 mod panic_in_result_fn;
 mod panic_unimplemented;
 mod partial_pub_fields;
@@ -272,6 +280,7 @@ mod slow_vector_initialization;
 mod std_instead_of_core;
 mod strings;
 mod strlen_on_c_strings;
+mod struct_padding;
 mod suspicious_operation_groupings;
 mod suspicious_trait_impl;
 mod suspicious_xor_used_as_pow;
@@ -279,6 +288,8 @@ mod swap;
 mod swap_ptr_to_ref;
 mod tabs_in_doc_comments;
 mod temporary_assignment;
+mod threads_and_channels;
+mod time;
 mod to_digit_is_some;
 mod trailing_empty_array;
 mod trait_bounds;
@@ -314,6 +325,7 @@ mod zero_div_zero;
 mod zero_sized_map_values;
 // end lints modules, do not remove this comment, it’s used in `update_lints`
 
+pub use crate::utils::cache_record::CacheRecord;
 use crate::utils::conf::{format_error, TryConf};
 pub use crate::utils::conf::{lookup_conf_file, Conf};
 
@@ -455,6 +467,12 @@ pub fn explain(name: &str) {
     }
 }
 
+/// Checks if `name` is the name of a Clippy lint, e.g. `needless_return`.
+pub fn is_lint(name: &str) -> bool {
+    let target = format!("clippy::{}", name.to_ascii_uppercase());
+    declared_lints::LINTS.iter().any(|info| info.lint.name == target)
+}
+
 fn register_categories(store: &mut rustc_lint::LintStore) {
     let mut groups = RegistrationGroups::default();
 
@@ -479,6 +497,8 @@ fn register_categories(store: &mut rustc_lint::LintStore) {
 pub fn register_plugins(store: &mut rustc_lint::LintStore, sess: &Session, conf: &Conf) {
     register_removed_non_tool_lints(store);
     register_categories(store);
+    // The passes registered below, which may be skipped if their lints are disabled
+    let first_passes = (store.late_passes.len(), store.late_module_passes.len());
 
     include!("lib.deprecated.rs");
 
@@ -515,6 +535,7 @@ pub fn register_plugins(store: &mut rustc_lint::LintStore, sess: &Session, conf:
     let arithmetic_side_effects_allowed = conf.arithmetic_side_effects_allowed.clone();
     let arithmetic_side_effects_allowed_binary = conf.arithmetic_side_effects_allowed_binary.clone();
     let arithmetic_side_effects_allowed_unary = conf.arithmetic_side_effects_allowed_unary.clone();
+    let arithmetic_side_effects_value_ranges = conf.arithmetic_side_effects_value_ranges;
     store.register_late_pass(move |_| {
         Box::new(operators::arithmetic_side_effects::ArithmeticSideEffects::new(
             arithmetic_side_effects_allowed
@@ -527,6 +548,7 @@ pub fn register_plugins(store: &mut rustc_lint::LintStore, sess: &Session, conf:
                 .chain(arithmetic_side_effects_allowed_unary.iter())
                 .cloned()
                 .collect(),
+            arithmetic_side_effects_value_ranges,
         ))
     });
     store.register_late_pass(|_| Box::new(utils::dump_hir::DumpHir));
@@ -560,7 +582,8 @@ pub fn register_plugins(store: &mut rustc_lint::LintStore, sess: &Session, conf:
     store.register_late_pass(|_| Box::new(mut_mut::MutMut));
     store.register_late_pass(|_| Box::new(mut_reference::UnnecessaryMutPassed));
     store.register_late_pass(|_| Box::new(len_zero::LenZero));
-    store.register_late_pass(|_| Box::new(attrs::Attributes));
+    let blanket_restriction_groups = blanket_restriction_groups(conf);
+    store.register_late_pass(move |_| Box::new(attrs::Attributes::new(blanket_restriction_groups.clone())));
     store.register_late_pass(|_| Box::new(blocks_in_if_conditions::BlocksInIfConditions));
     store.register_late_pass(|_| Box::new(unicode::Unicode));
     store.register_late_pass(|_| Box::new(uninit_vec::UninitVec));
@@ -579,6 +602,7 @@ pub fn register_plugins(store: &mut rustc_lint::LintStore, sess: &Session, conf:
     let allow_expect_in_tests = conf.allow_expect_in_tests;
     let allow_unwrap_in_tests = conf.allow_unwrap_in_tests;
     let suppress_restriction_lint_in_const = conf.suppress_restriction_lint_in_const;
+    let allow_debug_assert_bounds_checks = conf.allow_debug_assert_bounds_checks;
     store.register_late_pass(move |_| Box::new(approx_const::ApproxConstant::new(msrv())));
     store.register_late_pass(move |_| {
         Box::new(methods::Methods::new(
@@ -703,6 +727,7 @@ pub fn register_plugins(store: &mut rustc_lint::LintStore, sess: &Session, conf:
     store.register_late_pass(move |_| {
         Box::new(indexing_slicing::IndexingSlicing::new(
             suppress_restriction_lint_in_const,
+            allow_debug_assert_bounds_checks,
         ))
     });
     store.register_late_pass(|_| Box::new(non_copy_const::NonCopyConst));
@@ -912,7 +937,70 @@ pub fn register_plugins(store: &mut rustc_lint::LintStore, sess: &Session, conf:
     store.register_late_pass(|_| Box::new(size_of_ref::SizeOfRef));
     store.register_late_pass(|_| Box::new(multiple_unsafe_ops_per_block::MultipleUnsafeOpsPerBlock));
     store.register_late_pass(|_| Box::new(paths_from_format::PathsFromFormat));
+    let blocking_paths = conf.blocking_paths.clone();
+    store.register_late_pass(move |_| Box::new(blocking_in_async::BlockingInAsync::new(blocking_paths.clone())));
+    let future_size_threshold = conf.future_size_threshold;
+    store.register_late_pass(move |_| Box::new(large_futures::LargeFutures::new(future_size_threshold)));
+    store.register_late_pass(move |_| Box::new(incompatible_msrv::IncompatibleMsrv::new(msrv())));
+    let disallowed_patterns = conf.disallowed_patterns.clone();
+    store.register_late_pass(move |_| {
+        Box::new(disallowed_patterns::DisallowedPatterns::new(
+            disallowed_patterns.clone(),
+        ))
+    });
+    store.register_late_pass(|_| Box::<panic_in_extern_fn::PanicInExternFn>::default());
+    let struct_padding_threshold = conf.struct_padding_threshold;
+    store.register_late_pass(move |_| Box::new(struct_padding::StructPadding::new(struct_padding_threshold)));
+    store.register_late_pass(|_| Box::<atomic_ordering::AtomicOrdering>::default());
+    store.register_late_pass(|_| Box::new(time::Time));
+    let unbounded_channel_constructors = conf.unbounded_channel_constructors.clone();
+    store.register_late_pass(move |_| {
+        Box::new(threads_and_channels::ThreadsAndChannels::new(
+            unbounded_channel_constructors.clone(),
+        ))
+    });
     // add lints here, do not remove this comment, it's used in `new_lint`
+
+    // This has to stay the last late pass, so every other lint is emitted before it checks which
+    // `allow` attributes were used.
+    store.register_late_pass(|_| Box::new(attrs::UnusedClippyAllow));
+
+    utils::disabled_passes::skip_passes(store, first_passes);
+}
+
+/// Register the pass collecting the report of `cargo clippy --unsafe-report`
+///
+/// Used in `./src/driver.rs`.
+pub fn register_unsafe_report(store: &mut rustc_lint::LintStore, sess: &Session, format: &str) {
+    use utils::unsafe_report::{ReportFormat, UnsafeReport};
+
+    match ReportFormat::parse(format) {
+        Some(format) => store.register_late_pass(move |_| Box::new(UnsafeReport::new(format))),
+        None => {
+            sess.err(format!(
+                "unknown unsafe report format `{format}`, expected `json` or `markdown`"
+            ));
+        },
+    }
+}
+
+/// Register the pass of `UNUSED_PUB_ITEMS` for the given `--unused-pub` phase.
+///
+/// Used in `./src/driver.rs`.
+pub fn register_unused_pub(store: &mut rustc_lint::LintStore, sess: &Session, conf: &Conf, phase: &str) {
+    use cargo::{UnusedPubItems, UnusedPubPhase};
+
+    match UnusedPubPhase::parse(phase) {
+        Some(phase) => {
+            let published_crates = conf.published_crates.clone();
+            store.register_late_pass(move |_| Box::new(UnusedPubItems::new(phase.clone(), &published_crates)));
+        },
+        None => {
+            sess.err(format!(
+                "unknown `--unused-pub` phase `{phase}`, expected `record` or `check:<crates>`"
+            ));
+        },
+    }
 }
 
 #[rustfmt::skip]
@@ -968,6 +1056,153 @@ pub fn register_renamed(ls: &mut rustc_lint::LintStore) {
     }
 }
 
+/// Register the pass recording what's needed to replay the diagnostics of this run, see
+/// `utils::cache_record`.
+///
+/// Used in `./src/driver.rs` when it runs the compiler for a cached invocation.
+pub fn register_cache_record(store: &mut rustc_lint::LintStore, path: PathBuf) {
+    utils::cache_record::register(store, path);
+}
+
+/// Wrap all registered lint passes to measure their execution time, see `utils::profile`.
+///
+/// Used in `./src/driver.rs` with `CLIPPY_PROFILE` set, after all lint passes were registered.
+pub fn register_profiling(store: &mut rustc_lint::LintStore, sess: &Session, out_dir: Option<&Path>) {
+    use rustc_session::config::CrateType;
+
+    let report = out_dir.map(|out_dir| {
+        let crate_name = sess.opts.crate_name.as_deref().unwrap_or("unknown");
+        let crate_kind = if sess.opts.test {
+            "test"
+        } else if sess.opts.crate_types.contains(&CrateType::Executable) {
+            "bin"
+        } else {
+            "lib"
+        };
+        utils::profile::report_path(out_dir, crate_name, crate_kind)
+    });
+    utils::profile::wrap_passes(store, report);
+}
+
+/// Register the lint groups from the `groups` configuration.
+///
+/// Used in `./src/driver.rs`, after the lint libraries, whose lints can be added to groups too.
+pub fn register_user_groups(store: &mut rustc_lint::LintStore, sess: &Session, conf: &Conf) {
+    let mut invalid = FxHashSet::default();
+    for name in conf.groups.keys() {
+        if !name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+        {
+            sess.err(format!(
+                "invalid lint group name `{name}`, group names are written in snake case"
+            ));
+            invalid.insert(name.as_str());
+        } else if store.find_lints(&format!("clippy::{name}")).is_ok() {
+            sess.err(format!(
+                "the lint group `clippy::{name}` conflicts with an existing lint or lint group"
+            ));
+            invalid.insert(name.as_str());
+        }
+    }
+
+    // A group is registered after the groups it includes, which it is then expanded to.
+    let mut order = Vec::new();
+    let mut visited = FxHashSet::default();
+    for name in conf.groups.keys() {
+        visit_user_group(
+            conf,
+            sess,
+            name,
+            &mut Vec::new(),
+            &mut visited,
+            &mut invalid,
+            &mut order,
+        );
+    }
+
+    for name in order {
+        if invalid.contains(name) {
+            continue;
+        }
+        let group = format!("clippy::{name}");
+        let mut lints = Vec::new();
+        for member in &conf.groups[name] {
+            let member = user_group_member(member);
+            match store.find_lints(&format!("clippy::{member}")) {
+                Ok(ids) => lints.extend(ids),
+                // The error was emitted for the group itself
+                Err(_) if invalid.contains(member.as_str()) => {},
+                Err(_) => {
+                    sess.err(format!("unknown lint `clippy::{member}` in the lint group `{group}`"));
+                },
+            }
+        }
+        lints.sort_by_key(|id| id.lint_name_raw());
+        lints.dedup();
+        store.register_group(true, Box::leak(group.into_boxed_str()), None, lints);
+    }
+}
+
+/// Pushes the user-defined group `name` to `order` after the user-defined groups it includes.
+/// The groups of a cycle are reported, and added to `invalid`.
+fn visit_user_group<'a>(
+    conf: &'a Conf,
+    sess: &Session,
+    name: &'a str,
+    path: &mut Vec<&'a str>,
+    visited: &mut FxHashSet<&'a str>,
+    invalid: &mut FxHashSet<&'a str>,
+    order: &mut Vec<&'a str>,
+) {
+    if let Some(start) = path.iter().position(|&group| group == name) {
+        let cycle = path[start..]
+            .iter()
+            .chain([&name])
+            .map(|group| format!("`clippy::{group}`"))
+            .collect::<Vec<_>>()
+            .join(" -> ");
+        sess.err(format!("the lint group `clippy::{name}` includes itself: {cycle}"));
+        invalid.extend(&path[start..]);
+        return;
+    }
+    if !visited.insert(name) {
+        return;
+    }
+    path.push(name);
+    for member in &conf.groups[name] {
+        if let Some((member, _)) = conf.groups.get_key_value(user_group_member(member).as_str()) {
+            visit_user_group(conf, sess, member, path, visited, invalid, order);
+        }
+    }
+    path.pop();
+    order.push(name);
+}
+
+/// The name of a member of a user-defined group, without the `clippy::` prefix.
+fn user_group_member(member: &str) -> String {
+    member.strip_prefix("clippy::").unwrap_or(member).replace('-', "_")
+}
+
+/// The user-defined groups which include the `restriction` group, directly or through other
+/// user-defined groups.
+fn blanket_restriction_groups(conf: &Conf) -> Vec<String> {
+    let mut groups = vec![String::from("restriction")];
+    loop {
+        let found = groups.len();
+        for (name, members) in &conf.groups {
+            if !groups.contains(name) && members.iter().any(|member| groups.contains(&user_group_member(member))) {
+                groups.push(name.clone());
+            }
+        }
+        if groups.len() == found {
+            break;
+        }
+    }
+    groups.remove(0);
+    groups
+}
+
 // only exists to let the dogfood integration test works.
 // Don't run clippy as an executable directly
 #[allow(dead_code)]
@@ -2,7 +2,7 @@ use clippy_utils::diagnostics::span_lint_and_then;
 use clippy_utils::higher::IfLetOrMatch;
 use clippy_utils::msrvs::{self, Msrv};
 use clippy_utils::peel_blocks;
-use clippy_utils::source::snippet_with_context;
+use clippy_utils::source::{reattach_comments, snippet_with_context};
 use clippy_utils::ty::is_type_diagnostic_item;
 use clippy_utils::visitors::{for_each_expr, Descend};
 use if_chain::if_chain;
@@ -157,6 +157,7 @@ fn emit_manual_let_else(cx: &LateContext<'_>, span: Span, expr: &Expr<'_>, pat:
                 sn_pat.into_owned()
             };
             let sugg = format!("let {sn_bl} = {sn_expr} else {else_bl};");
+            let sugg = reattach_comments(cx, span, &[pat.span, expr.span, else_body.span], sugg, &mut app);
             diag.span_suggestion(span, "consider writing", sugg, app);
         },
     );
@@ -1,6 +1,6 @@
 use clippy_utils::diagnostics::span_lint_and_sugg;
 use clippy_utils::macros::HirNode;
-use clippy_utils::source::{indent_of, snippet, snippet_block, snippet_with_applicability};
+use clippy_utils::source::{indent_of, reattach_comments, snippet, snippet_block, snippet_with_applicability};
 use clippy_utils::sugg::Sugg;
 use clippy_utils::{get_parent_expr, is_refutable, peel_blocks};
 use rustc_errors::Applicability;
@@ -52,6 +52,13 @@ pub(crate) fn check<'a>(cx: &LateContext<'a>, ex: &Expr<'a>, arms: &[Arm<'_>], e
                         Some(span),
                         true,
                     );
+                    let sugg = reattach_comments(
+                        cx,
+                        span.to(match_span),
+                        &[span, bind_names, matched_vars, match_body.span],
+                        sugg,
+                        &mut applicability,
+                    );
 
                     span_lint_and_sugg(
                         cx,
@@ -65,16 +72,17 @@ pub(crate) fn check<'a>(cx: &LateContext<'a>, ex: &Expr<'a>, arms: &[Arm<'_>], e
 
                     return;
                 },
-                Some(AssignmentExpr::Local { span, pat_span }) => (
-                    span,
-                    format!(
+                Some(AssignmentExpr::Local { span, pat_span }) => {
+                    let sugg = format!(
                         "let {} = {};\n{}let {} = {snippet_body};",
                         snippet_with_applicability(cx, bind_names, "..", &mut applicability),
                         snippet_with_applicability(cx, matched_vars, "..", &mut applicability),
                         " ".repeat(indent_of(cx, expr.span).unwrap_or(0)),
                         snippet_with_applicability(cx, pat_span, "..", &mut applicability)
-                    ),
-                ),
+                    );
+                    let kept = [bind_names, matched_vars, pat_span, match_body.span];
+                    (span, reattach_comments(cx, span, &kept, sugg, &mut applicability))
+                },
                 None => {
                     let sugg = sugg_with_curlies(
                         cx,
@@ -85,7 +93,11 @@ pub(crate) fn check<'a>(cx: &LateContext<'a>, ex: &Expr<'a>, arms: &[Arm<'_>], e
                         None,
                         true,
                     );
-                    (expr.span, sugg)
+                    let kept = [bind_names, matched_vars, match_body.span];
+                    (
+                        expr.span,
+                        reattach_comments(cx, expr.span, &kept, sugg, &mut applicability),
+                    )
                 },
             };
 
@@ -110,6 +122,8 @@ pub(crate) fn check<'a>(cx: &LateContext<'a>, ex: &Expr<'a>, arms: &[Arm<'_>], e
                     None,
                     false,
                 );
+                let kept = [matched_vars, match_body.span];
+                let sugg = reattach_comments(cx, expr.span, &kept, sugg, &mut applicability);
 
                 span_lint_and_sugg(
                     cx,
@@ -121,14 +135,21 @@ pub(crate) fn check<'a>(cx: &LateContext<'a>, ex: &Expr<'a>, arms: &[Arm<'_>], e
                     applicability,
                 );
             } else {
+                let mut applicability = Applicability::MachineApplicable;
+                let sugg = Sugg::NonParen(snippet_body.into()).with_comments(
+                    cx,
+                    expr.span,
+                    &[match_body.span],
+                    &mut applicability,
+                );
                 span_lint_and_sugg(
                     cx,
                     MATCH_SINGLE_BINDING,
                     expr.span,
                     "this match could be replaced by its body itself",
                     "consider using the match body instead",
-                    snippet_body,
-                    Applicability::MachineApplicable,
+                    sugg.to_string(),
+                    applicability,
                 );
             }
         },
@@ -5,6 +5,7 @@ use clippy_utils::{
 use core::ops::ControlFlow::Continue;
 use hir::{
     def::{DefKind, Res},
+    def_id::DefId,
     BlockCheckMode, ExprKind, QPath, UnOp, Unsafety,
 };
 use rustc_ast::Mutability;
@@ -81,8 +82,8 @@ impl<'tcx> LateLintPass<'tcx> for MultipleUnsafeOpsPerBlock {
                     unsafe_ops.len()
                 ),
                 |diag| {
-                    for (msg, span) in unsafe_ops {
-                        diag.span_note(span, msg);
+                    for (op, span) in unsafe_ops {
+                        diag.span_note(span, op.note());
                     }
                 },
             );
@@ -90,33 +91,70 @@ impl<'tcx> LateLintPass<'tcx> for MultipleUnsafeOpsPerBlock {
     }
 }
 
-fn collect_unsafe_exprs<'tcx>(
+/// An operation that is only allowed in an `unsafe` context.
+#[derive(Clone, Copy)]
+pub(crate) enum UnsafeOp {
+    InlineAsm,
+    UnionFieldAccess,
+    MutStaticAccess(DefId),
+    RawPtrDeref,
+    UnsafeFnCall(DefId),
+    UnsafeMethodCall(DefId),
+    MutStaticModification(DefId),
+}
+
+impl UnsafeOp {
+    pub(crate) fn note(self) -> &'static str {
+        match self {
+            Self::InlineAsm => "inline assembly used here",
+            Self::UnionFieldAccess => "union field access occurs here",
+            Self::MutStaticAccess(_) => "access of a mutable static occurs here",
+            Self::RawPtrDeref => "raw pointer dereference occurs here",
+            Self::UnsafeFnCall(_) => "unsafe function call occurs here",
+            Self::UnsafeMethodCall(_) => "unsafe method call occurs here",
+            Self::MutStaticModification(_) => "modification of a mutable static occurs here",
+        }
+    }
+
+    /// The called function or the accessed static, if any.
+    pub(crate) fn def_id(self) -> Option<DefId> {
+        match self {
+            Self::MutStaticAccess(def_id)
+            | Self::UnsafeFnCall(def_id)
+            | Self::UnsafeMethodCall(def_id)
+            | Self::MutStaticModification(def_id) => Some(def_id),
+            Self::InlineAsm | Self::UnionFieldAccess | Self::RawPtrDeref => None,
+        }
+    }
+}
+
+pub(crate) fn collect_unsafe_exprs<'tcx>(
     cx: &LateContext<'tcx>,
     node: impl Visitable<'tcx>,
-    unsafe_ops: &mut Vec<(&'static str, Span)>,
+    unsafe_ops: &mut Vec<(UnsafeOp, Span)>,
 ) {
     for_each_expr_with_closures(cx, node, |expr| {
         match expr.kind {
-            ExprKind::InlineAsm(_) => unsafe_ops.push(("inline assembly used here", expr.span)),
+            ExprKind::InlineAsm(_) => unsafe_ops.push((UnsafeOp::InlineAsm, expr.span)),
 
             ExprKind::Field(e, _) => {
                 if cx.typeck_results().expr_ty(e).is_union() {
-                    unsafe_ops.push(("union field access occurs here", expr.span));
+                    unsafe_ops.push((UnsafeOp::UnionFieldAccess, expr.span));
                 }
             },
 
             ExprKind::Path(QPath::Resolved(
                 _,
                 hir::Path {
-                    res: Res::Def(DefKind::Static(Mutability::Mut), _),
+                    res: Res::Def(DefKind::Static(Mutability::Mut), def_id),
                     ..
                 },
             )) => {
-                unsafe_ops.push(("access of a mutable static occurs here", expr.span));
+                unsafe_ops.push((UnsafeOp::MutStaticAccess(*def_id), expr.span));
             },
 
             ExprKind::Unary(UnOp::Deref, e) if cx.typeck_results().expr_ty_adjusted(e).is_unsafe_ptr() => {
-                unsafe_ops.push(("raw pointer dereference occurs here", expr.span));
+                unsafe_ops.push((UnsafeOp::RawPtrDeref, expr.span));
             },
 
             ExprKind::Call(path_expr, _) => match path_expr.kind {
@@ -129,19 +167,15 @@ fn collect_unsafe_exprs<'tcx>(
                 )) if kind.is_fn_like() => {
                     let sig = cx.tcx.bound_fn_sig(*def_id);
                     if sig.0.unsafety() == Unsafety::Unsafe {
-                        unsafe_ops.push(("unsafe function call occurs here", expr.span));
+                        unsafe_ops.push((UnsafeOp::UnsafeFnCall(*def_id), expr.span));
                     }
                 },
 
                 ExprKind::Path(QPath::TypeRelative(..)) => {
-                    if let Some(sig) = cx
-                        .typeck_results()
-                        .type_dependent_def_id(path_expr.hir_id)
-                        .map(|def_id| cx.tcx.bound_fn_sig(def_id))
+                    if let Some(def_id) = cx.typeck_results().type_dependent_def_id(path_expr.hir_id)
+                        && cx.tcx.bound_fn_sig(def_id).0.unsafety() == Unsafety::Unsafe
                     {
-                        if sig.0.unsafety() == Unsafety::Unsafe {
-                            unsafe_ops.push(("unsafe function call occurs here", expr.span));
-                        }
+                        unsafe_ops.push((UnsafeOp::UnsafeFnCall(def_id), expr.span));
                     }
                 },
 
@@ -149,29 +183,23 @@ fn collect_unsafe_exprs<'tcx>(
             },
 
             ExprKind::MethodCall(..) => {
-                if let Some(sig) = cx
-                    .typeck_results()
-                    .type_dependent_def_id(expr.hir_id)
-                    .map(|def_id| cx.tcx.bound_fn_sig(def_id))
+                if let Some(def_id) = cx.typeck_results().type_dependent_def_id(expr.hir_id)
+                    && cx.tcx.bound_fn_sig(def_id).0.unsafety() == Unsafety::Unsafe
                 {
-                    if sig.0.unsafety() == Unsafety::Unsafe {
-                        unsafe_ops.push(("unsafe method call occurs here", expr.span));
-                    }
+                    unsafe_ops.push((UnsafeOp::UnsafeMethodCall(def_id), expr.span));
                 }
             },
 
             ExprKind::AssignOp(_, lhs, rhs) | ExprKind::Assign(lhs, rhs, _) => {
-                if matches!(
-                    lhs.kind,
-                    ExprKind::Path(QPath::Resolved(
-                        _,
-                        hir::Path {
-                            res: Res::Def(DefKind::Static(Mutability::Mut), _),
-                            ..
-                        }
-                    ))
-                ) {
-                    unsafe_ops.push(("modification of a mutable static occurs here", expr.span));
+                if let ExprKind::Path(QPath::Resolved(
+                    _,
+                    hir::Path {
+                        res: Res::Def(DefKind::Static(Mutability::Mut), def_id),
+                        ..
+                    },
+                )) = lhs.kind
+                {
+                    unsafe_ops.push((UnsafeOp::MutStaticModification(*def_id), expr.span));
                     collect_unsafe_exprs(cx, rhs, unsafe_ops);
                     return Continue(Descend::No);
                 }
@@ -1,6 +1,6 @@
 use clippy_utils::diagnostics::span_lint_and_then;
 use clippy_utils::path_to_local;
-use clippy_utils::source::snippet_opt;
+use clippy_utils::source::{reattach_comments, snippet_opt};
 use clippy_utils::ty::needs_ordered_drop;
 use clippy_utils::visitors::{for_each_expr, for_each_expr_with_closures, is_local_used};
 use core::ops::ControlFlow;
@@ -177,6 +177,16 @@ fn assignment_suggestions<'tcx>(
         assignments.push(assign);
     }
 
+    let mut applicability = match assignments.len() {
+        // All of `exprs` are never types
+        // https://github.com/rust-lang/rust-clippy/issues/8911
+        0 => return None,
+        1 => Applicability::MachineApplicable,
+        // multiple suggestions don't work with rustfix in multipart_suggest
+        // https://github.com/rust-lang/rustfix/issues/141
+        _ => Applicability::Unspecified,
+    };
+
     let suggestions = assignments
         .iter()
         .flat_map(|assignment| {
@@ -188,18 +198,15 @@ fn assignment_suggestions<'tcx>(
 
             spans
         })
-        .map(|span| (span, String::new()))
+        .map(|span| {
+            (
+                span,
+                reattach_comments(cx, span, &[], String::new(), &mut applicability),
+            )
+        })
         .collect::<Vec<(Span, String)>>();
 
-    match suggestions.len() {
-        // All of `exprs` are never types
-        // https://github.com/rust-lang/rust-clippy/issues/8911
-        0 => None,
-        1 => Some((Applicability::MachineApplicable, suggestions)),
-        // multiple suggestions don't work with rustfix in multipart_suggest
-        // https://github.com/rust-lang/rustfix/issues/141
-        _ => Some((Applicability::Unspecified, suggestions)),
-    }
+    Some((applicability, suggestions))
 }
 
 struct Usage<'tcx> {
@@ -238,17 +245,15 @@ fn first_usage<'tcx>(
         })
 }
 
-fn local_snippet_without_semicolon(cx: &LateContext<'_>, local: &Local<'_>) -> Option<String> {
-    let span = local.span.with_hi(match local.ty {
+fn local_span_without_semicolon(local: &Local<'_>) -> Span {
+    local.span.with_hi(match local.ty {
         // let <pat>: <ty>;
         // ~~~~~~~~~~~~~~~
         Some(ty) => ty.span.hi(),
         // let <pat>;
         // ~~~~~~~~~
         None => local.pat.span.hi(),
-    });
-
-    snippet_opt(cx, span)
+    })
 }
 
 fn check<'tcx>(
@@ -260,7 +265,8 @@ fn check<'tcx>(
 ) -> Option<()> {
     let usage = first_usage(cx, binding_id, local_stmt.hir_id, block)?;
     let binding_name = cx.tcx.hir().opt_name(binding_id)?;
-    let let_snippet = local_snippet_without_semicolon(cx, local)?;
+    let let_span = local_span_without_semicolon(local);
+    let let_snippet = snippet_opt(cx, let_span)?;
 
     match usage.expr.kind {
         ExprKind::Assign(..) => {
@@ -275,24 +281,23 @@ fn check<'tcx>(
                 msg_span,
                 "unneeded late initialization",
                 |diag| {
-                    diag.tool_only_span_suggestion(
-                        local_stmt.span,
-                        "remove the local",
-                        "",
-                        Applicability::MachineApplicable,
-                    );
+                    let mut applicability = Applicability::MachineApplicable;
+                    let removal =
+                        reattach_comments(cx, local_stmt.span, &[let_span], String::new(), &mut applicability);
+                    diag.tool_only_span_suggestion(local_stmt.span, "remove the local", removal, applicability);
 
                     diag.span_suggestion(
                         assign.lhs_span,
                         format!("declare `{binding_name}` here"),
                         let_snippet,
-                        Applicability::MachineApplicable,
+                        applicability,
                     );
                 },
             );
         },
         ExprKind::If(cond, then_expr, Some(else_expr)) if !contains_let(cond) => {
-            let (applicability, suggestions) = assignment_suggestions(cx, binding_id, [then_expr, else_expr])?;
+            let (mut applicability, suggestions) = assignment_suggestions(cx, binding_id, [then_expr, else_expr])?;
+            let removal = reattach_comments(cx, local_stmt.span, &[let_span], String::new(), &mut applicability);
 
             span_lint_and_then(
                 cx,
@@ -300,7 +305,7 @@ fn check<'tcx>(
                 local_stmt.span,
                 "unneeded late initialization",
                 |diag| {
-                    diag.tool_only_span_suggestion(local_stmt.span, "remove the local", String::new(), applicability);
+                    diag.tool_only_span_suggestion(local_stmt.span, "remove the local", removal, applicability);
 
                     diag.span_suggestion_verbose(
                         usage.stmt.span.shrink_to_lo(),
@@ -323,7 +328,9 @@ fn check<'tcx>(
             );
         },
         ExprKind::Match(_, arms, MatchSource::Normal) => {
-            let (applicability, suggestions) = assignment_suggestions(cx, binding_id, arms.iter().map(|arm| arm.body))?;
+            let (mut applicability, suggestions) =
+                assignment_suggestions(cx, binding_id, arms.iter().map(|arm| arm.body))?;
+            let removal = reattach_comments(cx, local_stmt.span, &[let_span], String::new(), &mut applicability);
 
             span_lint_and_then(
                 cx,
@@ -331,7 +338,7 @@ fn check<'tcx>(
                 local_stmt.span,
                 "unneeded late initialization",
                 |diag| {
-                    diag.tool_only_span_suggestion(local_stmt.span, "remove the local", String::new(), applicability);
+                    diag.tool_only_span_suggestion(local_stmt.span, "remove the local", removal, applicability);
 
                     diag.span_suggestion_verbose(
                         usage.stmt.span.shrink_to_lo(),
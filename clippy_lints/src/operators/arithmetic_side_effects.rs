@@ -3,6 +3,7 @@ use clippy_utils::{
     consts::{constant, constant_simple},
     diagnostics::span_lint,
     peel_hir_expr_refs, peel_hir_expr_unary,
+    value_range::is_overflow_free,
 };
 use rustc_ast as ast;
 use rustc_data_structures::fx::{FxHashMap, FxHashSet};
@@ -25,6 +26,7 @@ const HARD_CODED_ALLOWED_UNARY: &[&str] = &["f32", "f64", "std::num::Saturating"
 pub struct ArithmeticSideEffects {
     allowed_binary: FxHashMap<String, FxHashSet<String>>,
     allowed_unary: FxHashSet<String>,
+    value_ranges: bool,
     // Used to check whether expressions are constants, such as in enum discriminants and consts
     const_span: Option<Span>,
     expr_span: Option<Span>,
@@ -34,7 +36,7 @@ impl_lint_pass!(ArithmeticSideEffects => [ARITHMETIC_SIDE_EFFECTS]);
 
 impl ArithmeticSideEffects {
     #[must_use]
-    pub fn new(user_allowed_binary: Vec<[String; 2]>, user_allowed_unary: Vec<String>) -> Self {
+    pub fn new(user_allowed_binary: Vec<[String; 2]>, user_allowed_unary: Vec<String>, value_ranges: bool) -> Self {
         let mut allowed_binary: FxHashMap<String, FxHashSet<String>> = <_>::default();
         for [lhs, rhs] in user_allowed_binary.into_iter().chain(
             HARD_CODED_ALLOWED_BINARY
@@ -51,6 +53,7 @@ impl ArithmeticSideEffects {
         Self {
             allowed_binary,
             allowed_unary,
+            value_ranges,
             const_span: None,
             expr_span: None,
         }
@@ -111,11 +114,12 @@ impl ArithmeticSideEffects {
     }
 
     /// Manages when the lint should be triggered. Operations in constant environments, hard coded
-    /// types, custom allowed types and non-constant operations that won't overflow are ignored.
+    /// types, custom allowed types and non-constant operations that won't overflow, including the
+    /// ones whose operands are known to be in a safe range if `value_ranges` is set, are ignored.
     fn manage_bin_ops<'tcx>(
         &mut self,
         cx: &LateContext<'tcx>,
-        expr: &hir::Expr<'tcx>,
+        expr: &'tcx hir::Expr<'tcx>,
         op: &Spanned<hir::BinOpKind>,
         lhs: &hir::Expr<'tcx>,
         rhs: &hir::Expr<'tcx>,
@@ -159,15 +163,16 @@ impl ArithmeticSideEffects {
         } else {
             false
         };
-        if !has_valid_op {
-            self.issue_lint(cx, expr);
+        if has_valid_op || (self.value_ranges && is_overflow_free(cx, expr)) {
+            return;
         }
+        self.issue_lint(cx, expr);
     }
 
     fn manage_unary_ops<'tcx>(
         &mut self,
         cx: &LateContext<'tcx>,
-        expr: &hir::Expr<'tcx>,
+        expr: &'tcx hir::Expr<'tcx>,
         un_expr: &hir::Expr<'tcx>,
         un_op: hir::UnOp,
     ) {
@@ -180,7 +185,7 @@ impl ArithmeticSideEffects {
             return;
         }
         let actual_un_expr = peel_hir_expr_refs(un_expr).0;
-        if Self::literal_integer(actual_un_expr).is_some() {
+        if Self::literal_integer(actual_un_expr).is_some() || (self.value_ranges && is_overflow_free(cx, expr)) {
             return;
         }
         self.issue_lint(cx, expr);
@@ -192,7 +197,7 @@ impl ArithmeticSideEffects {
 }
 
 impl<'tcx> LateLintPass<'tcx> for ArithmeticSideEffects {
-    fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &hir::Expr<'tcx>) {
+    fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &'tcx hir::Expr<'tcx>) {
         if self.should_skip_expr(expr) {
             return;
         }
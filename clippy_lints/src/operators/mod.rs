@@ -14,11 +14,14 @@ mod integer_division;
 mod misrefactored_assign_op;
 mod modulo_arithmetic;
 mod modulo_one;
+mod mul_div_overflow;
 mod needless_bitwise_bool;
 mod numeric_arithmetic;
 mod op_ref;
+mod overflowing_midpoint;
 mod ptr_eq;
 mod self_assignment;
+mod unchecked_len_sub;
 mod verbose_bit_mask;
 
 pub(crate) mod arithmetic_side_effects;
@@ -780,6 +783,92 @@ declare_clippy_lint! {
     "explicit self-assignment"
 }
 
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for the midpoint of two integers computed as `(lo + hi) / 2` or `(lo + hi) >> 1`,
+    /// when the sum may overflow.
+    ///
+    /// ### Why is this bad?
+    /// The sum overflows for large bounds, even though the midpoint itself fits in the type.
+    /// This is a classic bug of binary searches.
+    ///
+    /// The suggestion, `lo + (hi - lo) / 2`, is only offered for unsigned integers: for signed
+    /// ones `hi - lo` overflows when the bounds have different signs, so the sum has to be
+    /// computed in a wider type instead.
+    ///
+    /// ### Example
+    /// ```rust
+    /// # let (lo, hi) = (0usize, 10usize);
+    /// let mid = (lo + hi) / 2;
+    /// ```
+    /// Use instead:
+    /// ```rust
+    /// # let (lo, hi) = (0usize, 10usize);
+    /// let mid = lo + (hi - lo) / 2;
+    /// ```
+    #[clippy::version = "1.68.0"]
+    pub OVERFLOWING_MIDPOINT,
+    suspicious,
+    "computing the midpoint of two integers with a sum which may overflow"
+}
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for integer expressions like `a * b / c` where the product may overflow.
+    ///
+    /// ### Why is this bad?
+    /// The product may overflow even when the quotient fits in the type, e.g. when scaling a
+    /// value by a ratio.
+    ///
+    /// ### Example
+    /// ```rust
+    /// fn scale(value: u64, num: u64, den: u64) -> u64 {
+    ///     value * num / den
+    /// }
+    /// ```
+    /// Use instead:
+    /// ```rust
+    /// fn scale(value: u64, num: u64, den: u64) -> u64 {
+    ///     (u128::from(value) * u128::from(num) / u128::from(den)) as u64
+    /// }
+    /// ```
+    #[clippy::version = "1.68.0"]
+    pub MUL_DIV_OVERFLOW,
+    pedantic,
+    "a product which may overflow before being divided"
+}
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for `x.len() - 1` when nothing before proves that `x` isn't empty.
+    ///
+    /// ### Why is this bad?
+    /// The subtraction overflows for an empty collection, which panics in debug builds and
+    /// wraps around to `usize::MAX` in release builds.
+    ///
+    /// ### Known problems
+    /// Only the checks preceding the subtraction are considered, like `!x.is_empty()`,
+    /// `x.len() > 0` or `if let Some(last) = x.last()`. Collections which are never empty by
+    /// construction are linted as well.
+    ///
+    /// ### Example
+    /// ```rust
+    /// fn last_index(v: &[u8]) -> usize {
+    ///     v.len() - 1
+    /// }
+    /// ```
+    /// Use instead:
+    /// ```rust
+    /// fn last_index(v: &[u8]) -> usize {
+    ///     v.len().saturating_sub(1)
+    /// }
+    /// ```
+    #[clippy::version = "1.68.0"]
+    pub UNCHECKED_LEN_SUB,
+    restriction,
+    "subtracting one from the length of a collection which may be empty"
+}
+
 pub struct Operators {
     arithmetic_context: numeric_arithmetic::Context,
     verbose_bit_mask_threshold: u64,
@@ -811,6 +900,9 @@ impl_lint_pass!(Operators => [
     NEEDLESS_BITWISE_BOOL,
     PTR_EQ,
     SELF_ASSIGNMENT,
+    OVERFLOWING_MIDPOINT,
+    MUL_DIV_OVERFLOW,
+    UNCHECKED_LEN_SUB,
 ]);
 impl Operators {
     pub fn new(verbose_bit_mask_threshold: u64) -> Self {
@@ -821,7 +913,7 @@ impl Operators {
     }
 }
 impl<'tcx> LateLintPass<'tcx> for Operators {
-    fn check_expr(&mut self, cx: &LateContext<'tcx>, e: &'tcx Expr<'_>) {
+    fn check_expr(&mut self, cx: &LateContext<'tcx>, e: &'tcx Expr<'tcx>) {
         eq_op::check_assert(cx, e);
         match e.kind {
             ExprKind::Binary(op, lhs, rhs) => {
@@ -848,6 +940,9 @@ impl<'tcx> LateLintPass<'tcx> for Operators {
                 float_cmp::check(cx, e, op.node, lhs, rhs);
                 modulo_one::check(cx, e, op.node, rhs);
                 modulo_arithmetic::check(cx, e, op.node, lhs, rhs);
+                overflowing_midpoint::check(cx, e, op.node, lhs, rhs);
+                mul_div_overflow::check(cx, e, op.node, lhs, rhs);
+                unchecked_len_sub::check(cx, e, op.node, lhs, rhs);
             },
             ExprKind::AssignOp(op, lhs, rhs) => {
                 self.arithmetic_context.check_binary(cx, e, op.node, lhs, rhs);
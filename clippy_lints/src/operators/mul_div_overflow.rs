@@ -0,0 +1,54 @@
+use clippy_utils::diagnostics::span_lint_and_help;
+use clippy_utils::value_range::is_overflow_free;
+use rustc_hir::{BinOpKind, Expr, ExprKind};
+use rustc_lint::LateContext;
+use rustc_middle::ty::{self, IntTy, Ty, UintTy};
+
+use super::MUL_DIV_OVERFLOW;
+
+pub(crate) fn check<'tcx>(
+    cx: &LateContext<'tcx>,
+    expr: &'tcx Expr<'tcx>,
+    op: BinOpKind,
+    left: &'tcx Expr<'tcx>,
+    right: &'tcx Expr<'tcx>,
+) {
+    if op == BinOpKind::Div
+        && let ExprKind::Binary(inner_op, ..) = left.kind
+        && inner_op.node == BinOpKind::Mul
+        && !left.span.from_expansion()
+        && !is_overflow_free(cx, left)
+    {
+        let ty = cx.typeck_results().expr_ty(right).peel_refs();
+        if !ty.is_integral() {
+            return;
+        }
+        let help = match wider_int_ty(ty) {
+            Some(wider) => format!("use `checked_mul`, or compute the product and the quotient in `{wider}`"),
+            None => "use `checked_mul` to handle the overflow".to_owned(),
+        };
+        span_lint_and_help(
+            cx,
+            MUL_DIV_OVERFLOW,
+            expr.span,
+            "the product may overflow before it is divided",
+            None,
+            &help,
+        );
+    }
+}
+
+/// The integer type of twice the width of `ty`, if there is one.
+pub(super) fn wider_int_ty(ty: Ty<'_>) -> Option<&'static str> {
+    Some(match ty.kind() {
+        ty::Uint(UintTy::U8) => "u16",
+        ty::Uint(UintTy::U16) => "u32",
+        ty::Uint(UintTy::U32) => "u64",
+        ty::Uint(UintTy::U64 | UintTy::Usize) => "u128",
+        ty::Int(IntTy::I8) => "i16",
+        ty::Int(IntTy::I16) => "i32",
+        ty::Int(IntTy::I32) => "i64",
+        ty::Int(IntTy::I64 | IntTy::Isize) => "i128",
+        _ => return None,
+    })
+}
@@ -0,0 +1,75 @@
+use clippy_utils::consts::{constant, Constant};
+use clippy_utils::diagnostics::span_lint_and_then;
+use clippy_utils::sugg::Sugg;
+use clippy_utils::value_range::is_overflow_free;
+use rustc_errors::Applicability;
+use rustc_hir::{BinOpKind, Expr, ExprKind};
+use rustc_lint::LateContext;
+
+use super::mul_div_overflow::wider_int_ty;
+use super::OVERFLOWING_MIDPOINT;
+
+pub(crate) fn check<'tcx>(
+    cx: &LateContext<'tcx>,
+    expr: &'tcx Expr<'tcx>,
+    op: BinOpKind,
+    left: &'tcx Expr<'tcx>,
+    right: &'tcx Expr<'tcx>,
+) {
+    let halving = match op {
+        BinOpKind::Div => 2,
+        BinOpKind::Shr => 1,
+        _ => return,
+    };
+    if let ExprKind::Binary(inner_op, lo, hi) = left.kind
+        && inner_op.node == BinOpKind::Add
+        && !left.span.from_expansion()
+        && is_bound(lo)
+        && is_bound(hi)
+        && cx.typeck_results().expr_ty(left).is_integral()
+        && let Some((Constant::Int(value), _)) = constant(cx, cx.typeck_results(), right)
+        && value == halving
+        && !is_overflow_free(cx, left)
+    {
+        span_lint_and_then(
+            cx,
+            OVERFLOWING_MIDPOINT,
+            expr.span,
+            "computing the midpoint of two integers this way may overflow",
+            |diag| {
+                let ty = cx.typeck_results().expr_ty(left);
+                if ty.is_signed() {
+                    // `hi - lo` overflows as well when the bounds have different signs
+                    match wider_int_ty(ty) {
+                        Some(wider) => diag.help(&format!("compute the sum and the midpoint in `{wider}`")),
+                        None => diag.help("use `checked_add` to handle the overflow"),
+                    };
+                    return;
+                }
+                let mut app = Applicability::MaybeIncorrect;
+                let lo = Sugg::hir_with_applicability(cx, lo, "..", &mut app).maybe_par();
+                let hi = Sugg::hir_with_applicability(cx, hi, "..", &mut app).maybe_par();
+                let half = if op == BinOpKind::Div {
+                    format!("({hi} - {lo}) / 2")
+                } else {
+                    format!("(({hi} - {lo}) >> 1)")
+                };
+                diag.span_suggestion(
+                    expr.span,
+                    "if the first operand is the smaller one, use",
+                    format!("{lo} + {half}"),
+                    app,
+                );
+            },
+        );
+    }
+}
+
+/// Checks if `expr` looks like a bound of a search, rather than an arbitrary operand, e.g. in
+/// `(a * b + c) / 2`.
+fn is_bound(expr: &Expr<'_>) -> bool {
+    matches!(
+        expr.kind,
+        ExprKind::Path(_) | ExprKind::Field(..) | ExprKind::MethodCall(..) | ExprKind::Call(..)
+    )
+}
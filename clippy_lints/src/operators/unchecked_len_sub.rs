@@ -0,0 +1,110 @@
+use clippy_utils::consts::{constant, Constant};
+use clippy_utils::diagnostics::span_lint_and_sugg;
+use clippy_utils::sugg::Sugg;
+use clippy_utils::value_range::{int_range, is_overflow_free};
+use clippy_utils::{eq_expr_value, expr_or_init, get_parent_expr, higher};
+use rustc_errors::Applicability;
+use rustc_hir::{BinOpKind, Expr, ExprKind, Node};
+use rustc_lint::LateContext;
+use rustc_middle::ty::{self, UintTy};
+use rustc_span::sym;
+
+use crate::indexing_slicing::is_proven_non_empty;
+
+use super::UNCHECKED_LEN_SUB;
+
+pub(crate) fn check<'tcx>(
+    cx: &LateContext<'tcx>,
+    expr: &'tcx Expr<'tcx>,
+    op: BinOpKind,
+    left: &'tcx Expr<'tcx>,
+    right: &'tcx Expr<'tcx>,
+) {
+    if op == BinOpKind::Sub
+        && let len_call = expr_or_init(cx, left)
+        && let ExprKind::MethodCall(method, collection, [], _) = len_call.kind
+        && method.ident.name == sym::len
+        && !expr.span.from_expansion()
+        && *cx.typeck_results().expr_ty(left).kind() == ty::Uint(UintTy::Usize)
+        && let Some((Constant::Int(1), _)) = constant(cx, cx.typeck_results(), right)
+        && !is_overflow_free(cx, expr)
+        && !is_proven_non_empty(cx, expr, collection)
+        && !indexing_panics_anyway(cx, expr, collection)
+        && !(left.hir_id == len_call.hir_id && is_get_last(cx, expr, collection))
+    {
+        let mut app = Applicability::MaybeIncorrect;
+        let left = Sugg::hir_with_applicability(cx, left, "..", &mut app).maybe_par();
+        span_lint_and_sugg(
+            cx,
+            UNCHECKED_LEN_SUB,
+            expr.span,
+            "subtracting from a length which may be zero",
+            "use",
+            format!("{left}.saturating_sub(1)"),
+            app,
+        );
+    }
+}
+
+/// Checks if `expr` is the argument of `collection.get(collection.len() - 1)`, which
+/// `get_last_with_len` already suggests replacing with `collection.last()`.
+fn is_get_last(cx: &LateContext<'_>, expr: &Expr<'_>, collection: &Expr<'_>) -> bool {
+    if let Some(parent) = get_parent_expr(cx, expr)
+        && let ExprKind::MethodCall(method, receiver, [arg], _) = parent.kind
+        && method.ident.as_str() == "get"
+        && arg.hir_id == expr.hir_id
+        && eq_expr_value(cx, receiver, collection)
+    {
+        match cx.typeck_results().expr_ty_adjusted(receiver).peel_refs().kind() {
+            ty::Adt(def, _) => cx.tcx.is_diagnostic_item(sym::VecDeque, def.did()),
+            ty::Slice(_) => true,
+            _ => false,
+        }
+    } else {
+        false
+    }
+}
+
+/// Checks if `expr` is used to index `collection` in a way which panics for an empty collection
+/// whatever the result of the subtraction, e.g. `x[x.len() - 1]` or `x[1..x.len() - 1 - n]`.
+fn indexing_panics_anyway<'tcx>(cx: &LateContext<'tcx>, mut expr: &'tcx Expr<'tcx>, collection: &Expr<'_>) -> bool {
+    // The end of a range is the field of a struct expression
+    let parent_expr = |expr: &Expr<'_>| {
+        cx.tcx.hir().parent_iter(expr.hir_id).find_map(|(_, node)| match node {
+            Node::Expr(parent) => Some(parent),
+            _ => None,
+        })
+    };
+    let Some(mut parent) = parent_expr(expr) else {
+        return false;
+    };
+    // Subtracting more only makes the index smaller
+    while let ExprKind::Binary(op, lhs, _) = parent.kind
+        && op.node == BinOpKind::Sub
+        && lhs.hir_id == expr.hir_id
+    {
+        expr = parent;
+        let Some(next) = parent_expr(parent) else {
+            return false;
+        };
+        parent = next;
+    }
+    let (index_expr, index) = match higher::Range::hir(parent) {
+        Some(range)
+            if range.end.map_or(false, |end| end.hir_id == expr.hir_id)
+                && range
+                    .start
+                    .and_then(|start| int_range(cx, start))
+                    .map_or(false, |start| start.lo > 0) =>
+        {
+            (parent_expr(parent), parent)
+        },
+        Some(_) => return false,
+        None => (Some(parent), expr),
+    };
+    matches!(
+        index_expr,
+        Some(Expr { kind: ExprKind::Index(base, idx), .. })
+            if idx.hir_id == index.hir_id && eq_expr_value(cx, base, collection)
+    )
+}
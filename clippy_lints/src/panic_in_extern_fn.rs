@@ -0,0 +1,272 @@
+use clippy_utils::diagnostics::span_lint_and_then;
+use clippy_utils::visitors::{for_each_expr, for_each_expr_with_closures, is_local_used, Descend};
+use clippy_utils::{get_parent_node, match_def_path, panic_source, path_to_local_id, paths, value_range, PanicSource};
+use core::ops::ControlFlow;
+use rustc_data_structures::fx::FxHashSet;
+use rustc_errors::MultiSpan;
+use rustc_hir::def::{DefKind, Res};
+use rustc_hir::def_id::{DefId, LocalDefId};
+use rustc_hir::intravisit::FnKind;
+use rustc_hir::{BinOpKind, Body, Closure, Expr, ExprKind, FnDecl, HirId, Node, PatKind, UnOp};
+use rustc_lint::{LateContext, LateLintPass};
+use rustc_middle::ty::{self, subst::GenericArgKind};
+use rustc_session::{declare_tool_lint, impl_lint_pass};
+use rustc_span::Span;
+use rustc_target::spec::abi::Abi;
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for functions with a non-unwinding foreign ABI, like `extern "C" fn`, whose body
+    /// may panic: with a panicking macro, `unwrap`, `expect`, an indexing which isn't proven to
+    /// be in bounds, or an arithmetic operation which may overflow in debug builds.
+    ///
+    /// Closures called by such a function are checked as well, e.g. the closure passed as the
+    /// data of a C callback to a generic `extern "C" fn trampoline<F: FnMut()>`. They are found
+    /// where the function is instantiated with their type, directly or by a generic function
+    /// like `get_trampoline::<F>` returning `trampoline::<F>`.
+    ///
+    /// ### Why is this bad?
+    /// A panic can't unwind out of such a function. Depending on the toolchain, it either aborts
+    /// the process or is undefined behavior.
+    ///
+    /// ### Known problems
+    /// Functions called from the body are not checked. Closures are only found through a single
+    /// generic function, and only if they are defined in the function instantiating it.
+    ///
+    /// ### Example
+    /// ```rust
+    /// extern "C" fn first(ptr: *const u8, len: usize) -> u8 {
+    ///     let s = unsafe { std::slice::from_raw_parts(ptr, len) };
+    ///     s[0]
+    /// }
+    /// ```
+    /// Use instead:
+    /// ```rust
+    /// extern "C" fn first(ptr: *const u8, len: usize) -> u8 {
+    ///     let s = unsafe { std::slice::from_raw_parts(ptr, len) };
+    ///     s.first().copied().unwrap_or(0)
+    /// }
+    /// ```
+    #[clippy::version = "1.68.0"]
+    pub PANIC_IN_EXTERN_FN,
+    pedantic,
+    "functions with a foreign ABI that may panic"
+}
+
+#[derive(Default)]
+pub struct PanicInExternFn {
+    /// The closures called through a function with a non-unwinding ABI which were checked.
+    checked_closures: FxHashSet<LocalDefId>,
+}
+
+impl_lint_pass!(PanicInExternFn => [PANIC_IN_EXTERN_FN]);
+
+impl<'tcx> LateLintPass<'tcx> for PanicInExternFn {
+    fn check_fn(
+        &mut self,
+        cx: &LateContext<'tcx>,
+        fn_kind: FnKind<'tcx>,
+        _: &'tcx FnDecl<'tcx>,
+        body: &'tcx Body<'tcx>,
+        span: Span,
+        _: HirId,
+    ) {
+        let abi = match fn_kind {
+            FnKind::ItemFn(_, _, header) => header.abi,
+            FnKind::Method(_, sig) => sig.header.abi,
+            FnKind::Closure => return,
+        };
+        if !cannot_unwind(abi) || span.from_expansion() {
+            return;
+        }
+        check_body(
+            cx,
+            body.value,
+            cx.tcx.def_span(body.value.hir_id.owner),
+            &format!("this `extern {abi}` function may panic"),
+            "a panic can't unwind out of this function",
+        );
+    }
+
+    fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'tcx>) {
+        // Closures are passed as C callbacks through functions instantiated with their type
+        let ExprKind::Path(ref qpath) = expr.kind else {
+            return;
+        };
+        let Res::Def(DefKind::Fn | DefKind::AssocFn, def_id) = cx.qpath_res(qpath, expr.hir_id) else {
+            return;
+        };
+        let typeck_root = cx.tcx.typeck_root_def_id(expr.hir_id.owner.to_def_id());
+        for (index, arg) in cx.typeck_results().node_substs(expr.hir_id).iter().enumerate() {
+            if let GenericArgKind::Type(ty) = arg.unpack()
+                && let ty::Closure(closure_id, _) = *ty.kind()
+                && let Some(closure_id) = closure_id.as_local()
+                // The typeck results of the closure are the current ones
+                && cx.tcx.typeck_root_def_id(closure_id.to_def_id()) == typeck_root
+                && let Some(callback) = non_unwinding_caller(cx, def_id, index)
+                && self.checked_closures.insert(closure_id)
+                && let Node::Expr(closure) = cx.tcx.hir().get_by_def_id(closure_id)
+                && let ExprKind::Closure(&Closure { body, .. }) = closure.kind
+                && !closure.span.from_expansion()
+            {
+                let abi = cx.tcx.fn_sig(callback).abi();
+                let name = cx.tcx.item_name(callback);
+                check_body(
+                    cx,
+                    cx.tcx.hir().body(body).value,
+                    closure.span,
+                    &format!("this closure is called by the `extern {abi}` function `{name}` and may panic"),
+                    &format!("a panic can't unwind out of `{name}`"),
+                );
+            }
+        }
+    }
+}
+
+/// Lints the panics in `body`, except for the ones in closures passed to `catch_unwind`.
+fn check_body<'tcx>(cx: &LateContext<'tcx>, body: &'tcx Expr<'tcx>, span: Span, msg: &str, note: &str) {
+    let mut panics = Vec::new();
+    let mut in_catch_unwind = false;
+    let _: Option<!> = for_each_expr_with_closures(cx, body, |e| {
+        if is_catch_unwind(cx, e) || is_caught_closure(cx, body, e) {
+            in_catch_unwind = true;
+            return ControlFlow::Continue(Descend::No);
+        }
+        if let Some((span, source)) = panic_source(cx, cx.typeck_results(), e) {
+            let label = match source {
+                PanicSource::Macro(_) => "this panics",
+                PanicSource::Unwrap => "this unwrap may panic",
+                PanicSource::Expect => "this expect may panic",
+            };
+            panics.push((span, label));
+            return ControlFlow::Continue(Descend::No);
+        }
+        if !e.span.from_expansion() {
+            if crate::indexing_slicing::index_may_panic(cx, e) {
+                panics.push((e.span, "this indexing may panic"));
+            } else if let Some(label) = overflow_label(cx, e) {
+                panics.push((e.span, label));
+            }
+        }
+        ControlFlow::Continue(Descend::Yes)
+    });
+    if panics.is_empty() {
+        return;
+    }
+
+    span_lint_and_then(cx, PANIC_IN_EXTERN_FN, span, msg, |diag| {
+        let mut spans = MultiSpan::from_spans(panics.iter().map(|&(span, _)| span).collect());
+        for (span, label) in panics {
+            spans.push_span_label(span, label);
+        }
+        diag.span_note(spans, note);
+        if in_catch_unwind {
+            diag.help("move these into the closure passed to `std::panic::catch_unwind`");
+        } else {
+            diag.help("handle the errors, or wrap the body in `std::panic::catch_unwind`");
+        }
+    });
+}
+
+/// Returns the function with a non-unwinding ABI which calls the closure passed as the generic
+/// argument `index` of the function `def_id`: `def_id` itself, or a function its body refers to
+/// with that type parameter, e.g. `get_trampoline` returning `trampoline::<F>`.
+fn non_unwinding_caller(cx: &LateContext<'_>, def_id: DefId, index: usize) -> Option<DefId> {
+    if cannot_unwind(cx.tcx.fn_sig(def_id).abi()) {
+        return Some(def_id);
+    }
+    let local_id = def_id.as_local()?;
+    let param = cx.tcx.generics_of(def_id).param_at(index, cx.tcx);
+    let body = cx.tcx.hir().body(cx.tcx.hir().maybe_body_owned_by(local_id)?);
+    let typeck = cx.tcx.typeck(local_id);
+    for_each_expr(body.value, |e| {
+        if let ExprKind::Path(ref qpath) = e.kind
+            && let Res::Def(DefKind::Fn | DefKind::AssocFn, callee) = typeck.qpath_res(qpath, e.hir_id)
+            && cannot_unwind(cx.tcx.fn_sig(callee).abi())
+            && typeck
+                .node_substs(e.hir_id)
+                .types()
+                .any(|ty| matches!(*ty.kind(), ty::Param(ty_param) if ty_param.index == param.index))
+        {
+            ControlFlow::Break(callee)
+        } else {
+            ControlFlow::Continue(())
+        }
+    })
+}
+
+/// Checks if a panic can't unwind out of a function with the given ABI.
+fn cannot_unwind(abi: Abi) -> bool {
+    match abi {
+        Abi::C { unwind }
+        | Abi::Cdecl { unwind }
+        | Abi::Stdcall { unwind }
+        | Abi::Fastcall { unwind }
+        | Abi::Vectorcall { unwind }
+        | Abi::Thiscall { unwind }
+        | Abi::Aapcs { unwind }
+        | Abi::Win64 { unwind }
+        | Abi::SysV64 { unwind }
+        | Abi::System { unwind } => !unwind,
+        Abi::EfiApi => true,
+        _ => false,
+    }
+}
+
+fn is_catch_unwind(cx: &LateContext<'_>, expr: &Expr<'_>) -> bool {
+    if let ExprKind::Call(func, _) = expr.kind
+        && let ExprKind::Path(ref qpath) = func.kind
+        && let Some(def_id) = cx.qpath_res(qpath, func.hir_id).opt_def_id()
+    {
+        match_def_path(cx, def_id, &paths::CATCH_UNWIND)
+    } else {
+        false
+    }
+}
+
+/// Checks if `expr` is a closure stored in a local which is only used in the arguments of
+/// `catch_unwind`, e.g. `let f = || ..; catch_unwind(f)`.
+fn is_caught_closure<'tcx>(cx: &LateContext<'tcx>, body: &'tcx Expr<'tcx>, expr: &Expr<'_>) -> bool {
+    let ExprKind::Closure(_) = expr.kind else {
+        return false;
+    };
+    let Some(Node::Local(local)) = get_parent_node(cx.tcx, expr.hir_id) else {
+        return false;
+    };
+    let PatKind::Binding(_, id, ..) = local.pat.kind else {
+        return false;
+    };
+
+    let mut caught = false;
+    let uncaught_use = for_each_expr_with_closures(cx, body, |e| {
+        if is_catch_unwind(cx, e) {
+            caught |= is_local_used(cx, e, id);
+            ControlFlow::Continue(Descend::No)
+        } else if path_to_local_id(e, id) {
+            ControlFlow::Break(())
+        } else {
+            ControlFlow::Continue(Descend::Yes)
+        }
+    });
+    caught && uncaught_use.is_none()
+}
+
+/// Returns the label to use if `expr` is an integer operation which may overflow, divide by zero
+/// or shift too far.
+fn overflow_label<'tcx>(cx: &LateContext<'tcx>, expr: &'tcx Expr<'tcx>) -> Option<&'static str> {
+    let (op, operand) = match expr.kind {
+        ExprKind::Binary(op, lhs, _) | ExprKind::AssignOp(op, lhs, _) => (Some(op.node), lhs),
+        ExprKind::Unary(UnOp::Neg, operand) => (None, operand),
+        _ => return None,
+    };
+    if !cx.typeck_results().expr_ty(operand).peel_refs().is_integral() || value_range::is_overflow_free(cx, expr) {
+        return None;
+    }
+    match op {
+        Some(BinOpKind::Div | BinOpKind::Rem) => Some("this may divide by zero or overflow"),
+        Some(BinOpKind::Add | BinOpKind::Sub | BinOpKind::Mul | BinOpKind::Shl | BinOpKind::Shr) | None => {
+            Some("this may overflow in debug builds")
+        },
+        _ => None,
+    }
+}
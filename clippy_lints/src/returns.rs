@@ -1,8 +1,9 @@
 use clippy_utils::diagnostics::{span_lint_and_then, span_lint_hir_and_then};
-use clippy_utils::source::{snippet_opt, snippet_with_context};
+use clippy_utils::source::{reattach_comments, snippet_opt, snippet_with_context};
 use clippy_utils::visitors::{for_each_expr, Descend};
 use clippy_utils::{fn_def_id, path_to_local_id, span_find_starting_semi};
 use core::ops::ControlFlow;
+use core::slice;
 use if_chain::if_chain;
 use rustc_errors::Applicability;
 use rustc_hir::intravisit::FnKind;
@@ -270,6 +271,8 @@ fn emit_return_lint(
             snippet.to_string()
         },
     );
+    let kept = inner_span.as_ref().map_or(&[][..], slice::from_ref);
+    let return_replacement = reattach_comments(cx, ret_span, kept, return_replacement, &mut applicability);
     let sugg_help = if inner_span.is_some() {
         "remove `return`"
     } else {
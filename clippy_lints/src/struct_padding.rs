@@ -0,0 +1,169 @@
+use clippy_utils::diagnostics::span_lint_and_then;
+use rustc_errors::MultiSpan;
+use rustc_hir::{FieldDef, Item, ItemKind, VariantData};
+use rustc_lint::{LateContext, LateLintPass};
+use rustc_middle::lint::in_external_macro;
+use rustc_middle::ty;
+use rustc_middle::ty::layout::LayoutOf;
+use rustc_session::{declare_tool_lint, impl_lint_pass};
+use rustc_target::abi::{Align, Size};
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for `#[repr(C)]` structs whose fields are declared in an order which wastes more
+    /// bytes in padding than reordering them would.
+    ///
+    /// ### Why is this bad?
+    /// The compiler lays out the fields of `repr(C)` structs in declaration order, inserting
+    /// padding so that each field is aligned. Declaring the fields by decreasing alignment makes
+    /// the struct smaller, which matters when many of them are stored, e.g. in a large `Vec`.
+    ///
+    /// ### Known problems
+    /// Reordering the fields changes the layout, which is wrong if the struct must match a type
+    /// defined in another language. Generic structs are not checked, and neither are tuple
+    /// structs, whose fields are named by their position.
+    ///
+    /// ### Example
+    /// ```rust
+    /// #[repr(C)]
+    /// struct Packet {
+    ///     kind: u8,
+    ///     id: u64,
+    ///     flags: u8,
+    /// }
+    /// ```
+    /// Use instead:
+    /// ```rust
+    /// #[repr(C)]
+    /// struct Packet {
+    ///     id: u64,
+    ///     kind: u8,
+    ///     flags: u8,
+    /// }
+    /// ```
+    #[clippy::version = "1.68.0"]
+    pub STRUCT_PADDING,
+    pedantic,
+    "`repr(C)` structs whose field order wastes space in padding"
+}
+
+#[derive(Copy, Clone)]
+pub struct StructPadding {
+    struct_padding_threshold: u64,
+}
+
+impl StructPadding {
+    #[must_use]
+    pub fn new(struct_padding_threshold: u64) -> Self {
+        Self {
+            struct_padding_threshold,
+        }
+    }
+}
+
+impl_lint_pass!(StructPadding => [STRUCT_PADDING]);
+
+/// The size and alignment of a field, in declaration order.
+#[derive(Clone, Copy)]
+struct FieldLayout {
+    offset: Size,
+    size: Size,
+    align: Align,
+}
+
+impl<'tcx> LateLintPass<'tcx> for StructPadding {
+    fn check_item(&mut self, cx: &LateContext<'tcx>, item: &Item<'tcx>) {
+        let ItemKind::Struct(ref data @ VariantData::Struct(..), generics) = item.kind else {
+            return;
+        };
+        if !generics.params.is_empty() || in_external_macro(cx.tcx.sess, item.span) {
+            return;
+        }
+        let ty = cx.tcx.type_of(item.owner_id);
+        let ty::Adt(adt, _) = ty.kind() else {
+            return;
+        };
+        let repr = adt.repr();
+        if !repr.c() || repr.pack.is_some() {
+            return;
+        }
+        let Ok(layout) = cx.layout_of(ty) else {
+            return;
+        };
+        // The unsized last field has to stay last
+        if layout.is_unsized() {
+            return;
+        }
+        let fields: Vec<FieldLayout> = (0..data.fields().len())
+            .map(|i| {
+                let field = layout.field(cx, i);
+                FieldLayout {
+                    offset: layout.fields.offset(i),
+                    size: field.size,
+                    align: field.align.abi,
+                }
+            })
+            .collect();
+
+        let mut order: Vec<usize> = (0..fields.len()).collect();
+        // `sort_by_key` is stable, so fields with the same alignment keep their relative order
+        order.sort_by_key(|&i| std::cmp::Reverse(fields[i].align));
+        let optimal_size = c_layout_size(order.iter().map(|&i| fields[i]), layout.align.abi);
+        let saved = layout.size.bytes().saturating_sub(optimal_size.bytes());
+        if saved <= self.struct_padding_threshold {
+            return;
+        }
+
+        let hir_fields = data.fields();
+        span_lint_and_then(
+            cx,
+            STRUCT_PADDING,
+            item.span,
+            &format!("the field order of this `repr(C)` struct wastes {saved} bytes in padding"),
+            |diag| {
+                let mut spans = MultiSpan::from_spans(hir_fields.iter().map(|field| field.span).collect());
+                for (i, (field, hir_field)) in fields.iter().zip(hir_fields).enumerate() {
+                    let end = fields.get(i + 1).map_or(layout.size, |next| next.offset);
+                    let padding = end.bytes().saturating_sub((field.offset + field.size).bytes());
+                    let label = if padding == 0 {
+                        format!("offset {}, size {}", field.offset.bytes(), field.size.bytes())
+                    } else {
+                        format!(
+                            "offset {}, size {}, followed by {padding} bytes of padding",
+                            field.offset.bytes(),
+                            field.size.bytes()
+                        )
+                    };
+                    spans.push_span_label(hir_field.span, label);
+                }
+                diag.span_note(
+                    spans,
+                    format!(
+                        "the fields are laid out in declaration order: `{}`, for a size of {} bytes",
+                        field_names(hir_fields, 0..fields.len()),
+                        layout.size.bytes()
+                    ),
+                );
+                diag.help(format!(
+                    "declare the fields by decreasing alignment: `{}`, for a size of {} bytes",
+                    field_names(hir_fields, order.iter().copied()),
+                    optimal_size.bytes()
+                ));
+            },
+        );
+    }
+}
+
+/// The size of a `repr(C)` struct with the given fields, in this order.
+fn c_layout_size(fields: impl Iterator<Item = FieldLayout>, align: Align) -> Size {
+    fields
+        .fold(Size::ZERO, |offset, field| offset.align_to(field.align) + field.size)
+        .align_to(align)
+}
+
+fn field_names(fields: &[FieldDef<'_>], order: impl Iterator<Item = usize>) -> String {
+    order
+        .map(|i| fields[i].ident.to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
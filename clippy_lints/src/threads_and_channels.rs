@@ -0,0 +1,197 @@
+use clippy_utils::diagnostics::{span_lint_and_help, span_lint_and_sugg};
+use clippy_utils::ty::match_type;
+use clippy_utils::{def_path_def_ids, path_def_id, paths};
+use rustc_errors::Applicability;
+use rustc_hir::def_id::DefIdSet;
+use rustc_hir::{Expr, ExprKind, Local, PatKind, Stmt, StmtKind};
+use rustc_lint::{LateContext, LateLintPass};
+use rustc_middle::lint::in_external_macro;
+use rustc_session::{declare_tool_lint, impl_lint_pass};
+use rustc_span::Span;
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for calls to functions creating an unbounded channel, like
+    /// `std::sync::mpsc::channel` or `crossbeam_channel::unbounded`.
+    ///
+    /// The list of constructors can be configured with `unbounded-channel-constructors`.
+    ///
+    /// ### Why is this bad?
+    /// Sending on an unbounded channel never blocks. If the consumer can't keep up with the
+    /// producers, messages pile up in the channel until the process runs out of memory.
+    ///
+    /// ### Example
+    /// ```rust
+    /// let (tx, rx) = std::sync::mpsc::channel::<u32>();
+    /// ```
+    /// Use instead:
+    /// ```rust
+    /// let (tx, rx) = std::sync::mpsc::sync_channel::<u32>(64);
+    /// ```
+    #[clippy::version = "1.68.0"]
+    pub UNBOUNDED_CHANNEL,
+    restriction,
+    "creating an unbounded channel"
+}
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for `JoinHandle`s of spawned threads which are dropped immediately, either as a
+    /// statement or with `let _ = ..`.
+    ///
+    /// ### Why is this bad?
+    /// Dropping the handle detaches the thread: nothing will notice if it panics, and the
+    /// process may exit before it's done.
+    ///
+    /// ### Example
+    /// ```rust
+    /// # fn work() {}
+    /// std::thread::spawn(work);
+    /// ```
+    /// Use instead:
+    /// ```rust
+    /// # fn work() {}
+    /// let worker = std::thread::spawn(work);
+    /// // ..
+    /// worker.join().unwrap_or_else(|payload| std::panic::resume_unwind(payload));
+    /// ```
+    /// Or, if the thread is meant to be detached, drop the handle explicitly with `drop`.
+    #[clippy::version = "1.68.0"]
+    pub DROPPED_JOIN_HANDLE,
+    restriction,
+    "`JoinHandle`s which are dropped immediately"
+}
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for `unwrap` or `expect` on the result of joining a thread.
+    ///
+    /// ### Why is this bad?
+    /// The error is the payload of the thread's panic, which doesn't implement `Debug`: the
+    /// resulting panic only says `Any { .. }`, and the original message is lost for anything
+    /// catching it, like the test harness. `std::panic::resume_unwind` propagates the payload
+    /// instead.
+    ///
+    /// ### Example
+    /// ```rust
+    /// let handle = std::thread::spawn(|| 1);
+    /// let one = handle.join().unwrap();
+    /// ```
+    /// Use instead:
+    /// ```rust
+    /// let handle = std::thread::spawn(|| 1);
+    /// let one = handle.join().unwrap_or_else(|payload| std::panic::resume_unwind(payload));
+    /// ```
+    #[clippy::version = "1.68.0"]
+    pub JOIN_UNWRAP,
+    suspicious,
+    "unwrapping the result of joining a thread, which loses the panic message"
+}
+
+#[derive(Clone)]
+pub struct ThreadsAndChannels {
+    unbounded_channel_constructors: Vec<String>,
+    constructor_ids: DefIdSet,
+}
+
+impl ThreadsAndChannels {
+    #[must_use]
+    pub fn new(unbounded_channel_constructors: Vec<String>) -> Self {
+        Self {
+            unbounded_channel_constructors,
+            constructor_ids: DefIdSet::default(),
+        }
+    }
+}
+
+impl_lint_pass!(ThreadsAndChannels => [UNBOUNDED_CHANNEL, DROPPED_JOIN_HANDLE, JOIN_UNWRAP]);
+
+impl<'tcx> LateLintPass<'tcx> for ThreadsAndChannels {
+    fn check_crate(&mut self, cx: &LateContext<'tcx>) {
+        for path in &self.unbounded_channel_constructors {
+            let segs: Vec<_> = path.split("::").collect();
+            self.constructor_ids.extend(def_path_def_ids(cx, &segs));
+        }
+    }
+
+    fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'tcx>) {
+        if in_external_macro(cx.tcx.sess, expr.span) {
+            return;
+        }
+        match expr.kind {
+            ExprKind::Call(func, _) if path_def_id(cx, func).map_or(false, |id| self.constructor_ids.contains(&id)) => {
+                span_lint_and_help(
+                    cx,
+                    UNBOUNDED_CHANNEL,
+                    expr.span,
+                    "creating an unbounded channel",
+                    None,
+                    "use a bounded channel, like `std::sync::mpsc::sync_channel`, so that senders wait \
+                    when the receiver can't keep up",
+                );
+            },
+            ExprKind::MethodCall(path, recv, _, _) => {
+                check_join_unwrap(cx, expr, path.ident.as_str(), path.ident.span, recv);
+            },
+            _ => {},
+        }
+    }
+
+    fn check_stmt(&mut self, cx: &LateContext<'tcx>, stmt: &'tcx Stmt<'tcx>) {
+        if let StmtKind::Semi(expr) = stmt.kind {
+            check_dropped_join_handle(cx, stmt.span, expr);
+        }
+    }
+
+    fn check_local(&mut self, cx: &LateContext<'tcx>, local: &'tcx Local<'tcx>) {
+        // `let _ = handle;` neither moves nor drops the handle
+        if let PatKind::Wild = local.pat.kind
+            && let Some(init) = local.init
+            && !init.is_syntactic_place_expr()
+        {
+            check_dropped_join_handle(cx, local.span, init);
+        }
+    }
+}
+
+fn is_join_handle(cx: &LateContext<'_>, expr: &Expr<'_>) -> bool {
+    let ty = cx.typeck_results().expr_ty(expr);
+    match_type(cx, ty, &paths::JOIN_HANDLE) || match_type(cx, ty, &paths::SCOPED_JOIN_HANDLE)
+}
+
+fn check_dropped_join_handle(cx: &LateContext<'_>, span: Span, expr: &Expr<'_>) {
+    // Scoped threads are joined at the end of the scope, dropping their handle is fine
+    if !in_external_macro(cx.tcx.sess, span) && match_type(cx, cx.typeck_results().expr_ty(expr), &paths::JOIN_HANDLE) {
+        span_lint_and_help(
+            cx,
+            DROPPED_JOIN_HANDLE,
+            span,
+            "the `JoinHandle` of this thread is dropped immediately",
+            None,
+            "keep the handle and `join` it to notice if the thread panics, or detach it explicitly with `drop`",
+        );
+    }
+}
+
+fn check_join_unwrap(cx: &LateContext<'_>, expr: &Expr<'_>, method: &str, method_span: Span, recv: &Expr<'_>) {
+    if let "unwrap" | "expect" = method
+        && let ExprKind::MethodCall(join, handle, [], _) = recv.kind
+        && join.ident.as_str() == "join"
+        && is_join_handle(cx, handle)
+    {
+        span_lint_and_sugg(
+            cx,
+            JOIN_UNWRAP,
+            method_span.with_hi(expr.span.hi()),
+            &format!("`{method}` on the result of joining a thread loses the message of its panic"),
+            "propagate the panic instead",
+            "unwrap_or_else(|payload| std::panic::resume_unwind(payload))".to_string(),
+            // The message passed to `expect` is dropped
+            if method == "unwrap" {
+                Applicability::MachineApplicable
+            } else {
+                Applicability::MaybeIncorrect
+            },
+        );
+    }
+}
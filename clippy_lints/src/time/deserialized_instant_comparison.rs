@@ -0,0 +1,95 @@
+use crate::instant_subtraction::is_instant_now_call;
+use clippy_utils::diagnostics::span_lint_and_then;
+use clippy_utils::ty::is_instant;
+use clippy_utils::{paths, peel_hir_expr_refs};
+use rustc_hir::{BinOpKind, Expr, ExprKind, PathSegment};
+use rustc_lint::LateContext;
+use rustc_middle::ty;
+
+use super::{implements_serde_trait, serde_field_options, DESERIALIZED_INSTANT_COMPARISON};
+
+pub(super) fn check_binary<'tcx>(
+    cx: &LateContext<'tcx>,
+    expr: &'tcx Expr<'tcx>,
+    op: BinOpKind,
+    lhs: &'tcx Expr<'tcx>,
+    rhs: &'tcx Expr<'tcx>,
+) {
+    if matches!(
+        op,
+        BinOpKind::Sub | BinOpKind::Lt | BinOpKind::Le | BinOpKind::Gt | BinOpKind::Ge | BinOpKind::Eq | BinOpKind::Ne
+    ) && let Some(deserialized) = now_and_deserialized(cx, lhs, rhs)
+    {
+        emit(cx, expr, deserialized);
+    }
+}
+
+pub(super) fn check_method<'tcx>(
+    cx: &LateContext<'tcx>,
+    expr: &'tcx Expr<'tcx>,
+    path: &PathSegment<'_>,
+    recv: &'tcx Expr<'tcx>,
+    args: &'tcx [Expr<'tcx>],
+) {
+    match (path.ident.as_str(), args) {
+        ("elapsed", []) if is_deserialized_instant(cx, recv) => emit(cx, expr, recv),
+        ("duration_since" | "checked_duration_since" | "saturating_duration_since" | "cmp" | "partial_cmp", [arg]) => {
+            if let Some(deserialized) = now_and_deserialized(cx, recv, arg) {
+                emit(cx, expr, deserialized);
+            }
+        },
+        _ => {},
+    }
+}
+
+/// If one of the operands is `Instant::now()` and the other one a deserialized `Instant`, returns
+/// the latter.
+fn now_and_deserialized<'a>(cx: &LateContext<'_>, lhs: &'a Expr<'a>, rhs: &'a Expr<'a>) -> Option<&'a Expr<'a>> {
+    if is_instant_now_call(cx, lhs) && is_deserialized_instant(cx, rhs) {
+        Some(rhs)
+    } else if is_instant_now_call(cx, rhs) && is_deserialized_instant(cx, lhs) {
+        Some(lhs)
+    } else {
+        None
+    }
+}
+
+/// Checks if the expression is an `Instant` field of a type implementing `Deserialize`, which is
+/// neither skipped nor converted when deserializing.
+fn is_deserialized_instant<'a>(cx: &LateContext<'_>, expr: &'a Expr<'a>) -> bool {
+    let typeck = cx.typeck_results();
+    let field_expr = peel_hir_expr_refs(expr).0;
+    if let ExprKind::Field(base, _) = field_expr.kind
+        && is_instant(cx, typeck.expr_ty(expr).peel_refs())
+        // The type of the base after auto-deref is the one declaring the field
+        && let base_ty = typeck.expr_ty_adjusted(base).peel_refs()
+        && let ty::Adt(adt, _) = base_ty.kind()
+        && implements_serde_trait(cx, base_ty, &paths::SERDE_DESERIALIZE)
+    {
+        let field = &adt.non_enum_variant().fields[typeck.field_index(field_expr.hir_id)];
+        !serde_field_options(cx, field.did).any(|option| {
+            matches!(
+                option.as_str(),
+                "skip" | "skip_deserializing" | "with" | "deserialize_with"
+            )
+        })
+    } else {
+        false
+    }
+}
+
+fn emit(cx: &LateContext<'_>, expr: &Expr<'_>, deserialized: &Expr<'_>) {
+    span_lint_and_then(
+        cx,
+        DESERIALIZED_INSTANT_COMPARISON,
+        expr.span,
+        "comparing the current `Instant` with a deserialized one",
+        |diag| {
+            diag.span_note(
+                deserialized.span,
+                "this `Instant` may come from another process, which uses a different timebase",
+            );
+            diag.help("serialize a `SystemTime` instead");
+        },
+    );
+}
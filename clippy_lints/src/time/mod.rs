@@ -0,0 +1,197 @@
+mod deserialized_instant_comparison;
+mod overflowing_duration_constructor;
+mod serialized_instant;
+mod unchecked_system_time_difference;
+
+use clippy_utils::get_trait_def_id;
+use rustc_hir::def_id::DefId;
+use rustc_hir::{Expr, ExprKind, Item};
+use rustc_lint::{LateContext, LateLintPass};
+use rustc_middle::ty::Ty;
+use rustc_session::{declare_lint_pass, declare_tool_lint};
+use rustc_span::Symbol;
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for `unwrap` or `expect` on the result of `SystemTime::duration_since` or
+    /// `SystemTime::elapsed`.
+    ///
+    /// ### Why is this bad?
+    /// The system clock isn't monotonic: it can be adjusted backwards at any time, e.g. by NTP,
+    /// in which case these methods return an error, and the code panics.
+    ///
+    /// Differences with `UNIX_EPOCH` aren't linted, since they only fail if the clock is set
+    /// before 1970.
+    ///
+    /// ### Example
+    /// ```rust
+    /// # use std::time::SystemTime;
+    /// let start = SystemTime::now();
+    /// let took = SystemTime::now().duration_since(start).unwrap();
+    /// ```
+    /// Use instead:
+    /// ```rust
+    /// # use std::time::SystemTime;
+    /// let start = SystemTime::now();
+    /// let took = SystemTime::now().duration_since(start).unwrap_or_default();
+    /// ```
+    /// Or use an `Instant`, which is monotonic, to measure elapsed time.
+    #[clippy::version = "1.68.0"]
+    pub UNCHECKED_SYSTEM_TIME_DIFFERENCE,
+    suspicious,
+    "unwrapping the difference between two `SystemTime`s, which panics on clock skew"
+}
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for fields containing an `Instant` in types which implement serde's `Serialize` or
+    /// `Deserialize`.
+    ///
+    /// Fields skipped with `#[serde(skip)]`, or converted with `#[serde(with = "..")]`,
+    /// `serialize_with` or `deserialize_with`, are not linted: the conversion usually stores the
+    /// time relative to the current `Instant`. Serde doesn't implement its traits for `Instant`,
+    /// so this mostly lints manual implementations.
+    ///
+    /// ### Why is this bad?
+    /// An `Instant` is an opaque reading of a clock whose origin is only meaningful within the
+    /// running process. Once written out and read back, by another process or after a reboot, it
+    /// can't be compared to `Instant::now()` anymore.
+    ///
+    /// ### Example
+    /// ```rust,ignore
+    /// struct Session {
+    ///     last_seen: Instant,
+    /// }
+    ///
+    /// impl Serialize for Session {
+    ///     // ..
+    /// }
+    /// ```
+    /// Use instead:
+    /// ```rust,ignore
+    /// #[derive(Serialize, Deserialize)]
+    /// struct Session {
+    ///     last_seen: SystemTime,
+    /// }
+    /// ```
+    #[clippy::version = "1.68.0"]
+    pub SERIALIZED_INSTANT,
+    suspicious,
+    "`Instant` fields in serialized types"
+}
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for `Duration` constructors, like `Duration::from_secs`, whose argument is a
+    /// multiplication by a constant unit factor which may overflow.
+    ///
+    /// ### Why is this bad?
+    /// Converting units by hand, as in `Duration::from_secs(hours * 60 * 60)`, panics in debug
+    /// builds and silently wraps around in release builds if the value is too large.
+    ///
+    /// ### Example
+    /// ```rust
+    /// # use std::time::Duration;
+    /// fn timeout(hours: u64) -> Duration {
+    ///     Duration::from_secs(hours * 60 * 60)
+    /// }
+    /// ```
+    /// Use instead:
+    /// ```rust
+    /// # use std::time::Duration;
+    /// fn timeout(hours: u64) -> Option<Duration> {
+    ///     hours.checked_mul(3600).map(Duration::from_secs)
+    /// }
+    /// ```
+    #[clippy::version = "1.68.0"]
+    pub OVERFLOWING_DURATION_CONSTRUCTOR,
+    pedantic,
+    "unit conversions in `Duration` constructor arguments which may overflow"
+}
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for `Instant::now()` being compared to, or subtracted from, an `Instant` which is a
+    /// field of a type implementing serde's `Deserialize`.
+    ///
+    /// Fields skipped with `#[serde(skip)]` or `skip_deserializing`, or converted with
+    /// `#[serde(with = "..")]` or `deserialize_with`, are not linted: their `Instant` is usually
+    /// created by the current process.
+    ///
+    /// ### Why is this bad?
+    /// The deserialized `Instant` was likely created by another process, or before a reboot, and
+    /// uses a different timebase: the comparison is meaningless.
+    ///
+    /// ### Known problems
+    /// Only `Instant`s read directly from a field of a deserializable type are recognized.
+    ///
+    /// ### Example
+    /// ```rust,ignore
+    /// let session: Session = serde_json::from_str(&data)?;
+    /// if Instant::now() - session.last_seen > TIMEOUT {
+    ///     // ..
+    /// }
+    /// ```
+    /// Use instead:
+    /// ```rust,ignore
+    /// // `last_seen` is now a `SystemTime`
+    /// let session: Session = serde_json::from_str(&data)?;
+    /// if session.last_seen.elapsed().unwrap_or_default() > TIMEOUT {
+    ///     // ..
+    /// }
+    /// ```
+    #[clippy::version = "1.68.0"]
+    pub DESERIALIZED_INSTANT_COMPARISON,
+    suspicious,
+    "comparing `Instant::now()` with a deserialized `Instant`"
+}
+
+declare_lint_pass!(Time => [
+    UNCHECKED_SYSTEM_TIME_DIFFERENCE,
+    SERIALIZED_INSTANT,
+    OVERFLOWING_DURATION_CONSTRUCTOR,
+    DESERIALIZED_INSTANT_COMPARISON,
+]);
+
+impl<'tcx> LateLintPass<'tcx> for Time {
+    fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'tcx>) {
+        if expr.span.from_expansion() {
+            return;
+        }
+        match expr.kind {
+            ExprKind::MethodCall(path, recv, args, _) => {
+                unchecked_system_time_difference::check(cx, expr, path, recv);
+                deserialized_instant_comparison::check_method(cx, expr, path, recv, args);
+            },
+            ExprKind::Binary(op, lhs, rhs) => {
+                deserialized_instant_comparison::check_binary(cx, expr, op.node, lhs, rhs);
+            },
+            ExprKind::Call(func, [arg]) => overflowing_duration_constructor::check(cx, expr, func, arg),
+            _ => {},
+        }
+    }
+
+    fn check_item(&mut self, cx: &LateContext<'tcx>, item: &'tcx Item<'tcx>) {
+        serialized_instant::check(cx, item);
+    }
+}
+
+/// The names of the options of the `#[serde(..)]` attributes of the field `field`, like `skip` or
+/// `with`.
+fn serde_field_options<'tcx>(cx: &LateContext<'tcx>, field: DefId) -> impl Iterator<Item = Symbol> + 'tcx {
+    cx.tcx
+        .get_attrs_unchecked(field)
+        .iter()
+        .filter(|attr| attr.has_name(sym!(serde)))
+        .filter_map(rustc_ast::Attribute::meta_item_list)
+        .flatten()
+        .map(|item| item.name_or_empty())
+}
+
+/// Checks if the type implements the serde trait with the given path, like
+/// `paths::SERDE_DESERIALIZE`. Returns `false` if serde isn't a dependency.
+fn implements_serde_trait<'tcx>(cx: &LateContext<'tcx>, ty: Ty<'tcx>, path: &[&str]) -> bool {
+    get_trait_def_id(cx, path).map_or(false, |trait_id| {
+        cx.tcx.non_blanket_impls_for_ty(trait_id, ty).next().is_some()
+    })
+}
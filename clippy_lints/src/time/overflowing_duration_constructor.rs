@@ -0,0 +1,71 @@
+use clippy_utils::consts::{constant, Constant};
+use clippy_utils::diagnostics::span_lint_and_then;
+use clippy_utils::sugg::Sugg;
+use clippy_utils::ty::is_duration;
+use clippy_utils::{path_def_id, value_range};
+use rustc_hir::{BinOpKind, Expr, ExprKind};
+use rustc_lint::LateContext;
+
+use super::OVERFLOWING_DURATION_CONSTRUCTOR;
+
+pub(super) fn check<'tcx>(cx: &LateContext<'tcx>, expr: &'tcx Expr<'tcx>, func: &Expr<'_>, arg: &'tcx Expr<'tcx>) {
+    let Some(def_id) = path_def_id(cx, func) else {
+        return;
+    };
+    let name = cx.tcx.item_name(def_id);
+    if !matches!(
+        name.as_str(),
+        "from_secs" | "from_millis" | "from_micros" | "from_nanos"
+    ) || !cx
+        .tcx
+        .impl_of_method(def_id)
+        .map_or(false, |impl_id| is_duration(cx, cx.tcx.type_of(impl_id)))
+        || !matches!(arg.kind, ExprKind::Binary(op, ..) if op.node == BinOpKind::Mul)
+    {
+        return;
+    }
+
+    let mut factors = Vec::new();
+    collect_factors(arg, &mut factors);
+    let mut unit = 1u128;
+    let mut values = Vec::new();
+    for factor in factors {
+        match constant(cx, cx.typeck_results(), factor) {
+            Some((Constant::Int(factor), _)) => unit = unit.saturating_mul(factor),
+            _ => values.push(factor),
+        }
+    }
+    // Only unit conversions are linted, not the product of two arbitrary values
+    if unit <= 1 || values.is_empty() || value_range::is_overflow_free(cx, arg) {
+        return;
+    }
+
+    span_lint_and_then(
+        cx,
+        OVERFLOWING_DURATION_CONSTRUCTOR,
+        expr.span,
+        &format!("this multiplication may overflow before `Duration::{name}` is called"),
+        |diag| {
+            if let [value] = values[..] {
+                let value = Sugg::hir(cx, value, "..").maybe_par();
+                diag.help(format!(
+                    "use checked arithmetic, like `{value}.checked_mul({unit}).map(Duration::{name})`"
+                ));
+            } else {
+                diag.help("use checked arithmetic, like `checked_mul`");
+            }
+        },
+    );
+}
+
+/// Collects the operands of a chain of multiplications, like `a * 60 * 60`.
+fn collect_factors<'tcx>(expr: &'tcx Expr<'tcx>, factors: &mut Vec<&'tcx Expr<'tcx>>) {
+    if let ExprKind::Binary(op, lhs, rhs) = expr.kind
+        && op.node == BinOpKind::Mul
+    {
+        collect_factors(lhs, factors);
+        collect_factors(rhs, factors);
+    } else {
+        factors.push(expr);
+    }
+}
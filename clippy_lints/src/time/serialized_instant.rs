@@ -0,0 +1,50 @@
+use clippy_utils::diagnostics::span_lint_and_then;
+use clippy_utils::paths;
+use clippy_utils::ty::contains_instant;
+use rustc_hir::{FieldDef, Item, ItemKind};
+use rustc_lint::LateContext;
+use rustc_middle::lint::in_external_macro;
+
+use super::{implements_serde_trait, serde_field_options, SERIALIZED_INSTANT};
+
+pub(super) fn check<'tcx>(cx: &LateContext<'tcx>, item: &'tcx Item<'tcx>) {
+    let fields: Vec<&FieldDef<'_>> = match item.kind {
+        ItemKind::Struct(ref data, _) | ItemKind::Union(ref data, _) => data.fields().iter().collect(),
+        ItemKind::Enum(ref def, _) => def.variants.iter().flat_map(|variant| variant.data.fields()).collect(),
+        _ => return,
+    };
+    if in_external_macro(cx.tcx.sess, item.span) {
+        return;
+    }
+    let ty = cx.tcx.type_of(item.owner_id);
+    if !implements_serde_trait(cx, ty, &paths::SERDE_SERIALIZE)
+        && !implements_serde_trait(cx, ty, &paths::SERDE_DESERIALIZE)
+    {
+        return;
+    }
+
+    for field in fields {
+        if contains_instant(cx, cx.tcx.type_of(field.def_id)) && !is_skipped_or_converted(cx, field) {
+            span_lint_and_then(
+                cx,
+                SERIALIZED_INSTANT,
+                field.span,
+                "this `Instant` is part of a serialized type",
+                |diag| {
+                    diag.note("an `Instant` is only meaningful within the process which created it");
+                    diag.help("store a `SystemTime` instead, or skip this field with `#[serde(skip)]`");
+                },
+            );
+        }
+    }
+}
+
+/// Checks for `#[serde(skip)]`, one of its variants, or a conversion of the field.
+fn is_skipped_or_converted(cx: &LateContext<'_>, field: &FieldDef<'_>) -> bool {
+    serde_field_options(cx, field.def_id.to_def_id()).any(|option| {
+        matches!(
+            option.as_str(),
+            "skip" | "skip_serializing" | "skip_deserializing" | "with" | "serialize_with" | "deserialize_with"
+        )
+    })
+}
@@ -0,0 +1,36 @@
+use clippy_utils::diagnostics::span_lint_and_sugg;
+use clippy_utils::ty::is_system_time;
+use clippy_utils::{match_def_path, path_def_id, paths};
+use rustc_errors::Applicability;
+use rustc_hir::{Expr, ExprKind, PathSegment};
+use rustc_lint::LateContext;
+
+use super::UNCHECKED_SYSTEM_TIME_DIFFERENCE;
+
+pub(super) fn check(cx: &LateContext<'_>, expr: &Expr<'_>, path: &PathSegment<'_>, recv: &Expr<'_>) {
+    if matches!(path.ident.as_str(), "unwrap" | "expect")
+        && let ExprKind::MethodCall(difference, time, args, _) = recv.kind
+        && matches!(difference.ident.as_str(), "duration_since" | "elapsed")
+        && is_system_time(cx, cx.typeck_results().expr_ty(time).peel_refs())
+        && !args.first().map_or(false, |earlier| is_unix_epoch(cx, earlier))
+    {
+        span_lint_and_sugg(
+            cx,
+            UNCHECKED_SYSTEM_TIME_DIFFERENCE,
+            path.ident.span.to(expr.span),
+            &format!(
+                "`SystemTime::{}` fails if the system clock went backwards, which would panic here",
+                difference.ident
+            ),
+            "use a zero `Duration` in that case",
+            "unwrap_or_default()".to_string(),
+            Applicability::MaybeIncorrect,
+        );
+    }
+}
+
+fn is_unix_epoch(cx: &LateContext<'_>, expr: &Expr<'_>) -> bool {
+    path_def_id(cx, expr).map_or(false, |def_id| {
+        match_def_path(cx, def_id, &paths::UNIX_EPOCH) || match_def_path(cx, def_id, &paths::SYSTEM_TIME_UNIX_EPOCH)
+    })
+}
@@ -1,10 +1,14 @@
 use std::ops::ControlFlow;
 
-use clippy_utils::diagnostics::span_lint_and_help;
+use crate::doc::safety_section;
+use crate::multiple_unsafe_ops_per_block::{collect_unsafe_exprs, UnsafeOp};
+use clippy_utils::diagnostics::{span_lint_and_help, span_lint_and_then};
 use clippy_utils::source::walk_span_to_context;
 use clippy_utils::visitors::{for_each_expr_with_closures, Descend};
 use clippy_utils::{get_parent_node, is_lint_allowed};
 use hir::HirId;
+use itertools::Itertools;
+use rustc_data_structures::fx::FxHashSet;
 use rustc_data_structures::sync::Lrc;
 use rustc_hir as hir;
 use rustc_hir::{Block, BlockCheckMode, ItemKind, Node, UnsafeSource};
@@ -92,7 +96,64 @@ declare_clippy_lint! {
     "annotating safe code with a safety comment"
 }
 
-declare_lint_pass!(UndocumentedUnsafeBlocks => [UNDOCUMENTED_UNSAFE_BLOCKS, UNNECESSARY_SAFETY_COMMENT]);
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks that the `// SAFETY: ` comment of an `unsafe` block addresses every
+    /// precondition listed in the `# Safety` section of the unsafe functions called
+    /// inside the block.
+    ///
+    /// This only applies to functions whose `# Safety` section is a bullet list. A
+    /// precondition is considered addressed if the safety comment either mentions one of
+    /// its code spans (or, if it has none, one of its significant words), or references it
+    /// by its position in the list, e.g. `(1)` for the first precondition.
+    ///
+    /// ### Why is this bad?
+    /// When a new precondition is added to an unsafe function, every call site has to be
+    /// re-checked. Safety comments that don't mention a precondition were likely written
+    /// before it existed, or overlooked it.
+    ///
+    /// ### Known problems
+    /// The matching is purely textual, so a comment can mention a precondition without
+    /// actually justifying it.
+    ///
+    /// ### Example
+    /// ```rust
+    /// /// # Safety
+    /// ///
+    /// /// - `ptr` must be valid for reads.
+    /// /// - `ptr` must be properly aligned.
+    /// unsafe fn read(ptr: *const u32) -> u32 {
+    ///     *ptr
+    /// }
+    ///
+    /// let x = 0;
+    /// // SAFETY: `ptr` comes from a reference, so it's valid for reads.
+    /// let y = unsafe { read(&x) };
+    /// ```
+    /// Use instead:
+    /// ```rust
+    /// # /// # Safety
+    /// # ///
+    /// # /// - `ptr` must be valid for reads.
+    /// # /// - `ptr` must be properly aligned.
+    /// # unsafe fn read(ptr: *const u32) -> u32 {
+    /// #     *ptr
+    /// # }
+    /// let x = 0;
+    /// // SAFETY: `ptr` comes from a reference, so it's valid for reads (1) and aligned (2).
+    /// let y = unsafe { read(&x) };
+    /// ```
+    #[clippy::version = "1.68.0"]
+    pub UNADDRESSED_SAFETY_PRECONDITIONS,
+    restriction,
+    "a safety comment that doesn't address every precondition of the called unsafe function"
+}
+
+declare_lint_pass!(UndocumentedUnsafeBlocks => [
+    UNDOCUMENTED_UNSAFE_BLOCKS,
+    UNNECESSARY_SAFETY_COMMENT,
+    UNADDRESSED_SAFETY_PRECONDITIONS,
+]);
 
 impl<'tcx> LateLintPass<'tcx> for UndocumentedUnsafeBlocks {
     fn check_block(&mut self, cx: &LateContext<'tcx>, block: &'tcx Block<'tcx>) {
@@ -100,8 +161,7 @@ impl<'tcx> LateLintPass<'tcx> for UndocumentedUnsafeBlocks {
             && !in_external_macro(cx.tcx.sess, block.span)
             && !is_lint_allowed(cx, UNDOCUMENTED_UNSAFE_BLOCKS, block.hir_id)
             && !is_unsafe_from_proc_macro(cx, block.span)
-            && !block_has_safety_comment(cx, block.span)
-            && !block_parents_have_safety_comment(cx, block.hir_id)
+            && matches!(block_has_safety_comment(cx, block), HasSafetyComment::No)
         {
             let source_map = cx.tcx.sess.source_map();
             let span = if source_map.is_multiline(block.span) {
@@ -120,6 +180,14 @@ impl<'tcx> LateLintPass<'tcx> for UndocumentedUnsafeBlocks {
             );
         }
 
+        if block.rules == BlockCheckMode::UnsafeBlock(UnsafeSource::UserProvided)
+            && !in_external_macro(cx.tcx.sess, block.span)
+            && !is_lint_allowed(cx, UNADDRESSED_SAFETY_PRECONDITIONS, block.hir_id)
+            && let Some(comment) = block_safety_comment(cx, block)
+        {
+            check_safety_preconditions(cx, block, &comment);
+        }
+
         if let Some(tail) = block.expr
             && !is_lint_allowed(cx, UNNECESSARY_SAFETY_COMMENT, tail.hir_id)
             && !in_external_macro(cx.tcx.sess, tail.span)
@@ -258,6 +326,140 @@ impl<'tcx> LateLintPass<'tcx> for UndocumentedUnsafeBlocks {
     }
 }
 
+fn check_safety_preconditions<'tcx>(cx: &LateContext<'tcx>, block: &'tcx Block<'tcx>, comment: &str) {
+    let comment = comment.to_lowercase();
+    let mut unsafe_ops = vec![];
+    collect_unsafe_exprs(cx, block, &mut unsafe_ops);
+    for (op, span) in unsafe_ops {
+        let (UnsafeOp::UnsafeFnCall(def_id) | UnsafeOp::UnsafeMethodCall(def_id)) = op else {
+            continue;
+        };
+        let Some(section) = safety_section(cx.tcx.get_attrs_unchecked(def_id)) else {
+            continue;
+        };
+        let unaddressed: Vec<_> = safety_preconditions(&section)
+            .into_iter()
+            .enumerate()
+            .filter(|(i, precondition)| !precondition.is_addressed_by(*i + 1, &comment))
+            .collect();
+        if unaddressed.is_empty() {
+            continue;
+        }
+
+        span_lint_and_then(
+            cx,
+            UNADDRESSED_SAFETY_PRECONDITIONS,
+            span,
+            &format!(
+                "the safety comment doesn't address every precondition of `{}`",
+                cx.tcx.def_path_str(def_id)
+            ),
+            |diag| {
+                for (i, precondition) in unaddressed {
+                    diag.note(format!("precondition ({}): {}", i + 1, precondition.text));
+                }
+                diag.help("mention each precondition in the safety comment, or reference it by its number, e.g. `(1)`");
+            },
+        );
+    }
+}
+
+/// A bullet of the `# Safety` section of an unsafe function's documentation.
+struct SafetyPrecondition {
+    text: String,
+    /// The words of which at least one has to appear in a safety comment addressing this
+    /// precondition.
+    keywords: Vec<String>,
+}
+
+impl SafetyPrecondition {
+    fn is_addressed_by(&self, number: usize, comment: &str) -> bool {
+        comment.contains(&format!("({number})")) || self.keywords.iter().any(|keyword| comment.contains(keyword))
+    }
+}
+
+/// Words that are too common in safety documentation to tell preconditions apart.
+const NON_KEYWORDS: &[&str] = &[
+    "about", "after", "before", "being", "below", "caller", "could", "ensure", "every", "there", "these", "those",
+    "which", "while", "within", "would",
+];
+
+/// Gets the top-level bullet list items of a `# Safety` section.
+///
+/// The keywords of a precondition are its code spans and its significant words. Keywords shared
+/// with other preconditions don't tell them apart, so they are only used if nothing else is left.
+fn safety_preconditions(section: &str) -> Vec<SafetyPrecondition> {
+    use pulldown_cmark::Event::{Code, End, SoftBreak, Start, Text};
+    use pulldown_cmark::{Parser, Tag};
+
+    let mut preconditions = vec![];
+    let mut depth = 0;
+    let mut text = String::new();
+    let mut keywords = FxHashSet::default();
+    for event in Parser::new(section) {
+        match event {
+            Start(Tag::Item) => {
+                depth += 1;
+                if depth == 1 {
+                    text.clear();
+                    keywords.clear();
+                } else {
+                    text.push(' ');
+                }
+            },
+            End(Tag::Item) => {
+                depth -= 1;
+                if depth == 0 {
+                    preconditions.push(SafetyPrecondition {
+                        text: text.trim().to_owned(),
+                        keywords: keywords.drain().collect(),
+                    });
+                }
+            },
+            Code(code) if depth > 0 => {
+                keywords.insert(code.to_lowercase());
+                text.push('`');
+                text.push_str(&code);
+                text.push('`');
+            },
+            Text(t) if depth > 0 => {
+                keywords.extend(
+                    t.split(|c: char| !c.is_alphanumeric() && c != '_')
+                        .map(str::to_lowercase)
+                        .filter(|word| word.len() >= 5 && !NON_KEYWORDS.contains(&word.as_str())),
+                );
+                text.push_str(&t);
+            },
+            SoftBreak if depth > 0 => text.push(' '),
+            _ => {},
+        }
+    }
+
+    let distinct_keywords: Vec<Vec<String>> = preconditions
+        .iter()
+        .enumerate()
+        .map(|(i, precondition)| {
+            precondition
+                .keywords
+                .iter()
+                .filter(|keyword| {
+                    !preconditions
+                        .iter()
+                        .enumerate()
+                        .any(|(j, other)| i != j && other.keywords.contains(keyword))
+                })
+                .cloned()
+                .collect()
+        })
+        .collect();
+    for (precondition, keywords) in preconditions.iter_mut().zip(distinct_keywords) {
+        if !keywords.is_empty() {
+            precondition.keywords = keywords;
+        }
+    }
+    preconditions
+}
+
 fn expr_has_unnecessary_safety_comment<'tcx>(
     cx: &LateContext<'tcx>,
     expr: &'tcx hir::Expr<'tcx>,
@@ -301,10 +503,10 @@ fn is_unsafe_from_proc_macro(cx: &LateContext<'_>, span: Span) -> bool {
 
 // Checks if any parent {expression, statement, block, local, const, static}
 // has a safety comment
-fn block_parents_have_safety_comment(cx: &LateContext<'_>, id: hir::HirId) -> bool {
+fn block_parents_have_safety_comment(cx: &LateContext<'_>, id: hir::HirId) -> HasSafetyComment {
     if let Some(node) = get_parent_node(cx.tcx, id) {
         return match node {
-            Node::Expr(expr) => !is_branchy(expr) && span_in_body_has_safety_comment(cx, expr.span),
+            Node::Expr(expr) if !is_branchy(expr) => span_in_body_has_safety_comment(cx, expr.span),
             Node::Stmt(hir::Stmt {
                 kind:
                     hir::StmtKind::Local(hir::Local { span, .. })
@@ -318,10 +520,10 @@ fn block_parents_have_safety_comment(cx: &LateContext<'_>, id: hir::HirId) -> bo
                 span,
                 ..
             }) => span_in_body_has_safety_comment(cx, *span),
-            _ => false,
+            _ => HasSafetyComment::No,
         };
     }
-    false
+    HasSafetyComment::No
 }
 
 /// Checks if an expression is "branchy", e.g. loop, match/if/etc.
@@ -332,8 +534,9 @@ fn is_branchy(expr: &hir::Expr<'_>) -> bool {
     )
 }
 
-/// Checks if the lines immediately preceding the block contain a safety comment.
-fn block_has_safety_comment(cx: &LateContext<'_>, span: Span) -> bool {
+/// Checks if the lines immediately preceding the block, or any of its parents, contain a safety
+/// comment.
+fn block_has_safety_comment(cx: &LateContext<'_>, block: &Block<'_>) -> HasSafetyComment {
     // This intentionally ignores text before the start of a function so something like:
     // ```
     //     // SAFETY: reason
@@ -342,10 +545,47 @@ fn block_has_safety_comment(cx: &LateContext<'_>, span: Span) -> bool {
     // won't work. This is to avoid dealing with where such a comment should be place relative to
     // attributes and doc comments.
 
-    matches!(
-        span_from_macro_expansion_has_safety_comment(cx, span),
-        HasSafetyComment::Yes(_)
-    ) || span_in_body_has_safety_comment(cx, span)
+    if let HasSafetyComment::Yes(pos) = span_from_macro_expansion_has_safety_comment(cx, block.span) {
+        return HasSafetyComment::Yes(pos);
+    }
+    match span_in_body_has_safety_comment(cx, block.span) {
+        HasSafetyComment::No => block_parents_have_safety_comment(cx, block.hir_id),
+        has_safety_comment => has_safety_comment,
+    }
+}
+
+/// Returns the text of the safety comment attached to the given unsafe block, if any.
+pub(crate) fn block_safety_comment(cx: &LateContext<'_>, block: &Block<'_>) -> Option<String> {
+    match block_has_safety_comment(cx, block) {
+        HasSafetyComment::Yes(pos) => safety_comment_text(cx, pos),
+        HasSafetyComment::No | HasSafetyComment::Maybe => None,
+    }
+}
+
+/// Returns the text of the safety comment attached to the given item, if any.
+pub(crate) fn item_safety_comment(cx: &LateContext<'_>, item: &hir::Item<'_>) -> Option<String> {
+    match item_has_safety_comment(cx, item) {
+        HasSafetyComment::Yes(pos) => safety_comment_text(cx, pos),
+        HasSafetyComment::No | HasSafetyComment::Maybe => None,
+    }
+}
+
+/// Gets the text of the comment starting at `pos`, without the comment delimiters.
+fn safety_comment_text(cx: &LateContext<'_>, pos: BytePos) -> Option<String> {
+    let file_pos = cx.sess().source_map().lookup_byte_offset(pos);
+    let src = file_pos.sf.src.as_deref()?.get(file_pos.pos.to_usize()..)?;
+    let text = if let Some(src) = src.strip_prefix("/*") {
+        src[..src.find("*/")?]
+            .lines()
+            .map(|line| line.trim().trim_start_matches('*').trim())
+            .join("\n")
+    } else {
+        src.lines()
+            .map_while(|line| line.trim_start().strip_prefix("//"))
+            .map(str::trim)
+            .join("\n")
+    };
+    Some(text.trim().to_owned())
 }
 
 enum HasSafetyComment {
@@ -541,7 +781,7 @@ fn get_body_search_span(cx: &LateContext<'_>) -> Option<Span> {
     Some(span)
 }
 
-fn span_in_body_has_safety_comment(cx: &LateContext<'_>, span: Span) -> bool {
+fn span_in_body_has_safety_comment(cx: &LateContext<'_>, span: Span) -> HasSafetyComment {
     let source_map = cx.sess().source_map();
     let ctxt = span.ctxt();
     if ctxt == SyntaxContext::root()
@@ -557,18 +797,24 @@ fn span_in_body_has_safety_comment(cx: &LateContext<'_>, span: Span) -> bool {
             //     fn foo() { some_stuff; unsafe { stuff }; other_stuff; }
             //              ^-------------^
             unsafe_line.sf.lines(|lines| {
-                body_line.line < unsafe_line.line && text_has_safety_comment(
-                    src,
-                    &lines[body_line.line + 1..=unsafe_line.line],
-                    unsafe_line.sf.start_pos.to_usize(),
-                ).is_some()
+                if body_line.line < unsafe_line.line
+                    && let Some(pos) = text_has_safety_comment(
+                        src,
+                        &lines[body_line.line + 1..=unsafe_line.line],
+                        unsafe_line.sf.start_pos.to_usize(),
+                    )
+                {
+                    HasSafetyComment::Yes(pos)
+                } else {
+                    HasSafetyComment::No
+                }
             })
         } else {
             // Problem getting source text. Pretend a comment was found.
-            true
+            HasSafetyComment::Maybe
         }
     } else {
-        false
+        HasSafetyComment::No
     }
 }
 
@@ -0,0 +1,100 @@
+//! Records what `clippy-driver` needs to know about a run to replay its diagnostics when only the
+//! levels of some lints changed, see `src/cache.rs`.
+//!
+//! This is enabled by the driver when it runs the compiler with `CLIPPY_CACHE_RECORD` set to the
+//! path of the record. The record is written once the lint store is dropped, i.e. at the end of the
+//! session, so that it includes the lints emitted by the late module passes, which run after the
+//! late passes. Nothing is written if the lints never ran, e.g. because of a compilation error.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+use crate::declared_lints::LINTS;
+use clippy_utils::diagnostics::{take_emitted_lints, track_emitted_lints};
+use rustc_hir::CRATE_HIR_ID;
+use rustc_lint::{unerased_lint_store, LateContext, LateLintPass, Lint, LintStore};
+use rustc_session::impl_lint_pass;
+use serde::{Deserialize, Serialize};
+
+#[derive(Default, Serialize, Deserialize)]
+pub struct CacheRecord {
+    /// The level of every Clippy lint at the crate root, e.g. `clippy::needless_return: warn`.
+    pub levels: BTreeMap<String, String>,
+    /// The Clippy lints emitted in the crate, including where they are allowed.
+    pub emitted: BTreeSet<String>,
+    /// The lints of every Clippy lint group, including the groups from `clippy.toml`.
+    pub groups: BTreeMap<String, Vec<String>>,
+}
+
+pub fn register(store: &mut LintStore, path: PathBuf) {
+    // The lint store is created on the thread running the lints
+    track_emitted_lints();
+    let recorder = Arc::new(Recorder {
+        path,
+        record: Mutex::default(),
+    });
+    store.register_late_pass(move |_| {
+        Box::new(RecordPass {
+            recorder: recorder.clone(),
+        })
+    });
+}
+
+struct Recorder {
+    path: PathBuf,
+    /// Set once the late lints ran.
+    record: Mutex<Option<CacheRecord>>,
+}
+
+impl Drop for Recorder {
+    fn drop(&mut self) {
+        let Some(mut record) = self.record.lock().unwrap().take() else {
+            return;
+        };
+        record
+            .emitted
+            .extend(take_emitted_lints().into_iter().map(Lint::name_lower));
+        let record = serde_json::to_string(&record).unwrap();
+        if let Err(error) = fs::write(&self.path, record) {
+            eprintln!(
+                "warning: could not write the cache record to `{}`: {error}",
+                self.path.display()
+            );
+        }
+    }
+}
+
+struct RecordPass {
+    recorder: Arc<Recorder>,
+}
+
+impl_lint_pass!(RecordPass => []);
+
+impl<'tcx> LateLintPass<'tcx> for RecordPass {
+    fn check_crate(&mut self, cx: &LateContext<'tcx>) {
+        let levels = LINTS
+            .iter()
+            .map(|info| {
+                let (level, _) = cx.tcx.lint_level_at_node(info.lint, CRATE_HIR_ID);
+                (info.lint.name_lower(), level.as_str().to_string())
+            })
+            .collect();
+        let groups = unerased_lint_store(cx.tcx)
+            .get_lint_groups()
+            .filter(|(name, ..)| name.starts_with("clippy::"))
+            .map(|(name, lints, _)| {
+                (
+                    name.to_string(),
+                    lints.iter().map(|lint| lint.lint.name_lower()).collect(),
+                )
+            })
+            .collect();
+        *self.recorder.record.lock().unwrap() = Some(CacheRecord {
+            levels,
+            emitted: BTreeSet::new(),
+            groups,
+        });
+    }
+}
@@ -31,6 +31,12 @@ const DEFAULT_DOC_VALID_IDENTS: &[&str] = &[
     "CamelCase",
 ];
 const DEFAULT_DISALLOWED_NAMES: &[&str] = &["foo", "baz", "quux"];
+const DEFAULT_UNBOUNDED_CHANNEL_CONSTRUCTORS: &[&str] = &[
+    "std::sync::mpsc::channel",
+    "crossbeam_channel::unbounded",
+    "crossbeam::channel::unbounded",
+    "tokio::sync::mpsc::unbounded_channel",
+];
 
 /// Holds information used by `MISSING_ENFORCED_IMPORT_RENAMES` lint.
 #[derive(Clone, Debug, Deserialize)]
@@ -63,6 +69,29 @@ impl DisallowedPath {
     }
 }
 
+/// An entry of the `disallowed-patterns` configuration.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DisallowedPattern {
+    /// The pattern, see `DISALLOWED_PATTERNS` for the syntax.
+    pub pattern: String,
+    pub message: Option<String>,
+    /// The suggested replacement, where `$name` is replaced with the expression matched by the
+    /// placeholder of that name.
+    pub replacement: Option<String>,
+    /// Only match the pattern inside of this kind of expression.
+    pub inside: Option<PatternContext>,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum PatternContext {
+    /// The body of a `loop`, `while` or `for` loop
+    Loop,
+    /// An `async` function or block
+    Async,
+}
+
 /// Conf with parse errors
 #[derive(Default)]
 pub struct TryConf {
@@ -249,11 +278,17 @@ define_Conf! {
     /// arithmetic-side-effects-allowed-unary = ["SomeType", "AnotherType"]
     /// ```
     (arithmetic_side_effects_allowed_unary: rustc_data_structures::fx::FxHashSet<String> = <_>::default()),
+    /// Lint: ARITHMETIC_SIDE_EFFECTS.
+    ///
+    /// Suppress checking of the operations which can't overflow on any target given the values
+    /// their operands can have, e.g. `x + 1` where `x` comes from `u16::from(_)`, or is known to be
+    /// less than 100 from a preceding `if`.
+    (arithmetic_side_effects_value_ranges: bool = false),
     /// Lint: ENUM_VARIANT_NAMES, LARGE_TYPES_PASSED_BY_VALUE, TRIVIALLY_COPY_PASS_BY_REF, UNNECESSARY_WRAPS, UNUSED_SELF, UPPER_CASE_ACRONYMS, WRONG_SELF_CONVENTION, BOX_COLLECTION, REDUNDANT_ALLOCATION, RC_BUFFER, VEC_BOX, OPTION_OPTION, LINKEDLIST, RC_MUTEX.
     ///
     /// Suppress lints whenever the suggested change would cause breakage for other crates.
     (avoid_breaking_exported_api: bool = true),
-    /// Lint: MANUAL_SPLIT_ONCE, MANUAL_STR_REPEAT, CLONED_INSTEAD_OF_COPIED, REDUNDANT_FIELD_NAMES, REDUNDANT_STATIC_LIFETIMES, FILTER_MAP_NEXT, CHECKED_CONVERSIONS, MANUAL_RANGE_CONTAINS, USE_SELF, MEM_REPLACE_WITH_DEFAULT, MANUAL_NON_EXHAUSTIVE, OPTION_AS_REF_DEREF, MAP_UNWRAP_OR, MATCH_LIKE_MATCHES_MACRO, MANUAL_STRIP, MISSING_CONST_FOR_FN, UNNESTED_OR_PATTERNS, FROM_OVER_INTO, PTR_AS_PTR, IF_THEN_SOME_ELSE_NONE, APPROX_CONSTANT, DEPRECATED_CFG_ATTR, INDEX_REFUTABLE_SLICE, MAP_CLONE, BORROW_AS_PTR, MANUAL_BITS, ERR_EXPECT, CAST_ABS_TO_UNSIGNED, UNINLINED_FORMAT_ARGS, MANUAL_CLAMP, MANUAL_LET_ELSE, UNCHECKED_DURATION_SUBTRACTION.
+    /// Lint: MANUAL_SPLIT_ONCE, MANUAL_STR_REPEAT, CLONED_INSTEAD_OF_COPIED, REDUNDANT_FIELD_NAMES, REDUNDANT_STATIC_LIFETIMES, FILTER_MAP_NEXT, CHECKED_CONVERSIONS, MANUAL_RANGE_CONTAINS, USE_SELF, MEM_REPLACE_WITH_DEFAULT, MANUAL_NON_EXHAUSTIVE, OPTION_AS_REF_DEREF, MAP_UNWRAP_OR, MATCH_LIKE_MATCHES_MACRO, MANUAL_STRIP, MISSING_CONST_FOR_FN, UNNESTED_OR_PATTERNS, FROM_OVER_INTO, PTR_AS_PTR, IF_THEN_SOME_ELSE_NONE, APPROX_CONSTANT, DEPRECATED_CFG_ATTR, INDEX_REFUTABLE_SLICE, MAP_CLONE, BORROW_AS_PTR, MANUAL_BITS, ERR_EXPECT, CAST_ABS_TO_UNSIGNED, UNINLINED_FORMAT_ARGS, MANUAL_CLAMP, MANUAL_LET_ELSE, UNCHECKED_DURATION_SUBTRACTION, INCOMPATIBLE_MSRV.
     ///
     /// The minimum rust version that the project supports
     (msrv: Option<String> = None),
@@ -454,6 +489,53 @@ define_Conf! {
     /// configuration will cause restriction lints to trigger even
     /// if no suggestion can be made.
     (suppress_restriction_lint_in_const: bool = false),
+    /// Lint: UNUSED_PUB_ITEMS.
+    ///
+    /// The crates of the workspace whose exported items are used outside of it, e.g. because they are
+    /// published. Their items are never linted.
+    (published_crates: Vec<String> = Vec::new()),
+    /// Lint: BLOCKING_IN_ASYNC.
+    ///
+    /// Additional blocking functions, written as fully qualified paths. A path to a module or a type
+    /// makes all of its functions blocking.
+    (blocking_paths: Vec<crate::utils::conf::DisallowedPath> = Vec::new()),
+    /// Lint: LARGE_FUTURES.
+    ///
+    /// The maximum size of a future, in bytes.
+    (future_size_threshold: u64 = 16 * 1024),
+    /// Not specific to a lint, and therefore not listed in the lint configuration chapter of the
+    /// book.
+    ///
+    /// The paths of dynamic libraries with additional lints for `clippy-driver` to load, relative
+    /// to the directory of `clippy.toml`. See the chapter on lint libraries in the book.
+    (lint_libraries: Vec<String> = Vec::new()),
+    /// Lint: DISALLOWED_PATTERNS.
+    ///
+    /// The list of disallowed expression patterns, each with an optional message, replacement and
+    /// context to match them in. See the lint's documentation for the pattern syntax.
+    (disallowed_patterns: Vec<crate::utils::conf::DisallowedPattern> = Vec::new()),
+    /// Not specific to a lint, and therefore not listed in the lint configuration chapter of the
+    /// book.
+    ///
+    /// User-defined lint groups, each a list of Clippy lints and lint groups. A group `name` is
+    /// enabled like Clippy's own groups, with `clippy::name` in lint attributes and on the command line.
+    (groups: std::collections::BTreeMap<String, Vec<String>> = std::collections::BTreeMap::new()),
+    /// Lint: INDEXING_SLICING.
+    ///
+    /// Whether a `debug_assert!` proving that an index is in bounds is enough not to lint the
+    /// indexing, like an `assert!` is. It isn't checked in release builds.
+    (allow_debug_assert_bounds_checks: bool = false),
+    /// Lint: STRUCT_PADDING.
+    ///
+    /// The number of bytes of padding that reordering the fields of a `repr(C)` struct must save
+    /// for the struct to be linted.
+    (struct_padding_threshold: u64 = 4),
+    /// Lint: UNBOUNDED_CHANNEL.
+    ///
+    /// The paths of the functions creating an unbounded channel. The value `".."` can be used as
+    /// part of the list to append the configured paths to the default ones.
+    (unbounded_channel_constructors: Vec<String> =
+        super::DEFAULT_UNBOUNDED_CHANNEL_CONSTRUCTORS.iter().map(ToString::to_string).collect()),
 }
 
 /// Search for the configuration file.
@@ -519,6 +601,10 @@ pub fn read(path: &Path) -> TryConf {
         Ok(mut conf) => {
             extend_vec_if_indicator_present(&mut conf.conf.doc_valid_idents, DEFAULT_DOC_VALID_IDENTS);
             extend_vec_if_indicator_present(&mut conf.conf.disallowed_names, DEFAULT_DISALLOWED_NAMES);
+            extend_vec_if_indicator_present(
+                &mut conf.conf.unbounded_channel_constructors,
+                DEFAULT_UNBOUNDED_CHANNEL_CONSTRUCTORS,
+            );
 
             conf
         },
@@ -0,0 +1,109 @@
+//! Skips the late lint passes which can't emit any lint that is enabled somewhere in the crate.
+//!
+//! Only the passes registered by `register_plugins` are skipped. The lints of each pass are taken
+//! from its `declare_lint_pass!` or `impl_lint_pass!` invocation, see `PASS_LINTS`. Passes without
+//! lints always run, and so do the passes registered by the driver, e.g. the ones writing reports,
+//! and the passes of lint libraries, whose names may clash with the names in `PASS_LINTS`. Early
+//! passes always run as well, the lint levels of the crate are only known once they are done.
+
+use std::mem;
+use std::sync::{Arc, OnceLock};
+
+use crate::attrs::UNUSED_CLIPPY_ALLOW;
+use crate::declared_lints::LINTS;
+use crate::pass_lints::PASS_LINTS;
+use rustc_data_structures::fx::{FxHashMap, FxHashSet};
+use rustc_hir::{MaybeOwner, OwnerId, CRATE_HIR_ID};
+use rustc_lint::{LateLintPass, Level, LintId, LintPass, LintStore};
+use rustc_middle::ty::TyCtxt;
+
+/// Replaces the late passes registered after the first `late_passes` and `late_module_passes`
+/// with passes doing nothing if they can't emit any enabled lint.
+pub fn skip_passes(store: &mut LintStore, (late_passes, late_module_passes): (usize, usize)) {
+    let lint_ids: FxHashMap<String, LintId> = LINTS
+        .iter()
+        .map(|info| (info.lint.name_lower(), LintId::of(info.lint)))
+        .collect();
+    let pass_lints: Arc<FxHashMap<&'static str, Vec<LintId>>> = Arc::new(
+        PASS_LINTS
+            .iter()
+            .map(|(pass, lints)| {
+                let lints = lints
+                    .iter()
+                    .filter_map(|lint| lint_ids.get(&format!("clippy::{lint}")).copied())
+                    .collect();
+                (*pass, lints)
+            })
+            .collect(),
+    );
+    let enabled_lints = Arc::new(OnceLock::new());
+
+    for (factories, first) in [
+        (&mut store.late_passes, late_passes),
+        (&mut store.late_module_passes, late_module_passes),
+    ] {
+        for factory in &mut factories[first..] {
+            let inner = mem::replace(factory, Box::new(|_| unreachable!()));
+            let pass_lints = pass_lints.clone();
+            let enabled_lints = enabled_lints.clone();
+            *factory = Box::new(move |tcx| -> Box<dyn LateLintPass<'_>> {
+                let pass = inner(tcx);
+                let enabled_lints = enabled_lints.get_or_init(|| enabled_lints_in_crate(tcx, &pass_lints));
+                match (pass_lints.get(pass.name()), enabled_lints) {
+                    (Some(lints), Some(enabled_lints))
+                        if !lints.is_empty() && !lints.iter().any(|lint| enabled_lints.contains(lint)) =>
+                    {
+                        Box::new(SkippedPass { name: pass.name() })
+                    },
+                    _ => pass,
+                }
+            });
+        }
+    }
+}
+
+/// Collects the lints of `pass_lints` which are not allowed at the crate root or which are set
+/// to another level than `allow` by an attribute anywhere in the crate. Returns `None` if every
+/// pass has to run, which is the case when `UNUSED_CLIPPY_ALLOW` needs to see all the lints which
+/// were emitted under an `allow` attribute.
+fn enabled_lints_in_crate(
+    tcx: TyCtxt<'_>,
+    pass_lints: &FxHashMap<&'static str, Vec<LintId>>,
+) -> Option<FxHashSet<LintId>> {
+    let mut enabled: FxHashSet<LintId> = pass_lints
+        .values()
+        .flatten()
+        .copied()
+        .chain([LintId::of(UNUSED_CLIPPY_ALLOW)])
+        .filter(|lint| tcx.lint_level_at_node(lint.lint, CRATE_HIR_ID).0 != Level::Allow)
+        .collect();
+
+    for (def_id, owner) in tcx.hir().krate().owners.iter_enumerated() {
+        if let MaybeOwner::Owner(_) = owner {
+            for (_, specs) in tcx.shallow_lint_levels_on(OwnerId { def_id }).specs.iter() {
+                enabled.extend(
+                    specs
+                        .iter()
+                        .filter(|(_, (level, _))| *level != Level::Allow)
+                        .map(|(lint, _)| *lint),
+                );
+            }
+        }
+    }
+
+    (!enabled.contains(&LintId::of(UNUSED_CLIPPY_ALLOW))).then_some(enabled)
+}
+
+/// Replaces a pass which can't emit any enabled lint.
+struct SkippedPass {
+    name: &'static str,
+}
+
+#[allow(rustc::lint_pass_impl_without_macro)]
+impl LintPass for SkippedPass {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+}
+
+impl LateLintPass<'_> for SkippedPass {}
@@ -1,5 +1,9 @@
 pub mod author;
+pub mod cache_record;
 pub mod conf;
+pub mod disabled_passes;
 pub mod dump_hir;
 #[cfg(feature = "internal")]
 pub mod internal_lints;
+pub mod profile;
+pub mod unsafe_report;
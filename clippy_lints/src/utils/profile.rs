@@ -0,0 +1,294 @@
+//! Measures the time spent in each registered lint pass and writes a per-pass and per-lint
+//! breakdown to a report.
+//!
+//! This is enabled by running `clippy-driver` with `CLIPPY_PROFILE` set to any non-empty value,
+//! e.g. `CLIPPY_PROFILE=1`. The report of each crate is
+//! written to `clippy-profile/<crate>-<kind>.json` next to the crate's build output, e.g.
+//! `target/debug/clippy-profile/foo-lib.json`, or printed to stderr if the compiler has no output
+//! directory.
+//!
+//! Every lint pass is wrapped in a pass timing each of its `check_*` methods. Lints don't have
+//! timings of their own, the time of a lint is the time of the passes declaring it, see
+//! `PASS_LINTS`, or emitting it, including where it's allowed. Lints sharing a pass are marked as
+//! `shared` in the report. The lints of lint libraries are neither declared in `PASS_LINTS` nor
+//! tracked when emitted, they use their own copy of `clippy_utils`.
+
+use std::fs;
+use std::mem;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::pass_lints::PASS_LINTS;
+use clippy_utils::diagnostics::{take_emitted_lints, track_emitted_lints};
+use rustc_ast as ast;
+use rustc_data_structures::fx::{FxHashMap, FxHashSet};
+use rustc_hir as hir;
+use rustc_lint::{
+    early_lint_methods, late_lint_methods, EarlyContext, EarlyLintPass, LateContext, LateLintPass, LintId, LintPass,
+    LintStore,
+};
+use rustc_span::symbol::Ident;
+use rustc_span::Span;
+use serde::Serialize;
+
+/// Wraps every lint pass registered in `store` in a pass measuring its execution time. The report
+/// is written to `report`, or printed to stderr if it's `None`, once the last of them is dropped,
+/// i.e. at the end of the session.
+pub fn wrap_passes(store: &mut LintStore, report: Option<PathBuf>) {
+    let profile = Arc::new(Profile {
+        report,
+        passes: Mutex::default(),
+    });
+
+    for (factories, kind) in [
+        (&mut store.pre_expansion_passes, "pre-expansion"),
+        (&mut store.early_passes, "early"),
+    ] {
+        for factory in factories {
+            let inner = mem::replace(factory, Box::new(|| unreachable!()));
+            let profile = profile.clone();
+            *factory = Box::new(move || -> Box<dyn EarlyLintPass> {
+                Box::new(ProfiledEarlyPass {
+                    inner: inner(),
+                    timer: PassTimer::new(kind, profile.clone()),
+                })
+            });
+        }
+    }
+
+    for (factories, kind) in [
+        (&mut store.late_passes, "late"),
+        (&mut store.late_module_passes, "late module"),
+    ] {
+        for factory in factories {
+            let inner = mem::replace(factory, Box::new(|_| unreachable!()));
+            let profile = profile.clone();
+            *factory = Box::new(move |tcx| -> Box<dyn LateLintPass<'_>> {
+                Box::new(ProfiledLatePass {
+                    inner: inner(tcx),
+                    timer: PassTimer::new(kind, profile.clone()),
+                })
+            });
+        }
+    }
+}
+
+/// The path the report of the crate `crate_name` is written to, given the output directory of the
+/// compiler.
+pub fn report_path(out_dir: &Path, crate_name: &str, crate_kind: &str) -> PathBuf {
+    out_dir
+        .parent()
+        .unwrap_or(out_dir)
+        .join("clippy-profile")
+        .join(format!("{crate_name}-{crate_kind}.json"))
+}
+
+#[derive(Serialize)]
+struct PassEntry {
+    name: &'static str,
+    kind: &'static str,
+    time_ms: f64,
+    lints: Vec<String>,
+}
+
+#[derive(Serialize)]
+struct LintEntry {
+    name: String,
+    /// The total time of `passes`.
+    time_ms: f64,
+    passes: Vec<&'static str>,
+    /// Whether any of `passes` also checks other lints. Their time is then counted in full for
+    /// each of these lints, so the times of the lints can add up to more than `total_ms`.
+    shared: bool,
+}
+
+#[derive(Serialize)]
+struct Report {
+    total_ms: f64,
+    passes: Vec<PassEntry>,
+    lints: Vec<LintEntry>,
+}
+
+struct Profile {
+    report: Option<PathBuf>,
+    /// The passes, by kind and name.
+    passes: Mutex<FxHashMap<(&'static str, &'static str), PassProfile>>,
+}
+
+/// The accumulated time of all instances of a pass and the lints they emitted, in addition to the
+/// ones it declares.
+#[derive(Default)]
+struct PassProfile {
+    time: Duration,
+    lints: FxHashSet<LintId>,
+}
+
+impl Profile {
+    fn render(&self) -> String {
+        let passes = mem::take(&mut *self.passes.lock().unwrap());
+        let declared_lints: FxHashMap<&str, &[&str]> = PASS_LINTS.iter().copied().collect();
+
+        let mut lints: FxHashMap<String, LintEntry> = FxHashMap::default();
+        let mut pass_entries = Vec::with_capacity(passes.len());
+        for (
+            (kind, name),
+            PassProfile {
+                time,
+                lints: pass_lints,
+            },
+        ) in passes
+        {
+            let time_ms = time.as_secs_f64() * 1000.0;
+            let mut pass_lints: Vec<_> = pass_lints
+                .into_iter()
+                .map(|lint| lint.to_string())
+                .chain(
+                    declared_lints
+                        .get(name)
+                        .into_iter()
+                        .flat_map(|lints| lints.iter().map(|lint| format!("clippy::{lint}"))),
+                )
+                .collect();
+            pass_lints.sort_unstable();
+            pass_lints.dedup();
+            for lint in &pass_lints {
+                let entry = lints.entry(lint.clone()).or_insert_with(|| LintEntry {
+                    name: lint.clone(),
+                    time_ms: 0.0,
+                    passes: Vec::new(),
+                    shared: false,
+                });
+                entry.time_ms += time_ms;
+                entry.passes.push(name);
+                entry.shared |= pass_lints.len() > 1;
+            }
+            pass_entries.push(PassEntry {
+                name,
+                kind,
+                time_ms,
+                lints: pass_lints,
+            });
+        }
+
+        pass_entries.sort_by(|a, b| b.time_ms.total_cmp(&a.time_ms).then(a.name.cmp(b.name)));
+        let mut lints: Vec<_> = lints.into_values().collect();
+        lints.sort_by(|a, b| b.time_ms.total_cmp(&a.time_ms).then(a.name.cmp(&b.name)));
+        for lint in &mut lints {
+            lint.passes.sort_unstable();
+        }
+
+        let report = Report {
+            total_ms: pass_entries.iter().map(|pass| pass.time_ms).sum(),
+            passes: pass_entries,
+            lints,
+        };
+        serde_json::to_string_pretty(&report).unwrap() + "\n"
+    }
+}
+
+impl Drop for Profile {
+    fn drop(&mut self) {
+        let report = self.render();
+        let Some(path) = &self.report else {
+            eprint!("{report}");
+            return;
+        };
+        if let Err(error) = path
+            .parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .and_then(|()| fs::write(path, report))
+        {
+            eprintln!("warning: could not write the profile to `{}`: {error}", path.display());
+        }
+    }
+}
+
+/// The time spent in one instance of a pass and the lints it emitted, added to the [`Profile`]
+/// when it's dropped.
+struct PassTimer {
+    kind: &'static str,
+    time: Duration,
+    lints: FxHashSet<LintId>,
+    profile: Arc<Profile>,
+}
+
+impl PassTimer {
+    fn new(kind: &'static str, profile: Arc<Profile>) -> Self {
+        // Passes are created on the thread running them
+        track_emitted_lints();
+        Self {
+            kind,
+            time: Duration::ZERO,
+            lints: FxHashSet::default(),
+            profile,
+        }
+    }
+
+    fn time(&mut self, check: impl FnOnce()) {
+        let start = Instant::now();
+        check();
+        self.time += start.elapsed();
+        self.lints.extend(take_emitted_lints().into_iter().map(LintId::of));
+    }
+
+    fn record(&mut self, pass: &'static str) {
+        let mut passes = self.profile.passes.lock().unwrap();
+        let entry = passes.entry((self.kind, pass)).or_default();
+        entry.time += mem::take(&mut self.time);
+        entry.lints.extend(self.lints.drain());
+    }
+}
+
+macro_rules! timed_methods {
+    ([$cx:ty], [$($(#[$attr:meta])* fn $name:ident($($param:ident: $arg:ty),*);)*]) => {
+        $(fn $name(&mut self, cx: $cx, $($param: $arg),*) {
+            let inner = &mut self.inner;
+            self.timer.time(|| inner.$name(cx, $($param),*));
+        })*
+    };
+}
+
+struct ProfiledEarlyPass {
+    inner: Box<dyn EarlyLintPass>,
+    timer: PassTimer,
+}
+
+#[allow(rustc::lint_pass_impl_without_macro)]
+impl LintPass for ProfiledEarlyPass {
+    fn name(&self) -> &'static str {
+        self.inner.name()
+    }
+}
+
+impl EarlyLintPass for ProfiledEarlyPass {
+    early_lint_methods!(timed_methods, [&EarlyContext<'_>]);
+}
+
+impl Drop for ProfiledEarlyPass {
+    fn drop(&mut self) {
+        self.timer.record(self.inner.name());
+    }
+}
+
+struct ProfiledLatePass<'tcx> {
+    inner: Box<dyn LateLintPass<'tcx> + 'tcx>,
+    timer: PassTimer,
+}
+
+#[allow(rustc::lint_pass_impl_without_macro)]
+impl LintPass for ProfiledLatePass<'_> {
+    fn name(&self) -> &'static str {
+        self.inner.name()
+    }
+}
+
+impl<'tcx> LateLintPass<'tcx> for ProfiledLatePass<'tcx> {
+    late_lint_methods!(timed_methods, [&LateContext<'tcx>]);
+}
+
+impl Drop for ProfiledLatePass<'_> {
+    fn drop(&mut self) {
+        self.timer.record(self.inner.name());
+    }
+}
@@ -0,0 +1,248 @@
+//! Collects every `unsafe` block, `unsafe impl` and `unsafe fn` of a crate, together with the
+//! unsafe operations they perform and their safety documentation, and writes them to a report.
+//!
+//! This is enabled with `cargo clippy --unsafe-report[=json|markdown]`. The report of each crate
+//! is written to `clippy-unsafe-report/<crate>-<kind>.<ext>` next to the crate's build output,
+//! e.g. `target/debug/clippy-unsafe-report/foo-lib.json`. Entries are keyed by their enclosing
+//! item and their index within it rather than by their location, which changes on unrelated edits.
+
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+
+use crate::doc::safety_section;
+use crate::multiple_unsafe_ops_per_block::{collect_unsafe_exprs, UnsafeOp};
+use crate::undocumented_unsafe_blocks::{block_safety_comment, item_safety_comment};
+use rustc_data_structures::fx::FxHashMap;
+use rustc_hir::intravisit::FnKind;
+use rustc_hir::{Block, BlockCheckMode, Body, FnDecl, HirId, Item, ItemKind, UnsafeSource, Unsafety};
+use rustc_lint::{LateContext, LateLintPass, LintContext};
+use rustc_middle::lint::in_external_macro;
+use rustc_session::config::CrateType;
+use rustc_session::impl_lint_pass;
+use rustc_span::def_id::{LocalDefId, LOCAL_CRATE};
+use rustc_span::Span;
+use serde::Serialize;
+
+impl_lint_pass!(UnsafeReport => []);
+
+#[derive(Clone, Copy)]
+pub enum ReportFormat {
+    Json,
+    Markdown,
+}
+
+impl ReportFormat {
+    pub fn parse(format: &str) -> Option<Self> {
+        match format {
+            "json" => Some(Self::Json),
+            "md" | "markdown" => Some(Self::Markdown),
+            _ => None,
+        }
+    }
+
+    fn extension(self) -> &'static str {
+        match self {
+            Self::Json => "json",
+            Self::Markdown => "md",
+        }
+    }
+}
+
+/// An entry of the report, identified by `enclosing_item` and `index`.
+#[derive(Serialize)]
+struct UnsafeEntry {
+    enclosing_item: String,
+    index: usize,
+    kind: &'static str,
+    location: String,
+    operations: Vec<UnsafeOperation>,
+    safety_comment: Option<String>,
+}
+
+#[derive(Serialize)]
+struct UnsafeOperation {
+    location: String,
+    kind: &'static str,
+    path: Option<String>,
+}
+
+pub struct UnsafeReport {
+    format: ReportFormat,
+    entries: Vec<UnsafeEntry>,
+    /// The number of entries of each enclosing item so far.
+    item_counts: FxHashMap<String, usize>,
+}
+
+impl UnsafeReport {
+    pub fn new(format: ReportFormat) -> Self {
+        Self {
+            format,
+            entries: Vec::new(),
+            item_counts: FxHashMap::default(),
+        }
+    }
+
+    fn push_entry(
+        &mut self,
+        cx: &LateContext<'_>,
+        kind: &'static str,
+        span: Span,
+        owner: LocalDefId,
+        unsafe_ops: Vec<(UnsafeOp, Span)>,
+        safety_comment: Option<String>,
+    ) {
+        let enclosing_item = cx.tcx.def_path_str(owner.to_def_id());
+        let count = self.item_counts.entry(enclosing_item.clone()).or_default();
+        let index = *count;
+        *count += 1;
+        self.entries.push(UnsafeEntry {
+            enclosing_item,
+            index,
+            kind,
+            location: location(cx, span),
+            operations: unsafe_ops
+                .into_iter()
+                .map(|(op, span)| UnsafeOperation {
+                    location: location(cx, span),
+                    kind: operation_kind(op),
+                    path: op.def_id().map(|def_id| cx.tcx.def_path_str(def_id)),
+                })
+                .collect(),
+            safety_comment,
+        });
+    }
+
+    fn render(&self, crate_name: &str) -> String {
+        match self.format {
+            ReportFormat::Json => serde_json::to_string_pretty(&self.entries).unwrap() + "\n",
+            ReportFormat::Markdown => {
+                let mut out = format!("# Unsafe code in `{crate_name}`\n");
+                for entry in &self.entries {
+                    let _ = write!(
+                        out,
+                        "\n## {} #{} in `{}`\n\nLocation: `{}`\n",
+                        entry.kind, entry.index, entry.enclosing_item, entry.location
+                    );
+                    if !entry.operations.is_empty() {
+                        out.push_str("\nOperations:\n");
+                        for op in &entry.operations {
+                            let _ = match &op.path {
+                                Some(path) => writeln!(out, "- {} `{path}` at `{}`", op.kind, op.location),
+                                None => writeln!(out, "- {} at `{}`", op.kind, op.location),
+                            };
+                        }
+                    }
+                    match &entry.safety_comment {
+                        Some(comment) => {
+                            out.push_str("\nSafety:\n\n");
+                            for line in comment.lines() {
+                                let _ = writeln!(out, "> {line}");
+                            }
+                        },
+                        None => out.push_str("\nSafety: *undocumented*\n"),
+                    }
+                }
+                out
+            },
+        }
+    }
+}
+
+impl<'tcx> LateLintPass<'tcx> for UnsafeReport {
+    fn check_block(&mut self, cx: &LateContext<'tcx>, block: &'tcx Block<'tcx>) {
+        if block.rules == BlockCheckMode::UnsafeBlock(UnsafeSource::UserProvided)
+            && !in_external_macro(cx.sess(), block.span)
+        {
+            let mut unsafe_ops = vec![];
+            collect_unsafe_exprs(cx, block, &mut unsafe_ops);
+            let owner = cx.tcx.hir().get_parent_item(block.hir_id).def_id;
+            let comment = block_safety_comment(cx, block);
+            self.push_entry(cx, "unsafe block", block.span, owner, unsafe_ops, comment);
+        }
+    }
+
+    fn check_item(&mut self, cx: &LateContext<'tcx>, item: &'tcx Item<'tcx>) {
+        if let ItemKind::Impl(impl_) = item.kind
+            && impl_.unsafety == Unsafety::Unsafe
+            && !in_external_macro(cx.sess(), item.span)
+        {
+            let comment = item_safety_comment(cx, item);
+            self.push_entry(cx, "unsafe impl", item.span, item.owner_id.def_id, vec![], comment);
+        }
+    }
+
+    fn check_fn(
+        &mut self,
+        cx: &LateContext<'tcx>,
+        kind: FnKind<'tcx>,
+        _: &'tcx FnDecl<'_>,
+        body: &'tcx Body<'_>,
+        span: Span,
+        hir_id: HirId,
+    ) {
+        if kind
+            .header()
+            .map_or(false, |header| header.unsafety == Unsafety::Unsafe)
+            && !in_external_macro(cx.sess(), span)
+        {
+            let mut unsafe_ops = vec![];
+            collect_unsafe_exprs(cx, body.value, &mut unsafe_ops);
+            let comment = safety_section(cx.tcx.hir().attrs(hir_id));
+            let def_id = cx.tcx.hir().local_def_id(hir_id);
+            self.push_entry(cx, "unsafe fn", span, def_id, unsafe_ops, comment);
+        }
+    }
+
+    fn check_crate_post(&mut self, cx: &LateContext<'tcx>) {
+        let sess = cx.sess();
+        let crate_name = cx.tcx.crate_name(LOCAL_CRATE);
+        let crate_kind = if sess.opts.test {
+            "test"
+        } else if sess.crate_types().contains(&CrateType::Executable) {
+            "bin"
+        } else {
+            "lib"
+        };
+        let out_dir = &cx.tcx.output_filenames(()).out_directory;
+        let report_dir = out_dir.parent().unwrap_or(out_dir).join("clippy-unsafe-report");
+        let file_name = format!("{crate_name}-{crate_kind}.{}", self.format.extension());
+        let report = self.render(crate_name.as_str());
+        if let Err(error) = write_report(&report_dir, &file_name, &report) {
+            sess.warn(format!(
+                "could not write the unsafe report to `{}`: {error}",
+                report_dir.join(file_name).display()
+            ));
+        }
+    }
+}
+
+fn write_report(dir: &Path, file_name: &str, report: &str) -> std::io::Result<()> {
+    fs::create_dir_all(dir)?;
+    fs::write(dir.join(file_name), report)
+}
+
+/// Formats the start of the span as `file:line:column`, using the path the file was passed to
+/// the compiler with so the report doesn't depend on where the workspace is checked out.
+fn location(cx: &LateContext<'_>, span: Span) -> String {
+    let source_map = cx.sess().source_map();
+    let loc = source_map.lookup_char_pos(span.lo());
+    format!(
+        "{}:{}:{}",
+        source_map.filename_for_diagnostics(&loc.file.name),
+        loc.line,
+        loc.col.0 + 1
+    )
+}
+
+fn operation_kind(op: UnsafeOp) -> &'static str {
+    match op {
+        UnsafeOp::InlineAsm => "inline assembly",
+        UnsafeOp::UnionFieldAccess => "union field access",
+        UnsafeOp::MutStaticAccess(_) => "access of mutable static",
+        UnsafeOp::RawPtrDeref => "raw pointer dereference",
+        UnsafeOp::UnsafeFnCall(_) => "call of unsafe function",
+        UnsafeOp::UnsafeMethodCall(_) => "call of unsafe method",
+        UnsafeOp::MutStaticModification(_) => "modification of mutable static",
+    }
+}
@@ -23,6 +23,7 @@ pub const BUILTIN_ATTRIBUTES: &[(&str, DeprecationStatus)] = &[
     ("dump",                  DeprecationStatus::None),
     ("msrv",                  DeprecationStatus::None),
     ("has_significant_drop",  DeprecationStatus::None),
+    ("skip_in_manual_impls",  DeprecationStatus::None),
 ];
 
 pub struct LimitStack {
@@ -8,14 +8,104 @@
 //! Thank you!
 //! ~The `INTERNAL_METADATA_COLLECTOR` lint
 
+use rustc_data_structures::fx::FxHashSet;
 use rustc_errors::{Applicability, Diagnostic, MultiSpan};
 use rustc_hir::HirId;
-use rustc_lint::{LateContext, Lint, LintContext};
+use rustc_lint::{EarlyContext, LateContext, Level, Lint, LintContext};
+use rustc_middle::lint::LintLevelSource;
 use rustc_span::source_map::Span;
+use std::cell::RefCell;
 use std::env;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+thread_local! {
+    /// Spans of the lint names in `allow` attributes that allowed a lint which was emitted, see
+    /// [`record_lint_level`].
+    static USED_ALLOWS: RefCell<FxHashSet<Span>> = RefCell::default();
+
+    /// The lints emitted by the functions in this module, if enabled with [`track_emitted_lints`].
+    static EMITTED_LINTS: RefCell<Option<Vec<&'static Lint>>> = RefCell::default();
+}
+
+/// A [`LintContext`] which knows where the level of a lint at the current node comes from.
+pub trait LintLevelContext: LintContext {
+    fn lint_level_source(&self, lint: &'static Lint) -> (Level, LintLevelSource);
+}
+
+impl LintLevelContext for LateContext<'_> {
+    fn lint_level_source(&self, lint: &'static Lint) -> (Level, LintLevelSource) {
+        self.tcx.lint_level_at_node(lint, self.last_node_with_lint_attrs)
+    }
+}
+
+impl LintLevelContext for EarlyContext<'_> {
+    fn lint_level_source(&self, lint: &'static Lint) -> (Level, LintLevelSource) {
+        self.builder.lint_level(lint)
+    }
+}
+
+/// Records that the lint level attribute in `source` was needed, if it allowed the lint.
+///
+/// This is done for every lint emitted by the functions in this module, i.e. when an `allow`
+/// attribute suppressed a diagnostic, and for every level checked by
+/// [`is_lint_allowed`](crate::is_lint_allowed), as lints may skip looking for the code they lint
+/// where they are allowed. `UNUSED_CLIPPY_ALLOW` uses it to find the `allow` attributes which
+/// never allowed anything.
+pub(crate) fn record_lint_level(level: Level, source: &LintLevelSource) {
+    if let (Level::Allow, LintLevelSource::Node { span, .. }) = (level, source) {
+        USED_ALLOWS.with(|used| used.borrow_mut().insert(*span));
+    }
+}
+
+/// Checks if the lint name at `span` in an `allow` attribute was recorded by
+/// [`record_lint_level`].
+pub fn is_allow_used(span: Span) -> bool {
+    USED_ALLOWS.with(|used| used.borrow().contains(&span))
+}
+
+/// Starts recording the lints emitted on this thread by the functions in this module, see
+/// [`take_emitted_lints`]. Used to attribute lints to the passes emitting them when profiling.
+pub fn track_emitted_lints() {
+    EMITTED_LINTS.with(|emitted| emitted.borrow_mut().get_or_insert_with(Vec::new).clear());
+}
+
+/// Returns the lints emitted since the last call, including the ones which are allowed. Always
+/// empty unless [`track_emitted_lints`] was called.
+pub fn take_emitted_lints() -> Vec<&'static Lint> {
+    EMITTED_LINTS.with(|emitted| emitted.borrow_mut().as_mut().map(std::mem::take).unwrap_or_default())
+}
+
+fn record_emitted_lint(lint: &'static Lint) {
+    EMITTED_LINTS.with(|emitted| {
+        if let Some(emitted) = &mut *emitted.borrow_mut() {
+            emitted.push(lint);
+        }
+    });
+}
+
+fn record_lint_at_node(cx: &impl LintLevelContext, lint: &'static Lint) {
+    let (level, source) = cx.lint_level_source(lint);
+    record_lint_level(level, &source);
+    record_emitted_lint(lint);
+}
+
+fn record_lint_at_hir(cx: &LateContext<'_>, lint: &'static Lint, hir_id: HirId) {
+    let (level, source) = cx.tcx.lint_level_at_node(lint, hir_id);
+    record_lint_level(level, &source);
+    record_emitted_lint(lint);
+}
+
+/// Set in lint libraries, whose lints are not documented on Clippy's website.
+static NO_DOCS_LINKS: AtomicBool = AtomicBool::new(false);
+
+/// Stops adding links to Clippy's lint documentation to the emitted lints, called by
+/// `clippy_lint_library::declare_lint_library!`.
+pub fn disable_docs_links() {
+    NO_DOCS_LINKS.store(true, Ordering::Relaxed);
+}
 
 fn docs_link(diag: &mut Diagnostic, lint: &'static Lint) {
-    if env::var("CLIPPY_DISABLE_DOCS_LINKS").is_err() {
+    if env::var("CLIPPY_DISABLE_DOCS_LINKS").is_err() && !NO_DOCS_LINKS.load(Ordering::Relaxed) {
         if let Some(lint) = lint.name_lower().strip_prefix("clippy::") {
             diag.help(format!(
                 "for further information visit https://rust-lang.github.io/rust-clippy/{}/index.html#{lint}",
@@ -45,7 +135,8 @@ fn docs_link(diag: &mut Diagnostic, lint: &'static Lint) {
 /// 17 |     std::mem::forget(seven);
 ///    |     ^^^^^^^^^^^^^^^^^^^^^^^
 /// ```
-pub fn span_lint<T: LintContext>(cx: &T, lint: &'static Lint, sp: impl Into<MultiSpan>, msg: &str) {
+pub fn span_lint<T: LintLevelContext>(cx: &T, lint: &'static Lint, sp: impl Into<MultiSpan>, msg: &str) {
+    record_lint_at_node(cx, lint);
     cx.struct_span_lint(lint, sp, msg, |diag| {
         docs_link(diag, lint);
         diag
@@ -72,7 +163,7 @@ pub fn span_lint<T: LintContext>(cx: &T, lint: &'static Lint, sp: impl Into<Mult
 ///    |
 ///    = help: consider using `f64::NAN` if you would like a constant representing NaN
 /// ```
-pub fn span_lint_and_help<T: LintContext>(
+pub fn span_lint_and_help<T: LintLevelContext>(
     cx: &T,
     lint: &'static Lint,
     span: impl Into<MultiSpan>,
@@ -80,6 +171,7 @@ pub fn span_lint_and_help<T: LintContext>(
     help_span: Option<Span>,
     help: &str,
 ) {
+    record_lint_at_node(cx, lint);
     cx.struct_span_lint(lint, span, msg, |diag| {
         if let Some(help_span) = help_span {
             diag.span_help(help_span, help);
@@ -114,7 +206,7 @@ pub fn span_lint_and_help<T: LintContext>(
 /// 10 |     forget(&SomeStruct);
 ///    |            ^^^^^^^^^^^
 /// ```
-pub fn span_lint_and_note<T: LintContext>(
+pub fn span_lint_and_note<T: LintLevelContext>(
     cx: &T,
     lint: &'static Lint,
     span: impl Into<MultiSpan>,
@@ -122,6 +214,7 @@ pub fn span_lint_and_note<T: LintContext>(
     note_span: Option<Span>,
     note: &str,
 ) {
+    record_lint_at_node(cx, lint);
     cx.struct_span_lint(lint, span, msg, |diag| {
         if let Some(note_span) = note_span {
             diag.span_note(note_span, note);
@@ -139,10 +232,11 @@ pub fn span_lint_and_note<T: LintContext>(
 /// If you change the signature, remember to update the internal lint `CollapsibleCalls`
 pub fn span_lint_and_then<C, S, F>(cx: &C, lint: &'static Lint, sp: S, msg: &str, f: F)
 where
-    C: LintContext,
+    C: LintLevelContext,
     S: Into<MultiSpan>,
     F: FnOnce(&mut Diagnostic),
 {
+    record_lint_at_node(cx, lint);
     cx.struct_span_lint(lint, sp, msg, |diag| {
         f(diag);
         docs_link(diag, lint);
@@ -151,6 +245,7 @@ where
 }
 
 pub fn span_lint_hir(cx: &LateContext<'_>, lint: &'static Lint, hir_id: HirId, sp: Span, msg: &str) {
+    record_lint_at_hir(cx, lint, hir_id);
     cx.tcx.struct_span_lint_hir(lint, hir_id, sp, msg, |diag| {
         docs_link(diag, lint);
         diag
@@ -165,6 +260,7 @@ pub fn span_lint_hir_and_then(
     msg: &str,
     f: impl FnOnce(&mut Diagnostic),
 ) {
+    record_lint_at_hir(cx, lint, hir_id);
     cx.tcx.struct_span_lint_hir(lint, hir_id, sp, msg, |diag| {
         f(diag);
         docs_link(diag, lint);
@@ -192,7 +288,7 @@ pub fn span_lint_hir_and_then(
 ///     = note: `-D fold-any` implied by `-D warnings`
 /// ```
 #[cfg_attr(feature = "internal", allow(clippy::collapsible_span_lint_calls))]
-pub fn span_lint_and_sugg<T: LintContext>(
+pub fn span_lint_and_sugg<T: LintLevelContext>(
     cx: &T,
     lint: &'static Lint,
     sp: Span,
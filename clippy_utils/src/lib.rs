@@ -64,6 +64,7 @@ pub mod str_utils;
 pub mod sugg;
 pub mod ty;
 pub mod usage;
+pub mod value_range;
 pub mod visitors;
 
 pub use self::attrs::*;
@@ -105,7 +106,8 @@ use rustc_middle::ty::fast_reject::SimplifiedType::{
     PtrSimplifiedType, SliceSimplifiedType, StrSimplifiedType, UintSimplifiedType,
 };
 use rustc_middle::ty::{
-    layout::IntegerExt, BorrowKind, ClosureKind, DefIdTree, Ty, TyCtxt, TypeAndMut, TypeVisitable, UpvarCapture,
+    layout::IntegerExt, BorrowKind, ClosureKind, DefIdTree, Ty, TyCtxt, TypeAndMut, TypeVisitable, TypeckResults,
+    UpvarCapture,
 };
 use rustc_middle::ty::{FloatTy, IntTy, UintTy};
 use rustc_span::hygiene::{ExpnKind, MacroKind};
@@ -1232,6 +1234,54 @@ pub fn method_chain_args<'a>(expr: &'a Expr<'_>, methods: &[&str]) -> Option<Vec
     Some(matched)
 }
 
+/// What makes an expression panic, see [`panic_source`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PanicSource {
+    /// A call of `panic!`, `todo!`, `unimplemented!`, `unreachable!` or of one of the `assert!`
+    /// macros, with the name of the macro.
+    Macro(Symbol),
+    /// A call of `unwrap` or `unwrap_err` on an `Option` or a `Result`.
+    Unwrap,
+    /// A call of `expect` or `expect_err` on an `Option` or a `Result`.
+    Expect,
+}
+
+/// Checks if `expr` panics explicitly, with a panicking macro or by unwrapping an `Option` or a
+/// `Result`, and returns the span to point at. Macros are only found at the first node of their
+/// expansion.
+pub fn panic_source<'tcx>(
+    cx: &LateContext<'tcx>,
+    typeck_results: &TypeckResults<'tcx>,
+    expr: &Expr<'_>,
+) -> Option<(Span, PanicSource)> {
+    if let Some(macro_call) = macros::root_macro_call_first_node(cx, expr) {
+        let name = cx.tcx.item_name(macro_call.def_id);
+        if macros::is_panic(cx, macro_call.def_id)
+            || matches!(
+                name.as_str(),
+                "assert" | "assert_eq" | "assert_ne" | "todo" | "unimplemented" | "unreachable"
+            )
+        {
+            return Some((macro_call.span, PanicSource::Macro(name)));
+        }
+    }
+
+    if let ExprKind::MethodCall(method, receiver, _, _) = expr.kind {
+        let source = match method.ident.as_str() {
+            "unwrap" | "unwrap_err" => PanicSource::Unwrap,
+            "expect" | "expect_err" => PanicSource::Expect,
+            _ => return None,
+        };
+        let receiver_ty = typeck_results.expr_ty(receiver).peel_refs();
+        if ty::is_type_diagnostic_item(cx, receiver_ty, sym::Option)
+            || ty::is_type_diagnostic_item(cx, receiver_ty, sym::Result)
+        {
+            return Some((expr.span, source));
+        }
+    }
+    None
+}
+
 /// Returns `true` if the provided `def_id` is an entrypoint to a program.
 pub fn is_entrypoint_fn(cx: &LateContext<'_>, def_id: DefId) -> bool {
     cx.tcx
@@ -1735,7 +1785,9 @@ pub fn is_try<'tcx>(cx: &LateContext<'_>, expr: &'tcx Expr<'tcx>) -> Option<&'tc
 /// make sure to use `span_lint_hir` functions to emit the lint. This ensures that
 /// expectations at the checked nodes will be fulfilled.
 pub fn is_lint_allowed(cx: &LateContext<'_>, lint: &'static Lint, id: HirId) -> bool {
-    cx.tcx.lint_level_at_node(lint, id).0 == Level::Allow
+    let (level, source) = cx.tcx.lint_level_at_node(lint, id);
+    diagnostics::record_lint_level(level, &source);
+    level == Level::Allow
 }
 
 pub fn strip_pat_refs<'hir>(mut pat: &'hir Pat<'hir>) -> &'hir Pat<'hir> {
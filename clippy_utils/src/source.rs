@@ -4,12 +4,14 @@
 
 use rustc_errors::Applicability;
 use rustc_hir::{Expr, ExprKind};
+use rustc_lexer::{tokenize, TokenKind};
 use rustc_lint::{LateContext, LintContext};
 use rustc_session::Session;
 use rustc_span::hygiene;
 use rustc_span::source_map::{original_sp, SourceMap};
 use rustc_span::{BytePos, Pos, Span, SpanData, SyntaxContext, DUMMY_SP};
 use std::borrow::Cow;
+use std::ops::Range;
 
 /// Like `snippet_block`, but add braces if the expr is not an `ExprKind::Block`.
 /// Also takes an `Option<String>` which can be put inside the braces.
@@ -442,9 +444,113 @@ pub fn expand_past_previous_comma(cx: &LateContext<'_>, span: Span) -> Span {
     extended.with_lo(extended.lo() - BytePos(1))
 }
 
+/// Keeps the comments of the code replaced by a suggestion.
+///
+/// Collects the comments inside of `replaced` that aren't inside one of the `kept` spans, i.e. the
+/// comments that would be lost by replacing `replaced` with `sugg`, and puts them in front of
+/// `sugg`. Line comments are followed by a new line with the indentation of `replaced`.
+///
+/// The applicability is lowered to `MaybeIncorrect` if the comments can't be collected, or if one
+/// of them is a doc comment, as moving it would change what it documents.
+///
+/// ```rust,ignore
+/// // `replaced`: `return // the answer\n    42`, `kept`: `42`
+/// reattach_comments(cx, ret_span, &[inner_span], "42".into(), &mut app);
+/// // => "// the answer\n    42"
+/// ```
+pub fn reattach_comments<T: LintContext>(
+    cx: &T,
+    replaced: Span,
+    kept: &[Span],
+    sugg: String,
+    applicability: &mut Applicability,
+) -> String {
+    if replaced.from_expansion() {
+        return sugg;
+    }
+    let Some(snippet) = snippet_opt(cx, replaced) else {
+        if *applicability != Applicability::Unspecified {
+            *applicability = Applicability::MaybeIncorrect;
+        }
+        return sugg;
+    };
+    let kept: Vec<Range<usize>> = kept
+        .iter()
+        .filter_map(|&span| walk_span_to_context(span, replaced.ctxt()))
+        .filter(|&span| replaced.contains(span))
+        .map(|span| (span.lo() - replaced.lo()).to_usize()..(span.hi() - replaced.lo()).to_usize())
+        .collect();
+    let comments = comments_outside(&snippet, &kept);
+    if comments.is_empty() {
+        return sugg;
+    }
+    if *applicability != Applicability::Unspecified && comments.iter().any(|comment| is_doc_comment(comment)) {
+        *applicability = Applicability::MaybeIncorrect;
+    }
+    let indent = snippet_indent(cx, replaced).unwrap_or_default();
+    let mut res = prepend_comments(&comments, &sugg, &indent);
+    // Nothing to separate the last comment from if the removed code ends with it, or ends its line.
+    if sugg.is_empty() && (comments.last().map_or(false, |last| snippet.ends_with(last)) || ends_line(cx, replaced)) {
+        res.truncate(res.trim_end().len());
+    }
+    res
+}
+
+/// Checks if only whitespace follows `span` on its line.
+fn ends_line<T: LintContext>(cx: &T, span: Span) -> bool {
+    cx.sess().source_map().span_to_next_source(span).map_or(false, |rest| {
+        rest.trim_start_matches([' ', '\t'])
+            .chars()
+            .next()
+            .map_or(true, |c| c == '\n' || c == '\r')
+    })
+}
+
+/// Returns the comments of `snippet` that don't overlap any of the `kept` byte ranges.
+fn comments_outside<'a>(snippet: &'a str, kept: &[Range<usize>]) -> Vec<&'a str> {
+    let mut comments = Vec::new();
+    let mut pos = 0;
+    for token in tokenize(snippet) {
+        let range = pos..pos + token.len as usize;
+        pos = range.end;
+        if matches!(
+            token.kind,
+            TokenKind::LineComment { .. } | TokenKind::BlockComment { .. }
+        ) && !kept.iter().any(|kept| kept.start < range.end && range.start < kept.end)
+        {
+            comments.push(&snippet[range]);
+        }
+    }
+    comments
+}
+
+fn is_doc_comment(comment: &str) -> bool {
+    (comment.starts_with("///") && !comment.starts_with("////"))
+        || (comment.starts_with("/**") && !comment.starts_with("/***") && comment != "/**/")
+        || comment.starts_with("//!")
+        || comment.starts_with("/*!")
+}
+
+/// Puts the comments in front of `sugg`, each followed by a space, or a new line for line
+/// comments.
+fn prepend_comments(comments: &[&str], sugg: &str, indent: &str) -> String {
+    let mut res = String::new();
+    for comment in comments {
+        res.push_str(comment);
+        if comment.starts_with("//") {
+            res.push('\n');
+            res.push_str(indent);
+        } else {
+            res.push(' ');
+        }
+    }
+    res.push_str(sugg);
+    res
+}
+
 #[cfg(test)]
 mod test {
-    use super::{reindent_multiline, without_block_comments};
+    use super::{comments_outside, prepend_comments, reindent_multiline, without_block_comments};
 
     #[test]
     fn test_reindent_multiline_single_line() {
@@ -537,4 +643,22 @@ mod test {
         let result = without_block_comments(vec!["foo", "bar", "baz"]);
         assert_eq!(result, vec!["foo", "bar", "baz"]);
     }
+
+    #[test]
+    fn test_comments_outside() {
+        let snippet = "return /* a */ x // b\n";
+        assert_eq!(comments_outside(snippet, &[]), vec!["/* a */", "// b"]);
+        assert_eq!(comments_outside(snippet, &[7..14]), vec!["// b"]);
+        assert!(comments_outside("return x", &[]).is_empty());
+    }
+
+    #[test]
+    fn test_prepend_comments() {
+        assert_eq!(
+            prepend_comments(&["/* a */", "// b"], "x", "    "),
+            "/* a */ // b\n    x"
+        );
+        assert_eq!(prepend_comments(&["/* a */"], "", "    "), "/* a */ ");
+        assert_eq!(prepend_comments(&["// b"], "", ""), "// b\n");
+    }
 }
@@ -2,7 +2,8 @@
 #![deny(clippy::missing_docs_in_private_items)]
 
 use crate::source::{
-    snippet, snippet_opt, snippet_with_applicability, snippet_with_context, snippet_with_macro_callsite,
+    reattach_comments, snippet, snippet_opt, snippet_with_applicability, snippet_with_context,
+    snippet_with_macro_callsite,
 };
 use crate::ty::expr_sig;
 use crate::{get_parent_expr_for_hir, higher};
@@ -343,6 +344,25 @@ impl<'a> Sugg<'a> {
         }
     }
 
+    /// Keeps the comments of the code replaced by the suggestion, see
+    /// [`reattach_comments`](crate::source::reattach_comments).
+    #[must_use]
+    pub fn with_comments<T: LintContext>(
+        self,
+        cx: &T,
+        replaced: Span,
+        kept: &[Span],
+        applicability: &mut Applicability,
+    ) -> Self {
+        let sugg = self.to_string();
+        let res = reattach_comments(cx, replaced, kept, sugg.clone(), applicability);
+        if res == sugg {
+            self
+        } else {
+            Sugg::MaybeParen(res.into())
+        }
+    }
+
     /// Adds parentheses to any expression that might need them. Suitable to the
     /// `self` argument of a method call
     /// (e.g., to build `bar.foo()` or `(1 + 2).foo()`).
@@ -408,6 +408,28 @@ pub fn match_type(cx: &LateContext<'_>, ty: Ty<'_>, path: &[&str]) -> bool {
     }
 }
 
+/// Checks if the type is `std::time::Instant`.
+pub fn is_instant(cx: &LateContext<'_>, ty: Ty<'_>) -> bool {
+    match_type(cx, ty, &paths::INSTANT)
+}
+
+/// Checks if the type is `std::time::SystemTime`.
+pub fn is_system_time(cx: &LateContext<'_>, ty: Ty<'_>) -> bool {
+    match_type(cx, ty, &paths::SYSTEM_TIME)
+}
+
+/// Checks if the type is `core::time::Duration`.
+pub fn is_duration(cx: &LateContext<'_>, ty: Ty<'_>) -> bool {
+    is_type_diagnostic_item(cx, ty, sym::Duration)
+}
+
+/// Checks if the type is, or contains, a `std::time::Instant`. Like `Option<Instant>` or
+/// `Vec<(u32, Instant)>`.
+pub fn contains_instant<'tcx>(cx: &LateContext<'tcx>, ty: Ty<'tcx>) -> bool {
+    ty.walk()
+        .any(|arg| matches!(arg.unpack(), GenericArgKind::Type(ty) if is_instant(cx, ty)))
+}
+
 /// Checks if the drop order for a type matters. Some std types implement drop solely to
 /// deallocate memory. For these types, and composites containing them, changing the drop order
 /// won't result in any observable side effects.
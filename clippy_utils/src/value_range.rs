@@ -0,0 +1,646 @@
+//! A value-range analysis of integer expressions.
+//!
+//! [`int_range`] computes an interval containing every value an integer expression can have, from
+//! constants, masks, remainders, `min`/`max`/`clamp`, lossless conversions, the ranges `for` loops
+//! iterate over and the conditions of the `if`s dominating the expression. It is used to silence
+//! the lints about casts and arithmetic which can't truncate or overflow.
+//!
+//! The results hold on every target: the analysis runs with `isize` and `usize` being at most 32
+//! bits wide, and again with them being at least 64 bits wide, see [`pointer_widths`].
+
+#![deny(clippy::missing_docs_in_private_items)]
+
+use crate::comparisons::{normalize_comparison, Rel};
+use crate::consts::{constant, Constant};
+use crate::higher::{ForLoop, Range};
+use crate::macros::{find_assert_args, root_macro_call_first_node};
+use crate::{find_binding_init, is_diag_trait_item, path_def_id, path_to_local, sext};
+use rustc_ast::ast::RangeLimits;
+use rustc_hir::{BinOpKind, BindingAnnotation, Block, Expr, ExprKind, HirId, Node, PatKind, StmtKind, UnOp};
+use rustc_lint::LateContext;
+use rustc_middle::ty::{self, Ty, TyCtxt};
+use rustc_span::{sym, Symbol};
+
+/// How deep expressions, local initializers and conditions are followed.
+const MAX_DEPTH: u32 = 8;
+
+/// An inclusive range of integer values.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct IntRange {
+    /// The smallest value.
+    pub lo: i128,
+    /// The largest value.
+    pub hi: i128,
+}
+
+impl IntRange {
+    /// The values from `lo` to `hi`, both included.
+    #[must_use]
+    pub fn new(lo: i128, hi: i128) -> Self {
+        Self { lo, hi }
+    }
+
+    /// The values of an integer type, with `isize` and `usize` being `pointer_bits` wide, or `None`
+    /// if it's not an integer type or `u128`, whose values can't all be represented.
+    fn of_ty(ty: Ty<'_>, pointer_bits: u64) -> Option<Self> {
+        let (bits, signed) = match *ty.kind() {
+            ty::Int(ty::IntTy::Isize) => (pointer_bits, true),
+            ty::Int(ity) => (ity.bit_width()?, true),
+            ty::Uint(ty::UintTy::Usize) => (pointer_bits, false),
+            ty::Uint(uty) => (uty.bit_width()?, false),
+            _ => return None,
+        };
+        match (bits, signed) {
+            (128, false) => None,
+            (128, true) => Some(Self::new(i128::MIN, i128::MAX)),
+            (bits, true) => Some(Self::new(-(1 << (bits - 1)), (1 << (bits - 1)) - 1)),
+            (bits, false) => Some(Self::new(0, (1 << bits) - 1)),
+        }
+    }
+
+    /// Checks if all the values fit in `ty`, on every target. `isize` and `usize` are assumed to be
+    /// at most 32 bits wide, and at least as wide as on the current target.
+    pub fn fits_in(self, tcx: TyCtxt<'_>, ty: Ty<'_>) -> bool {
+        let [narrow, _] = pointer_widths(tcx);
+        Self::of_ty(ty, narrow).map_or(false, |ty_range| ty_range.contains(self))
+    }
+
+    /// Checks if all the values of `other` are in this range.
+    #[must_use]
+    pub fn contains(self, other: Self) -> bool {
+        self.lo <= other.lo && other.hi <= self.hi
+    }
+
+    /// The values in both ranges. If there are none, the code is unreachable and `self` is kept.
+    #[must_use]
+    pub fn intersect(self, other: Self) -> Self {
+        let range = Self::new(self.lo.max(other.lo), self.hi.min(other.hi));
+        if range.lo <= range.hi { range } else { self }
+    }
+
+    /// The smallest range containing both ranges.
+    #[must_use]
+    pub fn hull(self, other: Self) -> Self {
+        Self::new(self.lo.min(other.lo), self.hi.max(other.hi))
+    }
+
+    /// The smallest range containing the results of `op` applied to the bounds of both ranges,
+    /// for operations which are monotonic in both arguments.
+    fn corners(self, other: Self, op: impl Fn(i128, i128) -> Option<i128>) -> Option<Self> {
+        let values = [
+            op(self.lo, other.lo)?,
+            op(self.lo, other.hi)?,
+            op(self.hi, other.lo)?,
+            op(self.hi, other.hi)?,
+        ];
+        Some(Self::new(*values.iter().min()?, *values.iter().max()?))
+    }
+
+    /// The largest absolute value.
+    fn magnitude(self) -> Option<i128> {
+        self.lo.checked_abs()?.max(self.hi.checked_abs()?).into()
+    }
+}
+
+/// Returns a range containing every value of the integer expression `expr`, or `None` if `expr`
+/// isn't of an integer type or is a `u128` which can have any value.
+///
+/// Without anything known about the expression, this is the range of its type, with `isize` and
+/// `usize` being at least 64 bits wide.
+pub fn int_range<'tcx>(cx: &LateContext<'tcx>, expr: &'tcx Expr<'tcx>) -> Option<IntRange> {
+    let [narrow, wide] = pointer_widths(cx.tcx).map(|pointer_bits| {
+        RangeCx {
+            cx,
+            depth: 0,
+            pointer_bits,
+        }
+        .range(expr)
+    });
+    Some(narrow?.hull(wide?))
+}
+
+/// Checks if the arithmetic operation `expr`, e.g. `a + b`, `a += b` or `-a`, can neither
+/// overflow, nor divide by zero, nor shift by more than the width of its type, on any target.
+pub fn is_overflow_free<'tcx>(cx: &LateContext<'tcx>, expr: &'tcx Expr<'tcx>) -> bool {
+    pointer_widths(cx.tcx).into_iter().all(|pointer_bits| {
+        RangeCx {
+            cx,
+            depth: 0,
+            pointer_bits,
+        }
+        .is_overflow_free(expr)
+    })
+}
+
+/// The widths of `isize` and `usize` the analysis runs with, see the module documentation: the
+/// width on the current target, at most 32 bits, and at least 64 bits.
+fn pointer_widths(tcx: TyCtxt<'_>) -> [u64; 2] {
+    let pointer_bits = tcx.data_layout.pointer_size.bits();
+    [pointer_bits.min(32), pointer_bits.max(64)]
+}
+
+/// A condition known to be true, or false, at some expression, see
+/// [`for_each_dominating_condition`].
+#[derive(Clone, Copy)]
+pub struct DominatingCondition<'tcx> {
+    /// The condition, which is never a `&&`, `||` or `!` expression.
+    pub cond: &'tcx Expr<'tcx>,
+    /// The value of the condition.
+    pub truth: bool,
+    /// The expression in which the condition is known: the branch of the `if`, or the block
+    /// containing the diverging `if` or `assert!`.
+    pub scope: &'tcx Expr<'tcx>,
+    /// Whether the condition comes from a `debug_assert!`, which is only checked when debug
+    /// assertions are enabled.
+    pub debug_assertion: bool,
+}
+
+/// Calls `f` with the conditions known to be true, or false, at `expr`: the conditions of the
+/// `if`s containing it, the left operands of the `&&`s and `||`s it's the right operand of, and
+/// the conditions of the preceding `if`s which return, break or panic, like `assert!` does, and of
+/// the preceding `debug_assert!`s. `&&`, `||` and `!` are split, e.g. `if !(a || b)` calls `f`
+/// with `a` and `b` being false.
+///
+/// Nothing checks that the operands of the conditions aren't changed in between.
+pub fn for_each_dominating_condition<'tcx>(
+    cx: &LateContext<'tcx>,
+    expr: &'tcx Expr<'tcx>,
+    mut f: impl FnMut(DominatingCondition<'tcx>),
+) {
+    let hir = cx.tcx.hir();
+    let mut child = expr.hir_id;
+    for (parent_id, node) in hir.parent_iter(expr.hir_id) {
+        match node {
+            Node::Expr(parent) => {
+                if let ExprKind::If(cond, then, els) = parent.kind {
+                    if then.hir_id == child {
+                        split_condition(cond, true, &mut |cond, truth| {
+                            f(DominatingCondition {
+                                cond,
+                                truth,
+                                scope: then,
+                                debug_assertion: false,
+                            });
+                        });
+                    } else if let Some(els) = els
+                        && els.hir_id == child
+                    {
+                        split_condition(cond, false, &mut |cond, truth| {
+                            f(DominatingCondition {
+                                cond,
+                                truth,
+                                scope: els,
+                                debug_assertion: false,
+                            });
+                        });
+                    }
+                } else if let ExprKind::Binary(op, lhs, rhs) = parent.kind
+                    && matches!(op.node, BinOpKind::And | BinOpKind::Or)
+                    && rhs.hir_id == child
+                {
+                    // `a && b` only evaluates `b` if `a` is true, `a || b` if it's false
+                    split_condition(lhs, op.node == BinOpKind::And, &mut |cond, truth| {
+                        f(DominatingCondition {
+                            cond,
+                            truth,
+                            scope: rhs,
+                            debug_assertion: false,
+                        });
+                    });
+                }
+            },
+            Node::Block(block) => {
+                let Some(Node::Expr(scope)) = hir.find_parent(block.hir_id) else {
+                    child = parent_id;
+                    continue;
+                };
+                for stmt in block.stmts.iter().take_while(|stmt| stmt.hir_id != child) {
+                    let (StmtKind::Expr(e) | StmtKind::Semi(e)) = stmt.kind else {
+                        continue;
+                    };
+                    let (cond, truth, debug_assertion) = if let Some(macro_call) = root_macro_call_first_node(cx, e)
+                        && cx.tcx.is_diagnostic_item(sym::debug_assert_macro, macro_call.def_id)
+                        && let Some((cond, _)) = find_assert_args(cx, e, macro_call.expn)
+                    {
+                        (cond, true, true)
+                    } else if let ExprKind::If(cond, then, None) = e.kind
+                        && diverges(cx, then)
+                    {
+                        (cond, false, false)
+                    } else {
+                        continue;
+                    };
+                    split_condition(cond, truth, &mut |cond, truth| {
+                        f(DominatingCondition {
+                            cond,
+                            truth,
+                            scope,
+                            debug_assertion,
+                        });
+                    });
+                }
+            },
+            Node::Item(_) | Node::ImplItem(_) | Node::TraitItem(_) | Node::ForeignItem(_) => break,
+            _ => {},
+        }
+        child = parent_id;
+    }
+}
+
+/// Splits `cond` at `&&`, `||` and `!`, as far as the parts are known from `cond` being `truth`.
+fn split_condition<'tcx>(cond: &'tcx Expr<'tcx>, truth: bool, f: &mut impl FnMut(&'tcx Expr<'tcx>, bool)) {
+    match cond.kind {
+        ExprKind::DropTemps(cond) => split_condition(cond, truth, f),
+        ExprKind::Unary(UnOp::Not, cond) => split_condition(cond, !truth, f),
+        ExprKind::Binary(op, lhs, rhs)
+            if (op.node == BinOpKind::And && truth) || (op.node == BinOpKind::Or && !truth) =>
+        {
+            split_condition(lhs, truth, f);
+            split_condition(rhs, truth, f);
+        },
+        _ => f(cond, truth),
+    }
+}
+
+/// Checks if the block `expr` always returns, breaks, continues or panics.
+fn diverges(cx: &LateContext<'_>, expr: &Expr<'_>) -> bool {
+    let ExprKind::Block(Block { stmts, expr: tail, .. }, _) = expr.kind else {
+        return cx.typeck_results().expr_ty(expr).is_never();
+    };
+    let last = tail.or_else(|| match stmts.last()?.kind {
+        StmtKind::Expr(e) | StmtKind::Semi(e) => Some(e),
+        _ => None,
+    });
+    last.map_or(false, |e| cx.typeck_results().expr_ty(e).is_never())
+}
+
+/// The number of bits of an integer type, with `isize` and `usize` being `pointer_bits` wide.
+fn bit_width(ty: Ty<'_>, pointer_bits: u64) -> u64 {
+    match *ty.kind() {
+        ty::Int(ty::IntTy::Isize) | ty::Uint(ty::UintTy::Usize) => pointer_bits,
+        ty::Int(ity) => ity.bit_width().unwrap_or(0),
+        ty::Uint(uty) => uty.bit_width().unwrap_or(0),
+        _ => 0,
+    }
+}
+
+/// The range of `lhs op rhs` for the ranges of both operands. Returns `None` if it's unknown,
+/// e.g. because the operation may overflow, which the caller has to check against the type.
+fn binary_range(op: BinOpKind, ty: Ty<'_>, pointer_bits: u64, lhs: IntRange, rhs: IntRange) -> Option<IntRange> {
+    match op {
+        BinOpKind::Add => lhs.corners(rhs, i128::checked_add),
+        BinOpKind::Sub => lhs.corners(rhs, i128::checked_sub),
+        BinOpKind::Mul => lhs.corners(rhs, i128::checked_mul),
+        // Division is monotonic in both operands as long as the divisor doesn't change its sign
+        BinOpKind::Div if rhs.lo > 0 || rhs.hi < 0 => lhs.corners(rhs, i128::checked_div),
+        BinOpKind::Rem => {
+            let max = rhs.magnitude()?.checked_sub(1)?;
+            let range = if lhs.lo >= 0 {
+                IntRange::new(0, max)
+            } else if lhs.hi <= 0 {
+                IntRange::new(-max, 0)
+            } else {
+                IntRange::new(-max, max)
+            };
+            Some(range.intersect(IntRange::new(lhs.lo.min(0), lhs.hi.max(0))))
+        },
+        BinOpKind::BitAnd => match (lhs.lo >= 0, rhs.lo >= 0) {
+            (true, true) => Some(IntRange::new(0, lhs.hi.min(rhs.hi))),
+            (true, false) => Some(IntRange::new(0, lhs.hi)),
+            (false, true) => Some(IntRange::new(0, rhs.hi)),
+            (false, false) => None,
+        },
+        BinOpKind::BitOr | BinOpKind::BitXor if lhs.lo >= 0 && rhs.lo >= 0 => {
+            // All the bits set in the result are set in one of the operands
+            let max = lhs.hi.max(rhs.hi);
+            let all_bits = 128 - max.leading_zeros();
+            let lo = if op == BinOpKind::BitOr { lhs.lo.max(rhs.lo) } else { 0 };
+            Some(IntRange::new(lo, 1i128.checked_shl(all_bits)? - 1))
+        },
+        BinOpKind::Shr | BinOpKind::Shl
+            if IntRange::new(0, i128::from(bit_width(ty, pointer_bits)) - 1).contains(rhs) =>
+        {
+            if op == BinOpKind::Shr {
+                lhs.corners(rhs, |value, shift| Some(value >> shift))
+            } else {
+                // Bits shifted out are lost, this is only the value if it fits in the type
+                lhs.corners(rhs, |value, shift| {
+                    value.checked_mul(1i128.checked_shl(u32::try_from(shift).ok()?)?)
+                })
+            }
+        },
+        _ => None,
+    }
+}
+
+/// The state of the analysis.
+struct RangeCx<'a, 'tcx> {
+    /// The context of the lint.
+    cx: &'a LateContext<'tcx>,
+    /// How many expressions are being analysed, see [`MAX_DEPTH`].
+    depth: u32,
+    /// The width of `isize` and `usize`, see [`pointer_widths`].
+    pointer_bits: u64,
+}
+
+impl<'a, 'tcx> RangeCx<'a, 'tcx> {
+    /// The range of `expr`, or of its type if nothing more is known.
+    fn range(&self, expr: &'tcx Expr<'tcx>) -> Option<IntRange> {
+        let ty = self.cx.typeck_results().expr_ty(expr).peel_refs();
+        let ty_range = IntRange::of_ty(ty, self.pointer_bits)?;
+        if self.depth >= MAX_DEPTH {
+            return Some(ty_range);
+        }
+        let rcx = RangeCx {
+            cx: self.cx,
+            depth: self.depth + 1,
+            pointer_bits: self.pointer_bits,
+        };
+        Some(
+            rcx.expr_range(expr, ty)
+                .filter(|range| ty_range.contains(*range))
+                .unwrap_or(ty_range),
+        )
+    }
+
+    /// Checks if the arithmetic operation `expr` can't overflow with this width of `isize` and
+    /// `usize`, see [`is_overflow_free`].
+    fn is_overflow_free(&self, expr: &'tcx Expr<'tcx>) -> bool {
+        match expr.kind {
+            ExprKind::Binary(op, lhs, rhs) | ExprKind::AssignOp(op, lhs, rhs) => {
+                let ty = self.cx.typeck_results().expr_ty(lhs).peel_refs();
+                let (Some(lhs_range), Some(rhs_range)) = (self.range(lhs), self.range(rhs)) else {
+                    return false;
+                };
+                let Some(ty_range) = IntRange::of_ty(ty, self.pointer_bits) else {
+                    return false;
+                };
+                match op.node {
+                    BinOpKind::Shl | BinOpKind::Shr => {
+                        IntRange::new(0, i128::from(bit_width(ty, self.pointer_bits)) - 1).contains(rhs_range)
+                    },
+                    BinOpKind::Div | BinOpKind::Rem
+                        if rhs_range.contains(IntRange::new(0, 0))
+                            // `MIN / -1` overflows
+                            || (lhs_range.contains(IntRange::new(ty_range.lo, ty_range.lo))
+                                && rhs_range.contains(IntRange::new(-1, -1))) =>
+                    {
+                        false
+                    },
+                    BinOpKind::Div | BinOpKind::Rem => true,
+                    _ => binary_range(op.node, ty, self.pointer_bits, lhs_range, rhs_range)
+                        .map_or(false, |range| ty_range.contains(range)),
+                }
+            },
+            ExprKind::Unary(UnOp::Neg, operand) => {
+                let ty = self.cx.typeck_results().expr_ty(operand).peel_refs();
+                match (self.range(operand), IntRange::of_ty(ty, self.pointer_bits)) {
+                    (Some(range), Some(ty_range)) => range.lo > ty_range.lo,
+                    _ => false,
+                }
+            },
+            _ => false,
+        }
+    }
+
+    /// The range of `expr`, if something is known about it.
+    fn expr_range(&self, expr: &'tcx Expr<'tcx>, ty: Ty<'tcx>) -> Option<IntRange> {
+        if let Some((Constant::Int(value), _)) = constant(self.cx, self.cx.typeck_results(), expr) {
+            let value = match *ty.kind() {
+                ty::Int(ity) => sext(self.cx.tcx, value, ity),
+                _ => i128::try_from(value).ok()?,
+            };
+            return Some(IntRange::new(value, value));
+        }
+
+        match expr.kind {
+            ExprKind::AddrOf(_, _, inner) | ExprKind::Unary(UnOp::Deref, inner) | ExprKind::DropTemps(inner) => {
+                self.range(inner)
+            },
+            ExprKind::Path(_) => self.local_range(expr),
+            ExprKind::Cast(inner, _) => self.conversion_range(inner),
+            ExprKind::Block(block, _) => self.range(block.expr?),
+            ExprKind::If(_, then, Some(els)) => self.hull([then, els]),
+            ExprKind::Match(_, arms, _) => self.hull(arms.iter().map(|arm| arm.body)),
+            ExprKind::Unary(UnOp::Neg, operand) => {
+                let range = self.range(operand)?;
+                Some(IntRange::new(range.hi.checked_neg()?, range.lo.checked_neg()?))
+            },
+            ExprKind::Binary(op, lhs, rhs) => {
+                binary_range(op.node, ty, self.pointer_bits, self.range(lhs)?, self.range(rhs)?)
+            },
+            ExprKind::MethodCall(method, receiver, args, _) => {
+                self.method_range(expr, method.ident.name, receiver, args)
+            },
+            ExprKind::Call(func, [lhs, rhs]) => {
+                let def_id = path_def_id(self.cx, func)?;
+                let (lhs, rhs) = (self.range(lhs)?, self.range(rhs)?);
+                match self.cx.tcx.get_diagnostic_name(def_id)? {
+                    sym::cmp_min => Some(IntRange::new(lhs.lo.min(rhs.lo), lhs.hi.min(rhs.hi))),
+                    sym::cmp_max => Some(IntRange::new(lhs.lo.max(rhs.lo), lhs.hi.max(rhs.hi))),
+                    _ => None,
+                }
+            },
+            ExprKind::Call(func, [arg]) => {
+                let def_id = path_def_id(self.cx, func)?;
+                is_diag_trait_item(self.cx, def_id, sym::From)
+                    .then(|| self.conversion_range(arg))
+                    .flatten()
+            },
+            _ => None,
+        }
+    }
+
+    /// The smallest range containing the ranges of all `exprs`, ignoring the ones which diverge.
+    fn hull(&self, exprs: impl IntoIterator<Item = &'tcx Expr<'tcx>>) -> Option<IntRange> {
+        exprs
+            .into_iter()
+            .filter(|expr| !self.cx.typeck_results().expr_ty(expr).is_never())
+            .map(|expr| self.range(expr))
+            .reduce(|a, b| Some(a?.hull(b?)))?
+    }
+
+    /// The range of a value converted from `expr`, which may be a `bool` or a `char`.
+    fn conversion_range(&self, expr: &'tcx Expr<'tcx>) -> Option<IntRange> {
+        match self.cx.typeck_results().expr_ty(expr).kind() {
+            ty::Bool => Some(IntRange::new(0, 1)),
+            ty::Char => Some(IntRange::new(0, u32::from(char::MAX).into())),
+            _ => self.range(expr),
+        }
+    }
+
+    /// The range of the integer methods which limit their results.
+    fn method_range(
+        &self,
+        expr: &'tcx Expr<'tcx>,
+        name: Symbol,
+        receiver: &'tcx Expr<'tcx>,
+        args: &'tcx [Expr<'tcx>],
+    ) -> Option<IntRange> {
+        let receiver_ty = self.cx.typeck_results().expr_ty(receiver).peel_refs();
+        if !receiver_ty.is_integral() {
+            if let Some(def_id) = self.cx.typeck_results().type_dependent_def_id(expr.hir_id)
+                && is_diag_trait_item(self.cx, def_id, sym::Into)
+            {
+                return self.conversion_range(receiver);
+            }
+            return None;
+        }
+        let bits = i128::from(bit_width(receiver_ty, self.pointer_bits));
+        match (name.as_str(), args) {
+            ("min", [other]) => {
+                let (value, other) = (self.range(receiver)?, self.range(other)?);
+                Some(IntRange::new(value.lo.min(other.lo), value.hi.min(other.hi)))
+            },
+            ("max", [other]) => {
+                let (value, other) = (self.range(receiver)?, self.range(other)?);
+                Some(IntRange::new(value.lo.max(other.lo), value.hi.max(other.hi)))
+            },
+            // `clamp` panics if `min > max`
+            ("clamp", [min, max]) => {
+                let (value, min, max) = (self.range(receiver)?, self.range(min)?, self.range(max)?);
+                Some(IntRange::new(
+                    min.lo.max(value.lo.min(max.lo)),
+                    max.hi.min(value.hi.max(min.hi)),
+                ))
+            },
+            ("rem_euclid", [divisor]) => Some(IntRange::new(0, self.range(divisor)?.magnitude()?.checked_sub(1)?)),
+            ("abs" | "unsigned_abs", []) => {
+                let value = self.range(receiver)?;
+                let lo = if value.lo <= 0 && value.hi >= 0 {
+                    0
+                } else {
+                    value.lo.checked_abs()?.min(value.hi.checked_abs()?)
+                };
+                Some(IntRange::new(lo, value.magnitude()?))
+            },
+            ("signum", []) => Some(IntRange::new(-1, 1)),
+            (
+                "count_ones" | "count_zeros" | "leading_zeros" | "trailing_zeros" | "leading_ones" | "trailing_ones",
+                [],
+            ) => Some(IntRange::new(0, bits)),
+            ("into" | "to_owned" | "clone", []) => self.range(receiver),
+            _ => None,
+        }
+    }
+
+    /// The range of the local variable `expr` refers to, from its initializer or the range of the
+    /// `for` loop binding it, narrowed by the conditions dominating `expr`.
+    fn local_range(&self, expr: &'tcx Expr<'tcx>) -> Option<IntRange> {
+        let id = path_to_local(expr)?;
+        let hir = self.cx.tcx.hir();
+        // Mutable bindings may change after their initialization or the conditions
+        let Some(Node::Pat(pat)) = hir.find(id) else {
+            return None;
+        };
+        let PatKind::Binding(BindingAnnotation::NONE, ..) = pat.kind else {
+            return None;
+        };
+
+        let ty = self.cx.typeck_results().expr_ty(expr).peel_refs();
+        let mut range = IntRange::of_ty(ty, self.pointer_bits)?;
+        if let Some(init) = find_binding_init(self.cx, id) {
+            range = self.range(init)?;
+        } else if let Some(loop_range) = self.loop_range(id) {
+            range = loop_range;
+        }
+
+        for_each_dominating_condition(self.cx, expr, |condition| {
+            // Release builds don't check `debug_assert!`s
+            if !condition.debug_assertion
+                && let Some(narrowed) = self.narrow(range, id, condition.cond, condition.truth)
+            {
+                range = narrowed;
+            }
+        });
+        Some(range)
+    }
+
+    /// The values taken by the binding `id` if it's the pattern of a `for` loop over a range.
+    fn loop_range(&self, id: HirId) -> Option<IntRange> {
+        let for_loop = self.cx.tcx.hir().parent_iter(id).find_map(|(_, node)| match node {
+            Node::Expr(expr) => ForLoop::hir(expr),
+            _ => None,
+        })?;
+        if for_loop.pat.hir_id != id {
+            return None;
+        }
+        let mut arg = for_loop.arg;
+        if let ExprKind::MethodCall(method, receiver, [], _) = arg.kind
+            && method.ident.name.as_str() == "rev"
+        {
+            arg = receiver;
+        }
+        let range = Range::hir(arg)?;
+        let lo = self.range(range.start?)?.lo;
+        let Some(end) = range.end else {
+            let ty = self.cx.typeck_results().expr_ty(range.start?);
+            return Some(IntRange::new(lo, IntRange::of_ty(ty, self.pointer_bits)?.hi));
+        };
+        let hi = self.range(end)?.hi;
+        let hi = match range.limits {
+            RangeLimits::HalfOpen => hi.checked_sub(1)?,
+            RangeLimits::Closed => hi,
+        };
+        (lo <= hi).then_some(IntRange::new(lo, hi))
+    }
+
+    /// Narrows the range of the local `id` with the comparison `cond` being `truth`.
+    fn narrow(&self, range: IntRange, id: HirId, cond: &'tcx Expr<'tcx>, truth: bool) -> Option<IntRange> {
+        if let ExprKind::MethodCall(method, receiver, [arg], _) = cond.kind
+            && truth
+            && method.ident.name.as_str() == "contains"
+            && path_to_local(peel_ref(arg)) == Some(id)
+        {
+            let bounds = Range::hir(peel_ref(receiver))?;
+            let lo = bounds.start.and_then(|start| self.range(start)).map_or(range.lo, |start| start.lo);
+            let hi = match (bounds.end.and_then(|end| self.range(end)), bounds.limits) {
+                (Some(end), RangeLimits::HalfOpen) => end.hi.checked_sub(1)?,
+                (Some(end), RangeLimits::Closed) => end.hi,
+                (None, _) => range.hi,
+            };
+            return Some(range.intersect(IntRange::new(lo, hi)));
+        }
+
+        let ExprKind::Binary(op, lhs, rhs) = cond.kind else {
+            return None;
+        };
+        let (rel, lhs, rhs) = normalize_comparison(op.node, lhs, rhs)?;
+        // `!(a < b)` is `b <= a` and `!(a <= b)` is `b < a`
+        let (rel, lhs, rhs) = match (rel, truth) {
+            (rel, true) => (rel, lhs, rhs),
+            (Rel::Lt, false) => (Rel::Le, rhs, lhs),
+            (Rel::Le, false) => (Rel::Lt, rhs, lhs),
+            (Rel::Eq, false) => (Rel::Ne, lhs, rhs),
+            (Rel::Ne, false) => (Rel::Eq, lhs, rhs),
+        };
+        let (lhs, rhs) = (peel_ref(lhs), peel_ref(rhs));
+        if path_to_local(lhs) == Some(id) {
+            let bound = self.range(rhs)?;
+            match rel {
+                Rel::Lt => Some(range.intersect(IntRange::new(range.lo, bound.hi.checked_sub(1)?))),
+                Rel::Le => Some(range.intersect(IntRange::new(range.lo, bound.hi))),
+                Rel::Eq => Some(range.intersect(bound)),
+                Rel::Ne => None,
+            }
+        } else if path_to_local(rhs) == Some(id) {
+            let bound = self.range(lhs)?;
+            match rel {
+                Rel::Lt => Some(range.intersect(IntRange::new(bound.lo.checked_add(1)?, range.hi))),
+                Rel::Le => Some(range.intersect(IntRange::new(bound.lo, range.hi))),
+                Rel::Eq => Some(range.intersect(bound)),
+                Rel::Ne => None,
+            }
+        } else {
+            None
+        }
+    }
+}
+
+/// Removes the `&` of `&x`.
+fn peel_ref<'tcx>(expr: &'tcx Expr<'tcx>) -> &'tcx Expr<'tcx> {
+    match expr.kind {
+        ExprKind::AddrOf(_, _, inner) => inner,
+        _ => expr,
+    }
+}
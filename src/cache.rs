@@ -0,0 +1,497 @@
+//! Caches the diagnostics of the crates checked by Cargo, enabled with `CLIPPY_CACHE=1`.
+//!
+//! Cargo re-runs Clippy on a crate whenever `CLIPPY_ARGS` changes, e.g. when a lint is enabled on
+//! the command line. With the cache, the driver runs the compiler in a child process and stores
+//! its output, the files it wrote and a [`CacheRecord`] in `clippy-cache/<crate>` next to the
+//! crate's build output. The next run of the same crate replays the stored output instead of
+//! checking the crate again if
+//!
+//! - the compiler arguments, except for lint levels, and `clippy.toml`'s location are the same,
+//! - the source files, the environment variables and the dependencies read by the compiler didn't
+//!   change, and
+//! - the lint levels set on the command line are the same, or only changed for Clippy lints which
+//!   were checked and never emitted in the cached run.
+//!
+//! Only invocations with `--error-format=json`, as used by Cargo, and `--emit=dep-info,metadata`,
+//! as used by `cargo check`, are cached.
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::SystemTime;
+
+use clippy_lints::CacheRecord;
+use serde::{Deserialize, Serialize};
+
+use crate::arg_value;
+
+/// The variable telling the driver running in the child process where to write its
+/// [`CacheRecord`].
+const RECORD_VAR: &str = "CLIPPY_CACHE_RECORD";
+
+/// Whether `CLIPPY_CACHE=1` is set. It is never set in the child process.
+pub fn enabled() -> bool {
+    env::var("CLIPPY_CACHE").map_or(false, |cache| cache == "1")
+}
+
+/// The path the [`CacheRecord`] is written to, if the driver runs in the child process.
+pub fn record_path() -> Option<PathBuf> {
+    env::var_os(RECORD_VAR).map(PathBuf::from)
+}
+
+/// Replays the output of the compiler for `args`, or runs it in a child process and caches its
+/// output. Returns the exit code, or `None` if the invocation can't be cached and the compiler has
+/// to run in this process.
+pub fn run(args: &[String]) -> Option<i32> {
+    if arg_value(args, "--error-format", |format| format == "json").is_none()
+        || arg_value(args, "--emit", |emit| emit == "dep-info,metadata").is_none()
+    {
+        return None;
+    }
+    let out_dir = Path::new(arg_value(args, "--out-dir", |_| true)?);
+    let crate_name = arg_value(args, "--crate-name", |_| true)?;
+    let extra_filename = arg_value(args, "-C", |opt| opt.starts_with("extra-filename="))
+        .and_then(|opt| opt.strip_prefix("extra-filename="))
+        .unwrap_or_default();
+    let file_stem = format!("{crate_name}{extra_filename}");
+    let dep_info = out_dir.join(format!("{file_stem}.d"));
+    // The dep-info file names the metadata of binaries without the `lib` prefix, which isn't the
+    // file that is written
+    let outputs = [dep_info.clone(), out_dir.join(format!("lib{file_stem}.rmeta"))];
+    let cache_dir = out_dir
+        .parent()
+        .unwrap_or(out_dir)
+        .join("clippy-cache")
+        .join(&file_stem);
+
+    let (other_args, lint_flags) = split_lint_flags(args);
+    let key = cache_key(&other_args);
+    if let Some(entry) = Entry::read(&cache_dir)
+        && entry.key == key
+        && entry.is_fresh()
+        && can_replay(&entry, &lint_flags)
+        && entry.replay(&cache_dir, &dep_info).is_ok()
+    {
+        return Some(entry.code);
+    }
+
+    let record = cache_dir.join("record.json");
+    if let Err(error) = fs::create_dir_all(&cache_dir).and_then(|()| match fs::remove_file(&record) {
+        Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(()),
+        result => result,
+    }) {
+        eprintln!(
+            "warning: could not create the Clippy cache in `{}`: {error}",
+            cache_dir.display()
+        );
+        return None;
+    }
+
+    let output = Command::new(env::current_exe().ok()?)
+        .args(env::args_os().skip(1))
+        .env_remove("CLIPPY_CACHE")
+        .env(RECORD_VAR, &record)
+        .stdin(Stdio::inherit())
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::piped())
+        .output()
+        .ok()?;
+    let stderr = String::from_utf8_lossy(&output.stderr).into_owned();
+    eprint!("{stderr}");
+    let code = output.status.code().unwrap_or(101);
+
+    // Only store the runs which completed the lints, not the ones that failed to compile or crashed
+    if matches!(code, 0 | 1)
+        && let Ok(record) = fs::read_to_string(&record)
+        && let Ok(record) = serde_json::from_str(&record)
+        && let Some(entry) = Entry::new(key, args, lint_flags, record, stderr, code, &outputs)
+        && let Err(error) = entry.write(&cache_dir)
+    {
+        eprintln!(
+            "warning: could not write the Clippy cache to `{}`: {error}",
+            cache_dir.display()
+        );
+    }
+    Some(code)
+}
+
+/// A lint level set on the command line, e.g. `("warn", "clippy::needless_return")`.
+type LintFlag = (String, String);
+
+/// Splits the lint levels, e.g. `-W clippy::needless-return`, from the other arguments.
+fn split_lint_flags(args: &[String]) -> (Vec<&str>, Vec<LintFlag>) {
+    const LEVELS: [(&str, &str, &str); 5] = [
+        ("-A", "--allow", "allow"),
+        ("-W", "--warn", "warn"),
+        ("", "--force-warn", "force-warn"),
+        ("-D", "--deny", "deny"),
+        ("-F", "--forbid", "forbid"),
+    ];
+
+    let mut other_args = Vec::new();
+    let mut lint_flags = Vec::new();
+    // Skip the name of the executable, which differs between the parent and the child process
+    let mut args = args.iter().skip(1).map(String::as_str);
+    while let Some(arg) = args.next() {
+        let flag = LEVELS.iter().find_map(|&(short, long, level)| {
+            let lint = if arg == long || (!short.is_empty() && arg == short) {
+                args.next()
+            } else if let Some(lint) = arg.strip_prefix(long).and_then(|lint| lint.strip_prefix('=')) {
+                Some(lint)
+            } else if short.is_empty() {
+                None
+            } else {
+                arg.strip_prefix(short).filter(|lint| !lint.is_empty())
+            };
+            lint.map(|lint| (level.to_string(), lint.replace('-', "_")))
+        });
+        match flag {
+            Some(flag) => lint_flags.push(flag),
+            None => other_args.push(arg),
+        }
+    }
+    (other_args, lint_flags)
+}
+
+/// Hashes everything the output depends on besides the lint levels and the files and environment
+/// variables listed in the dep-info file.
+fn cache_key(other_args: &[&str]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    rustc_tools_util::get_version_info!().to_string().hash(&mut hasher);
+    other_args.hash(&mut hasher);
+    env::current_dir().ok().hash(&mut hasher);
+    clippy_lints::lookup_conf_file().ok().flatten().hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Checks whether the lint levels changed only for lints that were checked and never emitted in
+/// the cached run, so that the output can't have changed.
+fn can_replay(entry: &Entry, lint_flags: &[LintFlag]) -> bool {
+    if entry.lint_flags == lint_flags {
+        return true;
+    }
+
+    // The levels of other lints, including `warnings`, must be set in the same way
+    let is_clippy_flag = |(_, lint): &&LintFlag| lint.starts_with("clippy::");
+    if !entry
+        .lint_flags
+        .iter()
+        .filter(|flag| !is_clippy_flag(flag))
+        .eq(lint_flags.iter().filter(|flag| !is_clippy_flag(flag)))
+    {
+        return false;
+    }
+
+    let (Some(cached), Some(current)) = (
+        command_line_levels(&entry.record, &entry.lint_flags),
+        command_line_levels(&entry.record, lint_flags),
+    ) else {
+        return false;
+    };
+    entry.record.levels.iter().all(|(lint, level)| {
+        let (cached, current) = (cached.get(lint.as_str()), current.get(lint.as_str()));
+        cached == current
+            || (level != "allow"
+                && !entry.record.emitted.contains(lint)
+                // `forbid` and `force-warn` also change the levels set by attributes
+                && [cached, current]
+                    .iter()
+                    .flatten()
+                    .all(|(level, _)| !matches!(*level, "forbid" | "force-warn")))
+    })
+}
+
+/// The level each flag sets for each Clippy lint, together with the flag setting it, which is
+/// mentioned in the output. Returns `None` if a flag names an unknown lint.
+fn command_line_levels<'a>(
+    record: &'a CacheRecord,
+    lint_flags: &'a [LintFlag],
+) -> Option<BTreeMap<&'a str, (&'a str, &'a str)>> {
+    let mut levels = BTreeMap::new();
+    for (level, name) in lint_flags {
+        if !name.starts_with("clippy::") {
+            continue;
+        }
+        if let Some(lints) = record.groups.get(name) {
+            for lint in lints {
+                levels.insert(lint.as_str(), (level.as_str(), name.as_str()));
+            }
+        } else if record.levels.contains_key(name) {
+            levels.insert(name.as_str(), (level.as_str(), name.as_str()));
+        } else {
+            return None;
+        }
+    }
+    Some(levels)
+}
+
+#[derive(Serialize, Deserialize)]
+struct Entry {
+    key: u64,
+    /// The files read by the compiler.
+    files: Vec<FileStamp>,
+    /// The environment variables read by the compiler, except for `CLIPPY_ARGS`.
+    env: Vec<(String, Option<String>)>,
+    lint_flags: Vec<LintFlag>,
+    record: CacheRecord,
+    /// The files written by the compiler, copied to the cache directory.
+    outputs: Vec<PathBuf>,
+    stderr: String,
+    code: i32,
+}
+
+impl Entry {
+    fn new(
+        key: u64,
+        args: &[String],
+        lint_flags: Vec<LintFlag>,
+        mut record: CacheRecord,
+        stderr: String,
+        code: i32,
+        outputs: &[PathBuf],
+    ) -> Option<Self> {
+        let dep_info = DepInfo::parse(&fs::read_to_string(&outputs[0]).ok()?);
+
+        // Lints emitted without the functions of `clippy_utils::diagnostics` are only found in the
+        // output
+        record.emitted.extend(
+            stderr
+                .match_indices("clippy::")
+                .map(|(start, _)| {
+                    let name = &stderr[start..];
+                    let end = name
+                        .char_indices()
+                        .skip("clippy::".len())
+                        .find(|(_, c)| !c.is_ascii_alphanumeric() && *c != '_')
+                        .map_or(name.len(), |(end, _)| end);
+                    name[..end].to_string()
+                })
+                .filter(|name| record.levels.contains_key(name)),
+        );
+
+        // The dependencies are read through `--extern`, they aren't listed in the dep-info file
+        let externs = args
+            .windows(2)
+            .filter(|args| args[0] == "--extern")
+            .filter_map(|args| args[1].split_once('=').map(|(_, path)| path));
+        let files = dep_info
+            .files
+            .iter()
+            .map(String::as_str)
+            .chain(externs)
+            .map(|path| FileStamp::new(Path::new(path)))
+            .collect::<Option<_>>()?;
+
+        Some(Self {
+            key,
+            files,
+            env: dep_info.env,
+            lint_flags,
+            record,
+            outputs: outputs.iter().filter(|output| output.exists()).cloned().collect(),
+            stderr,
+            code,
+        })
+    }
+
+    fn read(cache_dir: &Path) -> Option<Self> {
+        serde_json::from_str(&fs::read_to_string(cache_dir.join("entry.json")).ok()?).ok()
+    }
+
+    fn write(&self, cache_dir: &Path) -> io::Result<()> {
+        for output in &self.outputs {
+            if let Some(file_name) = output.file_name() {
+                fs::copy(output, cache_dir.join(file_name))?;
+            }
+        }
+        fs::write(cache_dir.join("entry.json"), serde_json::to_string(self)?)
+    }
+
+    /// Checks that the files and environment variables read by the compiler didn't change.
+    fn is_fresh(&self) -> bool {
+        self.files.iter().all(FileStamp::is_fresh)
+            && self
+                .env
+                .iter()
+                .all(|(var, value)| env::var(var).ok().as_ref() == value.as_ref())
+    }
+
+    /// Prints the cached output and restores the written files. The value of `CLIPPY_ARGS` in the
+    /// dep-info file is updated, Cargo uses it to decide when to run Clippy again.
+    fn replay(&self, cache_dir: &Path, dep_info: &Path) -> io::Result<()> {
+        for output in &self.outputs {
+            let Some(file_name) = output.file_name() else {
+                continue;
+            };
+            if output == dep_info {
+                let contents = fs::read_to_string(cache_dir.join(file_name))?;
+                fs::write(output, DepInfo::with_clippy_args(&contents))?;
+            } else {
+                fs::copy(cache_dir.join(file_name), output)?;
+            }
+        }
+        eprint!("{}", self.stderr);
+        Ok(())
+    }
+}
+
+/// The size and modification time of a file, like Cargo's fingerprints of the source files.
+#[derive(Serialize, Deserialize)]
+struct FileStamp {
+    path: PathBuf,
+    len: u64,
+    modified: SystemTime,
+}
+
+impl FileStamp {
+    fn new(path: &Path) -> Option<Self> {
+        let metadata = fs::metadata(path).ok()?;
+        Some(Self {
+            path: path.to_path_buf(),
+            len: metadata.len(),
+            modified: metadata.modified().ok()?,
+        })
+    }
+
+    fn is_fresh(&self) -> bool {
+        fs::metadata(&self.path).map_or(false, |metadata| {
+            metadata.len() == self.len && metadata.modified().ok() == Some(self.modified)
+        })
+    }
+}
+
+/// The contents of a dep-info file written by the compiler.
+#[derive(Debug, Default, PartialEq)]
+struct DepInfo {
+    /// The files read by the compiler, listed as rules without dependencies.
+    files: Vec<String>,
+    /// The environment variables read by the compiler, except for `CLIPPY_ARGS`.
+    env: Vec<(String, Option<String>)>,
+}
+
+impl DepInfo {
+    fn parse(contents: &str) -> Self {
+        let mut dep_info = Self::default();
+        for line in contents.lines() {
+            if let Some(env_dep) = line.strip_prefix("# env-dep:") {
+                let (var, value) = match env_dep.split_once('=') {
+                    Some((var, value)) => (var, Some(unescape_env(value))),
+                    None => (env_dep, None),
+                };
+                if var != "CLIPPY_ARGS" {
+                    dep_info.env.push((var.to_string(), value));
+                }
+            } else if let Some(target) = line.strip_suffix(':') {
+                dep_info.files.push(target.replace("\\ ", " "));
+            }
+        }
+        dep_info
+    }
+
+    /// Replaces the value of `CLIPPY_ARGS` with the current one.
+    fn with_clippy_args(contents: &str) -> String {
+        let clippy_args = env::var("CLIPPY_ARGS").ok();
+        contents
+            .lines()
+            .map(
+                |line| match (line.strip_prefix("# env-dep:CLIPPY_ARGS"), &clippy_args) {
+                    (Some(_), Some(clippy_args)) => format!("# env-dep:CLIPPY_ARGS={}\n", escape_env(clippy_args)),
+                    (Some(_), None) => "# env-dep:CLIPPY_ARGS\n".to_string(),
+                    (None, _) => format!("{line}\n"),
+                },
+            )
+            .collect()
+    }
+}
+
+/// Reverts the escaping of newlines and backslashes in the values of environment variables.
+fn unescape_env(value: &str) -> String {
+    let mut unescaped = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        match (c, chars.clone().next()) {
+            ('\\', Some(escaped @ ('n' | 'r' | '\\'))) => {
+                chars.next();
+                unescaped.push(match escaped {
+                    'n' => '\n',
+                    'r' => '\r',
+                    _ => '\\',
+                });
+            },
+            _ => unescaped.push(c),
+        }
+    }
+    unescaped
+}
+
+fn escape_env(value: &str) -> String {
+    value.replace('\\', r"\\").replace('\n', r"\n").replace('\r', r"\r")
+}
+
+#[test]
+fn test_split_lint_flags() {
+    let args: Vec<String> = [
+        "clippy-driver",
+        "src/lib.rs",
+        "-Dwarnings",
+        "--cap-lints",
+        "warn",
+        "-W",
+        "clippy::needless-return",
+        "--allow=clippy::style",
+        "--force-warn",
+        "clippy::unwrap_used",
+        "--deny",
+        "clippy::pedantic",
+    ]
+    .iter()
+    .map(ToString::to_string)
+    .collect();
+    let (other_args, lint_flags) = split_lint_flags(&args);
+    assert_eq!(other_args, ["src/lib.rs", "--cap-lints", "warn"]);
+    assert_eq!(
+        lint_flags,
+        [
+            ("deny", "warnings"),
+            ("warn", "clippy::needless_return"),
+            ("allow", "clippy::style"),
+            ("force-warn", "clippy::unwrap_used"),
+            ("deny", "clippy::pedantic"),
+        ]
+        .map(|(level, lint)| (level.to_string(), lint.to_string()))
+    );
+}
+
+#[test]
+fn test_dep_info() {
+    let contents = "\
+/target/debug/deps/foo-123.d: src/lib.rs src/my\\ mod.rs
+
+/target/debug/deps/libfoo-123.rmeta: src/lib.rs src/my\\ mod.rs
+
+src/lib.rs:
+src/my\\ mod.rs:
+
+# env-dep:CARGO_PKG_NAME=foo
+# env-dep:CLIPPY_ARGS=-W__CLIPPY_HACKERY__clippy::pedantic__CLIPPY_HACKERY__
+# env-dep:CLIPPY_PROFILE
+# env-dep:MULTILINE=a\\nb\\\\c
+";
+    assert_eq!(
+        DepInfo::parse(contents),
+        DepInfo {
+            files: vec!["src/lib.rs".to_string(), "src/my mod.rs".to_string()],
+            env: vec![
+                ("CARGO_PKG_NAME".to_string(), Some("foo".to_string())),
+                ("CLIPPY_PROFILE".to_string(), None),
+                ("MULTILINE".to_string(), Some("a\nb\\c".to_string())),
+            ],
+        }
+    );
+    assert_eq!(escape_env("a\nb\\c"), "a\\nb\\\\c");
+}
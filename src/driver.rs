@@ -24,10 +24,13 @@ use std::borrow::Cow;
 use std::env;
 use std::ops::Deref;
 use std::panic;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::process::exit;
 use std::sync::LazyLock;
 
+mod cache;
+mod lint_library;
+
 /// If a command-line option matches `find_arg`, then apply the predicate `pred` on its value. If
 /// true, then return it. The parameter is assumed to be either `--arg=value` or `--arg value`.
 fn arg_value<'a, T: Deref<Target = str>>(
@@ -72,6 +75,14 @@ fn track_clippy_args(parse_sess: &mut ParseSess, args_env_var: &Option<String>)
     ));
 }
 
+/// Track `CLIPPY_PROFILE` so that cargo re-runs clippy to write the profile when it's set
+fn track_profile(parse_sess: &mut ParseSess, profile: &Option<String>) {
+    parse_sess
+        .env_depinfo
+        .get_mut()
+        .insert((Symbol::intern("CLIPPY_PROFILE"), profile.as_deref().map(Symbol::intern)));
+}
+
 /// Track files that may be accessed at runtime in `file_depinfo` so that cargo will re-run clippy
 /// when any of them are modified
 fn track_files(parse_sess: &mut ParseSess, conf_path_string: Option<String>) {
@@ -123,6 +134,10 @@ impl rustc_driver::Callbacks for RustcCallbacks {
 
 struct ClippyCallbacks {
     clippy_args_var: Option<String>,
+    unsafe_report: Option<String>,
+    unused_pub: Option<String>,
+    profile: Option<String>,
+    cache_record: Option<PathBuf>,
 }
 
 impl rustc_driver::Callbacks for ClippyCallbacks {
@@ -138,8 +153,15 @@ impl rustc_driver::Callbacks for ClippyCallbacks {
 
         let previous = config.register_lints.take();
         let clippy_args_var = self.clippy_args_var.take();
+        let unsafe_report = self.unsafe_report.take();
+        let unused_pub = self.unused_pub.take();
+        let profile = self.profile.take();
+        let profiling = profile.is_some();
+        let cache_record = self.cache_record.take();
+        let out_dir = config.output_dir.clone();
         config.parse_sess_created = Some(Box::new(move |parse_sess| {
             track_clippy_args(parse_sess, &clippy_args_var);
+            track_profile(parse_sess, &profile);
             track_files(parse_sess, conf_path_string);
         }));
         config.register_lints = Some(Box::new(move |sess, lint_store| {
@@ -153,6 +175,25 @@ impl rustc_driver::Callbacks for ClippyCallbacks {
             clippy_lints::register_plugins(lint_store, sess, &conf);
             clippy_lints::register_pre_expansion_lints(lint_store, sess, &conf);
             clippy_lints::register_renamed(lint_store);
+            if let Some(format) = &unsafe_report {
+                clippy_lints::register_unsafe_report(lint_store, sess, format);
+            }
+            let conf_dir = conf_path
+                .as_ref()
+                .ok()
+                .and_then(Option::as_deref)
+                .and_then(Path::parent);
+            lint_library::register_lint_libraries(lint_store, sess, &conf, conf_dir);
+            clippy_lints::register_user_groups(lint_store, sess, &conf);
+            if let Some(phase) = &unused_pub {
+                clippy_lints::register_unused_pub(lint_store, sess, &conf, phase);
+            }
+            if let Some(path) = &cache_record {
+                clippy_lints::register_cache_record(lint_store, path.clone());
+            }
+            if profiling {
+                clippy_lints::register_profiling(lint_store, sess, out_dir.as_deref());
+            }
         }));
 
         // FIXME: #4825; This is required, because Clippy lints that are based on MIR have to be
@@ -304,6 +345,8 @@ pub fn main() {
         pass_sysroot_env_if_given(&mut args, sys_root_env);
 
         let mut no_deps = false;
+        let mut unsafe_report = None;
+        let mut unused_pub = None;
         let clippy_args_var = env::var("CLIPPY_ARGS").ok();
         let clippy_args = clippy_args_var
             .as_deref()
@@ -315,7 +358,17 @@ pub fn main() {
                     no_deps = true;
                     None
                 },
-                _ => Some(s.to_string()),
+                _ => {
+                    if let Some(format) = s.strip_prefix("--unsafe-report=") {
+                        unsafe_report = Some(format.to_string());
+                        None
+                    } else if let Some(phase) = s.strip_prefix("--unused-pub=") {
+                        unused_pub = Some(phase.to_string());
+                        None
+                    } else {
+                        Some(s.to_string())
+                    }
+                },
             })
             .chain(vec!["--cfg".into(), r#"feature="cargo-clippy""#.into()])
             .collect::<Vec<String>>();
@@ -332,7 +385,27 @@ pub fn main() {
         let clippy_enabled = !cap_lints_allow && (!no_deps || in_primary_package);
         if clippy_enabled {
             args.extend(clippy_args);
-            rustc_driver::RunCompiler::new(&args, &mut ClippyCallbacks { clippy_args_var }).run()
+            let profile = env::var("CLIPPY_PROFILE").ok().filter(|profile| !profile.is_empty());
+            // The reports and the profile are written by the lints, they can't be replayed
+            if unsafe_report.is_none()
+                && unused_pub.is_none()
+                && profile.is_none()
+                && cache::enabled()
+                && let Some(code) = cache::run(&args)
+            {
+                exit(code);
+            }
+            rustc_driver::RunCompiler::new(
+                &args,
+                &mut ClippyCallbacks {
+                    clippy_args_var,
+                    unsafe_report,
+                    unused_pub,
+                    profile,
+                    cache_record: cache::record_path(),
+                },
+            )
+            .run()
         } else {
             rustc_driver::RunCompiler::new(&args, &mut RustcCallbacks { clippy_args_var }).run()
         }
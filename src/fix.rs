@@ -0,0 +1,456 @@
+//! `cargo clippy --fix-only` and `cargo clippy --fix --interactive`.
+//!
+//! `cargo fix` applies all the machine applicable suggestions at once. To only apply the
+//! suggestions of some lints, or to review each of them, the suggestions are instead collected
+//! from the JSON messages of `cargo check` and applied here. Like `cargo fix`, this is repeated
+//! until no suggestions are left, as overlapping suggestions can only be applied one at a time.
+
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::io::{self, BufRead, Write as _};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use serde::Deserialize;
+
+use crate::{manifest_path_args, ClippyCmd};
+
+/// `cargo fix` gives up after the same number of rounds.
+const MAX_ROUNDS: usize = 4;
+
+#[derive(Deserialize)]
+struct CargoMessage {
+    reason: String,
+    message: Option<Diagnostic>,
+}
+
+#[derive(Deserialize)]
+struct Diagnostic {
+    message: String,
+    code: Option<DiagnosticCode>,
+    level: String,
+    spans: Vec<DiagnosticSpan>,
+    children: Vec<Diagnostic>,
+    rendered: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct DiagnosticCode {
+    code: String,
+}
+
+#[derive(Deserialize)]
+struct DiagnosticSpan {
+    file_name: String,
+    byte_start: usize,
+    byte_end: usize,
+    line_start: usize,
+    column_start: usize,
+    suggested_replacement: Option<String>,
+    suggestion_applicability: Option<String>,
+}
+
+/// The machine applicable suggestions of one diagnostic.
+#[derive(Clone, PartialEq, Eq, Hash)]
+struct Fix {
+    lint: String,
+    message: String,
+    file: String,
+    line: usize,
+    column: usize,
+    /// Sorted by position.
+    replacements: Vec<Replacement>,
+}
+
+#[derive(Clone, PartialEq, Eq, Hash)]
+struct Replacement {
+    start: usize,
+    end: usize,
+    text: String,
+}
+
+impl Replacement {
+    fn overlaps(&self, other: &Self) -> bool {
+        self.start == other.start || (self.start < other.end && other.start < self.end)
+    }
+}
+
+enum Answer {
+    Yes,
+    No,
+    All,
+    Quit,
+}
+
+/// Runs `cargo check` until the selected lints have no machine applicable suggestions left, and
+/// applies them. `cmd.fix_only` selects the lints, all of them if it's empty.
+pub(crate) fn fix(mut cmd: ClippyCmd) -> Result<(), i32> {
+    // The suggestions are read from the JSON messages.
+    if cmd
+        .args
+        .iter()
+        .any(|arg| arg == "--message-format" || arg.starts_with("--message-format="))
+    {
+        eprintln!("error: `--message-format` can't be used with `--fix-only` or `--interactive`");
+        return Err(1);
+    }
+    let allow_no_vcs = take_flag(&mut cmd.args, "--allow-no-vcs");
+    let allow_dirty = take_flag(&mut cmd.args, "--allow-dirty");
+    let allow_staged = take_flag(&mut cmd.args, "--allow-staged");
+    let root = workspace_root(&cmd.args)?;
+    if !allow_no_vcs {
+        if let Err(e) = check_version_control(&root, allow_dirty, allow_staged) {
+            eprintln!("error: {e}");
+            return Err(101);
+        }
+    }
+
+    let selected: HashSet<String> = cmd.fix_only.iter().map(|lint| format!("clippy::{lint}")).collect();
+    // Lints denied with `-D warnings` or `#![deny(..)]` are capped to warnings, so that they don't
+    // fail the build: only errors of the compiler keep the fixes from being applied.
+    let enable: Vec<String> = selected
+        .iter()
+        .map(|lint| format!("-W{lint}"))
+        .chain(["--cap-lints=warn".to_string()])
+        .collect();
+    let enable: Vec<&str> = enable.iter().map(String::as_str).collect();
+    cmd.cargo_subcommand = "check";
+    cmd.args.push("--message-format=json".into());
+
+    let mut accept_all = HashSet::new();
+    let mut rejected = HashSet::new();
+    for _ in 0..MAX_ROUNDS {
+        let fixes = collect_fixes(cmd.std_cmd(&enable))?
+            .into_iter()
+            .filter(|fix| selected.is_empty() || selected.contains(&fix.lint));
+
+        // The fixes are relative to the files at the start of the round.
+        let mut sources = BTreeMap::new();
+        let mut accepted: BTreeMap<String, Vec<Fix>> = BTreeMap::new();
+        let mut quit = false;
+        for fix in fixes {
+            if !sources.contains_key(&fix.file) {
+                match fs::read_to_string(root.join(&fix.file)) {
+                    Ok(source) => {
+                        sources.insert(fix.file.clone(), source);
+                    },
+                    Err(e) => {
+                        eprintln!("warning: could not read `{}`: {e}", fix.file);
+                        continue;
+                    },
+                }
+            }
+            let source = &sources[&fix.file];
+            let in_file = accepted.entry(fix.file.clone()).or_default();
+            // Overlapping fixes are left for the next round.
+            let overlaps = in_file
+                .iter()
+                .flat_map(|other| &other.replacements)
+                .any(|other| fix.replacements.iter().any(|r| r.overlaps(other)));
+            let key = fix_key(&fix, source);
+            if overlaps || fix.replacements.last().map_or(true, |r| r.end > source.len()) || rejected.contains(&key) {
+                continue;
+            }
+            if cmd.interactive && !accept_all.contains(&fix.lint) {
+                match ask(&fix, source) {
+                    Answer::Yes => {},
+                    Answer::No => {
+                        rejected.insert(key);
+                        continue;
+                    },
+                    Answer::All => {
+                        accept_all.insert(fix.lint.clone());
+                    },
+                    Answer::Quit => {
+                        quit = true;
+                        break;
+                    },
+                }
+            }
+            in_file.push(fix);
+        }
+
+        accepted.retain(|_, fixes| !fixes.is_empty());
+        if accepted.is_empty() {
+            break;
+        }
+        for (file, fixes) in accepted {
+            let source = apply(&sources[&file], fixes.iter().flat_map(|fix| &fix.replacements));
+            if let Err(e) = fs::write(root.join(&file), source) {
+                eprintln!("error: could not write `{file}`: {e}");
+                return Err(101);
+            }
+            let s = if fixes.len() == 1 { "" } else { "es" };
+            eprintln!("{:>12} {file} ({} fix{s})", "Fixed", fixes.len());
+        }
+        if quit {
+            break;
+        }
+    }
+
+    Ok(())
+}
+
+fn take_flag(args: &mut Vec<String>, flag: &str) -> bool {
+    let len = args.len();
+    args.retain(|arg| arg != flag);
+    args.len() != len
+}
+
+/// Refuses to overwrite uncommitted changes, the same way `cargo fix` does.
+fn check_version_control(root: &Path, allow_dirty: bool, allow_staged: bool) -> Result<(), String> {
+    let output = Command::new("git")
+        .args(["status", "--porcelain"])
+        .current_dir(root)
+        .stderr(Stdio::null())
+        .output();
+    let status = match output {
+        Ok(output) if output.status.success() => String::from_utf8_lossy(&output.stdout).into_owned(),
+        _ => {
+            return Err(
+                "no VCS found for this package and `cargo clippy` can potentially perform destructive \
+                        changes; if you'd like to suppress this error pass `--allow-no-vcs`"
+                    .into(),
+            );
+        },
+    };
+    if allow_dirty {
+        return Ok(());
+    }
+
+    let mut dirty = Vec::new();
+    for line in status.lines() {
+        let (Some(index), Some(worktree), Some(path)) = (line.get(..1), line.get(1..2), line.get(3..)) else {
+            continue;
+        };
+        if worktree != " " || index == "?" {
+            dirty.push(format!("  * {path} (dirty)"));
+        } else if !allow_staged {
+            dirty.push(format!("  * {path} (staged)"));
+        }
+    }
+    if dirty.is_empty() {
+        Ok(())
+    } else {
+        Err(format!(
+            "the working directory of this package has uncommitted changes, and `cargo clippy` can \
+             potentially perform destructive changes; if you'd like to suppress this error pass \
+             `--allow-dirty`, `--allow-staged`, or commit the changes to these files:\n\n{}\n",
+            dirty.join("\n")
+        ))
+    }
+}
+
+/// The paths of the diagnostics are relative to the workspace root.
+fn workspace_root(args: &[String]) -> Result<PathBuf, i32> {
+    let mut cmd = Command::new("cargo");
+    cmd.args(["locate-project", "--workspace", "--message-format", "plain"])
+        .args(manifest_path_args(args));
+    let output = cmd.stderr(Stdio::inherit()).output().expect("could not run cargo");
+    if !output.status.success() {
+        return Err(output.status.code().unwrap_or(-1));
+    }
+    let manifest = PathBuf::from(String::from_utf8_lossy(&output.stdout).trim());
+    Ok(manifest.parent().map_or_else(PathBuf::new, Path::to_path_buf))
+}
+
+/// Runs `cargo check`, and collects the machine applicable suggestions of its diagnostics. The
+/// fixes aren't applied to code that doesn't compile.
+fn collect_fixes(mut cmd: Command) -> Result<Vec<Fix>, i32> {
+    let output = cmd
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit())
+        .output()
+        .expect("could not run cargo");
+
+    let mut fixes = Vec::new();
+    let mut seen = HashSet::new();
+    let mut errors = Vec::new();
+    for line in output.stdout.split(|&b| b == b'\n') {
+        let Ok(CargoMessage {
+            reason,
+            message: Some(diag),
+        }) = serde_json::from_slice(line) else {
+            continue;
+        };
+        if reason != "compiler-message" {
+            continue;
+        }
+        if diag.level == "error" {
+            errors.extend(diag.rendered);
+        } else if let Some(fix) = Fix::from_diagnostic(&diag) {
+            // Diagnostics are repeated for each target the file is a part of.
+            if seen.insert(fix.clone()) {
+                fixes.push(fix);
+            }
+        }
+    }
+
+    if output.status.success() {
+        Ok(fixes)
+    } else {
+        for error in errors {
+            eprint!("{error}");
+        }
+        eprintln!("error: no fixes were applied, as the code doesn't compile");
+        Err(output.status.code().unwrap_or(-1))
+    }
+}
+
+impl Fix {
+    fn from_diagnostic(diag: &Diagnostic) -> Option<Self> {
+        let lint = diag.code.as_ref()?.code.clone();
+        let primary = diag.spans.first()?;
+        let mut replacements = Vec::new();
+        for child in &diag.children {
+            let spans: Vec<_> = child
+                .spans
+                .iter()
+                .filter(|span| span.suggested_replacement.is_some())
+                .collect();
+            // Like `cargo fix`, a suggestion is only applied if all of its parts are.
+            if !spans.is_empty()
+                && spans.iter().all(|span| {
+                    span.file_name == primary.file_name
+                        && span.suggestion_applicability.as_deref() == Some("MachineApplicable")
+                })
+            {
+                replacements.extend(spans.into_iter().map(|span| Replacement {
+                    start: span.byte_start,
+                    end: span.byte_end,
+                    text: span.suggested_replacement.clone().unwrap_or_default(),
+                }));
+            }
+        }
+        if replacements.is_empty() || Path::new(&primary.file_name).is_absolute() {
+            return None;
+        }
+        replacements.sort_by_key(|r| (r.start, r.end));
+        Some(Self {
+            lint,
+            message: diag.message.clone(),
+            file: primary.file_name.clone(),
+            line: primary.line_start,
+            column: primary.column_start,
+            replacements,
+        })
+    }
+}
+
+/// Identifies a fix across rounds, in which its position may change.
+fn fix_key(fix: &Fix, source: &str) -> (String, String, Vec<(String, String)>) {
+    let replaced = fix
+        .replacements
+        .iter()
+        .map(|r| {
+            (
+                source.get(r.start..r.end).unwrap_or_default().to_owned(),
+                r.text.clone(),
+            )
+        })
+        .collect();
+    (fix.lint.clone(), fix.file.clone(), replaced)
+}
+
+fn apply<'a>(source: &str, replacements: impl IntoIterator<Item = &'a Replacement>) -> String {
+    let mut replacements: Vec<_> = replacements.into_iter().collect();
+    replacements.sort_by_key(|r| (r.start, r.end));
+    let mut res = String::with_capacity(source.len());
+    let mut pos = 0;
+    for r in replacements {
+        res.push_str(&source[pos..r.start]);
+        res.push_str(&r.text);
+        pos = r.end;
+    }
+    res.push_str(&source[pos..]);
+    res
+}
+
+/// Renders the lines changed by the replacements as a diff.
+fn render_diff(source: &str, replacements: &[Replacement]) -> String {
+    let (Some(first), Some(last)) = (replacements.first(), replacements.iter().map(|r| r.end).max()) else {
+        return String::new();
+    };
+    let lo = source[..first.start].rfind('\n').map_or(0, |pos| pos + 1);
+    let hi = source[last..].find('\n').map_or(source.len(), |pos| last + pos);
+    let shifted: Vec<_> = replacements
+        .iter()
+        .map(|r| Replacement {
+            start: r.start - lo,
+            end: r.end - lo,
+            text: r.text.clone(),
+        })
+        .collect();
+    let old = &source[lo..hi];
+    let new = apply(old, &shifted);
+
+    let mut diff = String::new();
+    for line in old.lines() {
+        diff.push_str(&format!("-{line}\n"));
+    }
+    for line in new.lines() {
+        diff.push_str(&format!("+{line}\n"));
+    }
+    diff
+}
+
+fn ask(fix: &Fix, source: &str) -> Answer {
+    eprintln!(
+        "\n{}:{}:{}: {} ({})",
+        fix.file, fix.line, fix.column, fix.message, fix.lint
+    );
+    eprint!("{}", render_diff(source, &fix.replacements));
+    loop {
+        eprint!("Apply this fix? [y]es, [n]o, [a]ll `{}` fixes, [q]uit: ", fix.lint);
+        let _ = io::stderr().flush();
+        let mut answer = String::new();
+        match io::stdin().lock().read_line(&mut answer) {
+            Ok(0) | Err(_) => {
+                eprintln!();
+                return Answer::Quit;
+            },
+            Ok(_) => {},
+        }
+        match answer.trim() {
+            "y" | "yes" => return Answer::Yes,
+            "n" | "no" => return Answer::No,
+            "a" | "all" => return Answer::All,
+            "q" | "quit" => return Answer::Quit,
+            _ => {},
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{apply, render_diff, Replacement};
+
+    fn replacement(start: usize, end: usize, text: &str) -> Replacement {
+        Replacement {
+            start,
+            end,
+            text: text.into(),
+        }
+    }
+
+    #[test]
+    fn apply_replacements() {
+        let source = "fn f() -> u32 {\n    return 1;\n}\n";
+        let replacements = [replacement(20, 27, ""), replacement(28, 29, "")];
+        assert_eq!(apply(source, &replacements), "fn f() -> u32 {\n    1\n}\n");
+    }
+
+    #[test]
+    fn diff_of_changed_lines() {
+        let source = "fn f() -> u32 {\n    return 1;\n}\n";
+        let replacements = [replacement(20, 27, ""), replacement(28, 29, "")];
+        assert_eq!(render_diff(source, &replacements), "-    return 1;\n+    1\n");
+    }
+
+    #[test]
+    fn overlapping_replacements() {
+        assert!(replacement(0, 4, "").overlaps(&replacement(2, 6, "")));
+        assert!(replacement(2, 2, "a").overlaps(&replacement(2, 2, "b")));
+        assert!(!replacement(0, 2, "").overlaps(&replacement(2, 4, "")));
+    }
+}
@@ -0,0 +1,95 @@
+//! Loads the lint libraries from the `lint-libraries` configuration, see `clippy_lint_library`.
+//!
+//! A library is only loaded if it was built for the same [`LINT_LIBRARY_VERSION`] as the driver.
+//! The version identifies the commits of Clippy and of the compiler, so if either wasn't known
+//! when building the driver no library is loaded.
+
+use clippy_lint_library::{
+    LintStore, RegisterLintsFn, Session, VersionFn, LINT_LIBRARY_VERSION, REGISTER_SYMBOL, VERSION_SYMBOL,
+};
+use clippy_lints::Conf;
+use libloading::Library;
+use rustc_span::symbol::Symbol;
+
+use std::ffi::CStr;
+use std::path::{Path, PathBuf};
+
+/// Load the lint libraries of `conf` and register their lints. Relative paths are relative to
+/// `conf_dir`, the directory of `clippy.toml`.
+pub fn register_lint_libraries(store: &mut LintStore, sess: &Session, conf: &Conf, conf_dir: Option<&Path>) {
+    if conf.lint_libraries.is_empty() {
+        return;
+    }
+    if LINT_LIBRARY_VERSION.is_empty() {
+        sess.struct_err("this build of Clippy can't load lint libraries")
+            .note("the commit of Clippy or of the compiler wasn't known when building Clippy")
+            .emit();
+        return;
+    }
+
+    for path in &conf.lint_libraries {
+        let path = conf_dir.map_or_else(|| PathBuf::from(path), |dir| dir.join(path));
+        if !path.exists() {
+            sess.err(format!("lint library `{}` does not exist", path.display()));
+            continue;
+        }
+        // Rerun Clippy when the library is rebuilt
+        sess.parse_sess
+            .file_depinfo
+            .borrow_mut()
+            .insert(Symbol::intern(&path.to_string_lossy()));
+
+        // SAFETY: loading a library runs its initializers, lint libraries are trusted like build
+        // scripts and proc macros
+        let library = match unsafe { Library::new(&path) } {
+            Ok(library) => library,
+            Err(error) => {
+                sess.err(format!("could not load lint library `{}`: {error}", path.display()));
+                continue;
+            },
+        };
+        // SAFETY: the version function has this type if it was declared with `declare_lint_library!`,
+        // it uses the C ABI to be callable whatever the compiler the library was built with
+        let Ok(version) = (unsafe { library.get::<VersionFn>(VERSION_SYMBOL) }) else {
+            sess.struct_err(format!("`{}` is not a Clippy lint library", path.display()))
+                .help("lint libraries declare their lints with `clippy_lint_library::declare_lint_library!`")
+                .emit();
+            continue;
+        };
+        // SAFETY: the version is a static C string
+        let version = unsafe { CStr::from_ptr(version()) }.to_string_lossy();
+        if version.is_empty() {
+            sess.struct_err(format!(
+                "lint library `{}` was built without a known version of Clippy",
+                path.display()
+            ))
+            .note("the commit of Clippy or of the compiler wasn't known when building the library")
+            .help("build the library from a git checkout of Clippy")
+            .emit();
+            continue;
+        }
+        if version != LINT_LIBRARY_VERSION {
+            sess.struct_err(format!(
+                "lint library `{}` was built for another version of Clippy",
+                path.display()
+            ))
+            .note(format!("the library was built for Clippy {version}"))
+            .note(format!("this is Clippy {LINT_LIBRARY_VERSION}"))
+            .help("rebuild the library with the same toolchain and `clippy_lint_library` as Clippy")
+            .emit();
+            continue;
+        }
+        // SAFETY: the library was built with the same compiler and Clippy commit, the registration
+        // function has this type if it was declared with `declare_lint_library!`
+        let register = match unsafe { library.get::<RegisterLintsFn>(REGISTER_SYMBOL) } {
+            Ok(register) => *register,
+            Err(error) => {
+                sess.err(format!("could not load lint library `{}`: {error}", path.display()));
+                continue;
+            },
+        };
+        register(store, sess);
+        // The registered lint passes point into the library, so it must never be unloaded
+        std::mem::forget(library);
+    }
+}
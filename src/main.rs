@@ -4,7 +4,11 @@
 
 use std::env;
 use std::path::PathBuf;
-use std::process::{self, Command};
+use std::process::{self, Command, Stdio};
+
+use serde::Deserialize;
+
+mod fix;
 
 const CARGO_CLIPPY_HELP: &str = r#"Checks a package to catch common mistakes and improve your Rust code.
 
@@ -14,6 +18,15 @@ Usage:
 Common options:
     --no-deps                Run Clippy only on the given crate, without linting the dependencies
     --fix                    Automatically apply lint suggestions. This flag implies `--no-deps`
+    --fix-only LINTS         Only apply the suggestions of the given comma separated lints, e.g.
+                             `needless_return,collapsible_if`. This flag implies `--fix`
+    --interactive            With `--fix` or `--fix-only`, show each suggestion as a diff and ask
+                             whether to apply it
+    --unsafe-report[=FORMAT] Write an inventory of the `unsafe` code of each checked crate to
+                             `target/<profile>/clippy-unsafe-report`. FORMAT is `json` (default)
+                             or `markdown`
+    --unused-pub             Check the workspace twice to find exported items that no other crate
+                             of the workspace uses (`clippy::unused_pub_items`)
     -h, --help               Print this message
     -V, --version            Print version info and exit
     --explain LINT           Print the documentation for a given lint
@@ -73,27 +86,67 @@ struct ClippyCmd {
     cargo_subcommand: &'static str,
     args: Vec<String>,
     clippy_args: Vec<String>,
+    unused_pub: bool,
+    /// The lints whose suggestions `--fix-only` applies, without the `clippy::` prefix.
+    fix_only: Vec<String>,
+    /// `--fix-only` was followed by another flag instead of the lints.
+    fix_only_without_lints: bool,
+    interactive: bool,
 }
 
 impl ClippyCmd {
-    fn new<I>(mut old_args: I) -> Self
+    fn new<I>(old_args: I) -> Self
     where
         I: Iterator<Item = String>,
     {
+        let mut old_args = old_args.peekable();
         let mut cargo_subcommand = "check";
         let mut args = vec![];
         let mut clippy_args: Vec<String> = vec![];
+        let mut unused_pub = false;
+        let mut fix_only = vec![];
+        let mut fix_only_without_lints = false;
+        let mut interactive = false;
 
-        for arg in old_args.by_ref() {
+        while let Some(arg) = old_args.next() {
             match arg.as_str() {
                 "--fix" => {
                     cargo_subcommand = "fix";
                     continue;
                 },
+                "--fix-only" => {
+                    cargo_subcommand = "fix";
+                    match old_args.next_if(|arg| !arg.starts_with('-')) {
+                        Some(lints) => fix_only.extend(parse_lints(&lints)),
+                        None => fix_only_without_lints = true,
+                    }
+                    continue;
+                },
+                _ if arg.starts_with("--fix-only=") => {
+                    cargo_subcommand = "fix";
+                    fix_only.extend(parse_lints(&arg["--fix-only=".len()..]));
+                    continue;
+                },
+                "--interactive" => {
+                    interactive = true;
+                    continue;
+                },
                 "--no-deps" => {
                     clippy_args.push("--no-deps".into());
                     continue;
                 },
+                "--unsafe-report" => {
+                    clippy_args.push("--unsafe-report=json".into());
+                    continue;
+                },
+                _ if arg.starts_with("--unsafe-report=") => {
+                    clippy_args.push(arg);
+                    continue;
+                },
+                "--unused-pub" => {
+                    unused_pub = true;
+                    continue;
+                },
                 "--" => break,
                 _ => {},
             }
@@ -110,6 +163,10 @@ impl ClippyCmd {
             cargo_subcommand,
             args,
             clippy_args,
+            unused_pub,
+            fix_only,
+            fix_only_without_lints,
+            interactive,
         }
     }
 
@@ -125,11 +182,12 @@ impl ClippyCmd {
         path
     }
 
-    fn into_std_cmd(self) -> Command {
+    fn std_cmd(&self, extra_clippy_args: &[&str]) -> Command {
         let mut cmd = Command::new("cargo");
-        let clippy_args: String = self
-            .clippy_args
+        let clippy_args: String = extra_clippy_args
             .iter()
+            .copied()
+            .chain(self.clippy_args.iter().map(String::as_str))
             .map(|arg| format!("{arg}__CLIPPY_HACKERY__"))
             .collect();
 
@@ -152,8 +210,96 @@ where
 {
     let cmd = ClippyCmd::new(old_args);
 
-    let mut cmd = cmd.into_std_cmd();
+    if cmd.fix_only_without_lints {
+        eprintln!("error: `--fix-only` expects a comma separated list of lints");
+        return Err(1);
+    }
+    if let Some(lint) = cmd.fix_only.iter().find(|lint| !clippy_lints::is_lint(lint)) {
+        eprintln!("error: unknown lint `clippy::{lint}`");
+        return Err(1);
+    }
+    if cmd.interactive && cmd.cargo_subcommand != "fix" {
+        eprintln!("error: `--interactive` can only be used with `--fix` or `--fix-only`");
+        return Err(1);
+    }
+
+    if cmd.cargo_subcommand == "fix" && (cmd.interactive || !cmd.fix_only.is_empty()) {
+        fix::fix(cmd)
+    } else if cmd.unused_pub {
+        // The first run records which items each crate uses, without reporting any lints that the
+        // second run would report again. The second run then lints the unused items.
+        run(cmd.std_cmd(&["--unused-pub=record", "--cap-lints=allow"]))?;
+        let check = format!("--unused-pub=check:{}", workspace_crates(&cmd.args)?.join(","));
+        run(cmd.std_cmd(&[&check, "-Wclippy::unused_pub_items"]))
+    } else {
+        run(cmd.std_cmd(&[]))
+    }
+}
+
+/// The names of the crates of the workspace members, from `cargo metadata`.
+fn workspace_crates(args: &[String]) -> Result<Vec<String>, i32> {
+    #[derive(Deserialize)]
+    struct Metadata {
+        packages: Vec<Package>,
+    }
+    #[derive(Deserialize)]
+    struct Package {
+        targets: Vec<Target>,
+    }
+    #[derive(Deserialize)]
+    struct Target {
+        name: String,
+    }
 
+    let output = Command::new("cargo")
+        .args(["metadata", "--no-deps", "--format-version", "1"])
+        .args(manifest_path_args(args))
+        .stderr(Stdio::inherit())
+        .output()
+        .expect("could not run cargo");
+    if !output.status.success() {
+        return Err(output.status.code().unwrap_or(-1));
+    }
+    let Ok(metadata) = serde_json::from_slice::<Metadata>(&output.stdout) else {
+        eprintln!("error: could not parse the output of `cargo metadata`");
+        return Err(1);
+    };
+    // Without dependencies, the metadata only contains the workspace members
+    Ok(metadata
+        .packages
+        .into_iter()
+        .flat_map(|package| package.targets)
+        .map(|target| target.name.replace('-', "_"))
+        .collect())
+}
+
+/// The `--manifest-path` argument of `args`, if any, to pass it on to other cargo commands.
+fn manifest_path_args(args: &[String]) -> &[String] {
+    if let Some(pos) = args.iter().position(|arg| arg == "--manifest-path") {
+        &args[pos..args.len().min(pos + 2)]
+    } else if let Some(pos) = args.iter().position(|arg| arg.starts_with("--manifest-path=")) {
+        &args[pos..=pos]
+    } else {
+        &[]
+    }
+}
+
+/// Parses a comma separated list of lints, e.g. `clippy::needless_return,collapsible-if`.
+fn parse_lints(lints: &str) -> Vec<String> {
+    lints
+        .split(',')
+        .map(str::trim)
+        .filter(|lint| !lint.is_empty())
+        .map(|lint| {
+            lint.strip_prefix("clippy::")
+                .unwrap_or(lint)
+                .replace('-', "_")
+                .to_ascii_lowercase()
+        })
+        .collect()
+}
+
+fn run(mut cmd: Command) -> Result<(), i32> {
     let exit_status = cmd
         .spawn()
         .expect("could not run cargo")
@@ -195,6 +341,67 @@ mod tests {
         assert_eq!(cmd.clippy_args.iter().filter(|arg| *arg == "--no-deps").count(), 1);
     }
 
+    #[test]
+    fn unsafe_report() {
+        let args = "cargo clippy --unsafe-report"
+            .split_whitespace()
+            .map(ToString::to_string);
+        let cmd = ClippyCmd::new(args);
+        assert_eq!("check", cmd.cargo_subcommand);
+        assert!(cmd.clippy_args.iter().any(|arg| arg == "--unsafe-report=json"));
+        assert!(!cmd.args.iter().any(|arg| arg.starts_with("--unsafe-report")));
+
+        let args = "cargo clippy --unsafe-report=markdown"
+            .split_whitespace()
+            .map(ToString::to_string);
+        let cmd = ClippyCmd::new(args);
+        assert!(cmd.clippy_args.iter().any(|arg| arg == "--unsafe-report=markdown"));
+    }
+
+    #[test]
+    fn unused_pub() {
+        let args = "cargo clippy --unused-pub -- -Dwarnings"
+            .split_whitespace()
+            .map(ToString::to_string);
+        let cmd = ClippyCmd::new(args);
+        assert!(cmd.unused_pub);
+        assert!(!cmd.args.iter().any(|arg| arg == "--unused-pub"));
+        assert_eq!(cmd.clippy_args, ["-Dwarnings"]);
+    }
+
+    #[test]
+    fn fix_only() {
+        let args = "cargo clippy --fix-only clippy::needless_return,collapsible-if --interactive"
+            .split_whitespace()
+            .map(ToString::to_string);
+        let cmd = ClippyCmd::new(args);
+        assert_eq!("fix", cmd.cargo_subcommand);
+        assert_eq!(cmd.fix_only, ["needless_return", "collapsible_if"]);
+        assert!(cmd.interactive);
+        assert!(
+            !cmd.args
+                .iter()
+                .any(|arg| arg.starts_with("--") || arg.contains("needless_return"))
+        );
+        assert!(cmd.clippy_args.iter().any(|arg| arg == "--no-deps"));
+
+        let args = "cargo clippy --fix-only=needless_return"
+            .split_whitespace()
+            .map(ToString::to_string);
+        let cmd = ClippyCmd::new(args);
+        assert_eq!(cmd.fix_only, ["needless_return"]);
+        assert!(!cmd.interactive);
+
+        // Flags aren't taken as the lints
+        let args = "cargo clippy --fix-only --allow-dirty"
+            .split_whitespace()
+            .map(ToString::to_string);
+        let cmd = ClippyCmd::new(args);
+        assert!(cmd.fix_only.is_empty());
+        assert!(cmd.fix_only_without_lints);
+        assert!(cmd.args.iter().any(|arg| arg == "--allow-dirty"));
+    }
+
     #[test]
     fn check() {
         let args = "cargo clippy".split_whitespace().map(ToString::to_string);
@@ -0,0 +1,88 @@
+#![feature(once_cell)]
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+use test_utils::{CARGO_CLIPPY_PATH, IS_RUSTC_TEST_SUITE};
+
+mod test_utils;
+
+fn run_clippy(cwd: &Path, target_dir: &Path, args: &[&str]) -> Output {
+    let output = Command::new(&*CARGO_CLIPPY_PATH)
+        .current_dir(cwd)
+        .env("CARGO_INCREMENTAL", "0")
+        .env("CARGO_TARGET_DIR", target_dir)
+        .env("CLIPPY_CACHE", "1")
+        .arg("clippy")
+        .args(args)
+        .output()
+        .unwrap();
+    println!("status: {}", output.status);
+    println!("stdout: {}", String::from_utf8_lossy(&output.stdout));
+    println!("stderr: {}", String::from_utf8_lossy(&output.stderr));
+    output
+}
+
+/// The cache entry of the crate, in a directory named after the crate and a hash.
+fn read_entry(target_dir: &Path) -> String {
+    let mut dirs = fs::read_dir(target_dir.join("debug/clippy-cache")).unwrap();
+    let dir = dirs.next().unwrap().unwrap().path();
+    assert!(dirs.next().is_none());
+    fs::read_to_string(dir.join("entry.json")).unwrap()
+}
+
+#[test]
+fn test_cache() {
+    if IS_RUSTC_TEST_SUITE {
+        return;
+    }
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let target_dir = root.join("target").join("cache_test");
+    let fixture = root.join("tests/cache_test");
+
+    // The sources are changed in a copy of the crate, the cache is started from scratch.
+    let cwd = target_dir.join("crate");
+    let _ = fs::remove_dir_all(&target_dir);
+    fs::create_dir_all(cwd.join("src")).unwrap();
+    fs::copy(fixture.join("Cargo.toml"), cwd.join("Cargo.toml")).unwrap();
+    fs::copy(fixture.join("src/lib.rs"), cwd.join("src/lib.rs")).unwrap();
+
+    let output = run_clippy(&cwd, &target_dir, &[]);
+    assert!(output.status.success());
+    assert!(
+        String::from_utf8(output.stderr)
+            .unwrap()
+            .contains("warning: unneeded `return` statement")
+    );
+    let cached = read_entry(&target_dir);
+
+    // `clippy::let_and_return` is checked but not emitted, the output is replayed from the cache
+    let output = run_clippy(&cwd, &target_dir, &["--", "-D", "clippy::let_and_return"]);
+    assert!(output.status.success());
+    assert!(
+        String::from_utf8(output.stderr)
+            .unwrap()
+            .contains("warning: unneeded `return` statement")
+    );
+    assert_eq!(read_entry(&target_dir), cached);
+
+    // The level of an emitted lint changed, Clippy runs again
+    let output = run_clippy(&cwd, &target_dir, &["--", "-D", "clippy::needless_return"]);
+    assert!(!output.status.success());
+    assert!(
+        String::from_utf8(output.stderr)
+            .unwrap()
+            .contains("error: unneeded `return` statement")
+    );
+    assert_ne!(read_entry(&target_dir), cached);
+
+    // A source file changed, Clippy runs again
+    fs::write(cwd.join("src/lib.rs"), "pub fn answer() -> u32 {\n    42\n}\n").unwrap();
+    let output = run_clippy(&cwd, &target_dir, &["--", "-D", "clippy::needless_return"]);
+    assert!(output.status.success());
+    assert!(
+        !String::from_utf8(output.stderr)
+            .unwrap()
+            .contains("unneeded `return` statement")
+    );
+}
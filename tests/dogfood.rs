@@ -23,6 +23,7 @@ fn dogfood_clippy() {
     for package in &[
         "",
         "clippy_dev",
+        "clippy_lint_library",
         "clippy_lints",
         "clippy_utils",
         "lintcheck",
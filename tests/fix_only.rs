@@ -0,0 +1,49 @@
+#![feature(once_cell)]
+
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+use test_utils::{CARGO_CLIPPY_PATH, IS_RUSTC_TEST_SUITE};
+
+mod test_utils;
+
+#[test]
+fn test_fix_only() {
+    if IS_RUSTC_TEST_SUITE {
+        return;
+    }
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let target_dir = root.join("target").join("fix_only_test");
+    let fixture = root.join("tests/fix_only_test");
+
+    // The fixes are applied to a copy of the crate.
+    let cwd = target_dir.join("crate");
+    fs::create_dir_all(cwd.join("src")).unwrap();
+    fs::copy(fixture.join("Cargo.toml"), cwd.join("Cargo.toml")).unwrap();
+    fs::copy(fixture.join("src/lib.rs"), cwd.join("src/lib.rs")).unwrap();
+
+    // Denied lints, selected or not, don't keep the fixes from being applied.
+    let output = Command::new(&*CARGO_CLIPPY_PATH)
+        .current_dir(&cwd)
+        .env("CARGO_INCREMENTAL", "0")
+        .env("CARGO_TARGET_DIR", &target_dir)
+        .args([
+            "clippy",
+            "--fix-only",
+            "needless_return",
+            "--allow-no-vcs",
+            "--",
+            "-D",
+            "warnings",
+        ])
+        .output()
+        .unwrap();
+    println!("status: {}", output.status);
+    println!("stdout: {}", String::from_utf8_lossy(&output.stdout));
+    println!("stderr: {}", String::from_utf8_lossy(&output.stderr));
+    assert!(output.status.success());
+
+    let fixed = fs::read_to_string(cwd.join("src/lib.rs")).unwrap();
+    let expected = fs::read_to_string(fixture.join("expected.rs")).unwrap();
+    assert_eq!(fixed, expected);
+}
@@ -0,0 +1,13 @@
+#![deny(clippy::collapsible_if)]
+
+pub fn answer() -> u32 {
+    return 42;
+}
+
+pub fn both(a: bool, b: bool) {
+    if a {
+        if b {
+            println!("both");
+        }
+    }
+}
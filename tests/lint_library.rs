@@ -0,0 +1,60 @@
+#![feature(once_cell)]
+
+use std::env::consts::{DLL_PREFIX, DLL_SUFFIX};
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+use test_utils::{CARGO_CLIPPY_PATH, IS_RUSTC_TEST_SUITE};
+
+mod test_utils;
+
+#[test]
+fn test_lint_library() {
+    if IS_RUSTC_TEST_SUITE {
+        return;
+    }
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let target_dir = root.join("target").join("lint_library_test");
+    let library_dir = root.join("tests/lint_library_test");
+
+    // The library is built with the same toolchain and `clippy_utils` as Clippy.
+    let output = Command::new("cargo")
+        .current_dir(&library_dir)
+        .env("CARGO_INCREMENTAL", "0")
+        .env("CARGO_TARGET_DIR", &target_dir)
+        .arg("build")
+        .output()
+        .unwrap();
+    println!("status: {}", output.status);
+    println!("stderr: {}", String::from_utf8_lossy(&output.stderr));
+    assert!(output.status.success());
+
+    let library = target_dir
+        .join("debug")
+        .join(format!("{DLL_PREFIX}lint_library_test{DLL_SUFFIX}"));
+    let conf_dir = target_dir.join("conf");
+    fs::create_dir_all(&conf_dir).unwrap();
+    fs::write(
+        conf_dir.join("clippy.toml"),
+        format!("lint-libraries = [{:?}]\n", library.display().to_string()),
+    )
+    .unwrap();
+
+    let output = Command::new(&*CARGO_CLIPPY_PATH)
+        .current_dir(library_dir.join("app"))
+        .env("CARGO_INCREMENTAL", "0")
+        .env("CARGO_TARGET_DIR", &target_dir)
+        .env("CLIPPY_CONF_DIR", &conf_dir)
+        .arg("clippy")
+        .output()
+        .unwrap();
+    println!("status: {}", output.status);
+    println!("stdout: {}", String::from_utf8_lossy(&output.stdout));
+    println!("stderr: {}", String::from_utf8_lossy(&output.stderr));
+    assert!(output.status.success());
+
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("warning: function named `legacy`"));
+    assert!(stderr.contains("#[warn(clippy::legacy_names)]` on by default"));
+    assert_eq!(stderr.matches("function named").count(), 1);
+}
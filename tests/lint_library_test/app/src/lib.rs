@@ -0,0 +1,5 @@
+#![allow(clippy::needless_return, clippy::let_and_return)]
+
+pub fn legacy() {}
+
+pub fn current() {}
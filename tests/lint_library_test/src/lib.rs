@@ -0,0 +1,35 @@
+#![feature(rustc_private)]
+
+extern crate rustc_driver;
+extern crate rustc_hir;
+extern crate rustc_lint;
+extern crate rustc_session;
+
+use clippy_lint_library::{LintStore, Session};
+use clippy_utils::diagnostics::span_lint;
+use rustc_hir::{Item, ItemKind};
+use rustc_lint::{LateContext, LateLintPass};
+use rustc_session::{declare_lint_pass, declare_tool_lint};
+
+declare_tool_lint! {
+    pub clippy::LEGACY_NAMES, Warn, "functions named `legacy`"
+}
+
+// Named like Clippy's pass of `needless_return`, which is allowed in the app, to check that only
+// Clippy's passes are skipped
+declare_lint_pass!(Return => [LEGACY_NAMES]);
+
+impl<'tcx> LateLintPass<'tcx> for Return {
+    fn check_item(&mut self, cx: &LateContext<'tcx>, item: &'tcx Item<'_>) {
+        if matches!(item.kind, ItemKind::Fn(..)) && item.ident.as_str() == "legacy" {
+            span_lint(cx, LEGACY_NAMES, item.ident.span, "function named `legacy`");
+        }
+    }
+}
+
+clippy_lint_library::declare_lint_library!(register_lints);
+
+fn register_lints(store: &mut LintStore, _: &Session) {
+    store.register_lints(&[&LEGACY_NAMES]);
+    store.register_late_pass(|_| Box::new(Return));
+}
@@ -0,0 +1,65 @@
+#![feature(once_cell)]
+
+use serde_json::Value;
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+use test_utils::{CARGO_CLIPPY_PATH, IS_RUSTC_TEST_SUITE};
+
+mod test_utils;
+
+#[test]
+fn test_profile() {
+    if IS_RUSTC_TEST_SUITE {
+        return;
+    }
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let target_dir = root.join("target").join("profile_test");
+    let cwd = root.join("tests/profile_test");
+    let report = target_dir.join("debug/clippy-profile/profile_test-lib.json");
+    let _ = fs::remove_file(&report);
+
+    let output = Command::new(&*CARGO_CLIPPY_PATH)
+        .current_dir(&cwd)
+        .env("CARGO_INCREMENTAL", "0")
+        .env("CARGO_TARGET_DIR", &target_dir)
+        .env("CLIPPY_PROFILE", "1")
+        .arg("clippy")
+        .output()
+        .unwrap();
+    println!("status: {}", output.status);
+    println!("stdout: {}", String::from_utf8_lossy(&output.stdout));
+    println!("stderr: {}", String::from_utf8_lossy(&output.stderr));
+    assert!(output.status.success());
+
+    let report: Value = serde_json::from_str(&fs::read_to_string(report).unwrap()).unwrap();
+    let total_ms = report["total_ms"].as_f64().unwrap();
+    let passes = report["passes"].as_array().unwrap();
+    let lints = report["lints"].as_array().unwrap();
+
+    let mut pass_total_ms = 0.0;
+    for pass in passes {
+        assert!(pass["name"].is_string());
+        assert!(["pre-expansion", "early", "late", "late module"].contains(&pass["kind"].as_str().unwrap()));
+        assert!(pass["lints"].is_array());
+        pass_total_ms += pass["time_ms"].as_f64().unwrap();
+    }
+    assert!((total_ms - pass_total_ms).abs() < 1e-6);
+
+    // `needless_return` is emitted by the pass also checking `let_and_return`
+    let needless_return = lints
+        .iter()
+        .find(|lint| lint["name"] == "clippy::needless_return")
+        .unwrap();
+    assert!(needless_return["time_ms"].as_f64().unwrap() <= total_ms);
+    assert_eq!(needless_return["shared"], true);
+    for pass in needless_return["passes"].as_array().unwrap() {
+        let pass = passes.iter().find(|entry| entry["name"] == *pass).unwrap();
+        assert!(
+            pass["lints"]
+                .as_array()
+                .unwrap()
+                .contains(&Value::from("clippy::needless_return"))
+        );
+    }
+}
@@ -0,0 +1,4 @@
+fn main() {
+    pub_lib::used();
+    pub_lib::reexported();
+}
@@ -0,0 +1,9 @@
+mod inner {
+    pub fn reexported() {}
+}
+
+pub use inner::reexported;
+
+pub fn used() {}
+
+pub fn unused() {}
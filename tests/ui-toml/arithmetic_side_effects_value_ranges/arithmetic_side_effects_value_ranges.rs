@@ -0,0 +1,44 @@
+#![warn(clippy::arithmetic_side_effects)]
+
+fn guards(n: u32, i: i32) {
+    if n < 100 {
+        let _ = n + 1;
+    }
+    let _ = n + 1;
+    if i > i32::MIN {
+        let _ = -i;
+    }
+    let _ = -i;
+}
+
+fn loops(len: usize) {
+    for i in 0..len - 1 {
+        let _ = i + 1;
+        let _ = i - 1;
+    }
+    for i in 0..10usize {
+        let _ = 20 - i;
+    }
+}
+
+fn arithmetic(a: u8, b: u8, d: u32) {
+    let _ = u16::from(a) + u16::from(b);
+    let _ = u16::from(a) * u16::from(b);
+    let _ = u32::from(a) * u32::from(b) * 256;
+    let _ = d / (d % 7 + 1);
+    let _ = d / (u32::from(a) + 1);
+    let _ = d / u32::from(a);
+    let _ = d + 1;
+    let shift = d % 32;
+    let _ = d << shift;
+    let _ = d >> (d % 64);
+}
+
+fn pointer_width(a: u32, b: u16) {
+    let _ = usize::from(b) * usize::from(b);
+    // Overflows where `usize` is 32 bits wide
+    let _ = a as usize + 1;
+    let _ = a as usize * usize::from(b);
+}
+
+fn main() {}
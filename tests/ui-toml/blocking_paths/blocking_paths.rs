@@ -0,0 +1,41 @@
+// edition:2018
+#![warn(clippy::blocking_in_async)]
+
+mod db {
+    pub fn query() {}
+
+    pub struct Connection;
+
+    impl Connection {
+        pub fn execute(&self) {}
+    }
+}
+
+struct Legacy;
+
+impl Legacy {
+    fn new() -> Self {
+        Legacy
+    }
+
+    fn load(&self) {}
+}
+
+fn flush() {}
+
+fn save() {
+    flush();
+}
+
+async fn run(conn: &db::Connection) {
+    db::query();
+    conn.execute();
+    let legacy = Legacy::new();
+    legacy.load();
+    flush();
+    save();
+    // Still uses the built-in paths.
+    std::thread::sleep(std::time::Duration::from_secs(1));
+}
+
+fn main() {}
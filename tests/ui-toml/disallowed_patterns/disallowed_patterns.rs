@@ -0,0 +1,35 @@
+#![warn(clippy::disallowed_patterns)]
+#![allow(clippy::iter_count, clippy::needless_borrow, clippy::redundant_closure)]
+
+use std::fs;
+use std::time::Instant;
+
+fn main() {
+    let _ = fs::read_to_string("a").unwrap();
+    let _ = fs::read_to_string("a").expect("reading a");
+
+    let _ = Instant::now();
+    for _ in 0..2 {
+        let _ = Instant::now();
+        let _ = || Instant::now();
+    }
+
+    let v = vec![1, 2];
+    let _ = v.iter().count();
+    let _ = (&v).iter().count();
+    let _ = [1, 2].iter().count();
+
+    let _ = std::cmp::max(1, 1);
+    let _ = std::cmp::max(1, 2);
+
+    let _ = std::f64::consts::PI;
+}
+
+async fn unwrap(o: Option<i32>) -> i32 {
+    let _ = Some(1).unwrap();
+    o.unwrap()
+}
+
+fn sync_unwrap(o: Option<i32>) -> i32 {
+    o.unwrap()
+}
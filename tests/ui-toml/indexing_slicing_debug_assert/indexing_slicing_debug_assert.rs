@@ -0,0 +1,14 @@
+#![warn(clippy::indexing_slicing)]
+#![allow(clippy::no_effect)]
+
+fn checked(v: &[u8], i: usize) -> u8 {
+    debug_assert!(i < v.len());
+    v[i]
+}
+
+fn unchecked(v: &[u8], i: usize) -> u8 {
+    debug_assert!(i <= v.len());
+    v[i]
+}
+
+fn main() {}
@@ -0,0 +1,18 @@
+// edition:2018
+#![warn(clippy::large_futures)]
+
+async fn small() {}
+
+async fn below_threshold() {
+    let buffer = [0u8; 512];
+    small().await;
+    let _ = buffer.len();
+}
+
+async fn above_threshold() {
+    let buffer = [0u8; 2048];
+    small().await;
+    let _ = buffer.len();
+}
+
+fn main() {}
@@ -0,0 +1,25 @@
+#![warn(clippy::safety_critical)]
+
+fn main() {
+    let v = vec![1, 2, 3];
+    let _ = v[1];
+    let _ = v.first().unwrap();
+    let _ = v.len() + 1;
+}
+
+#[allow(clippy::safety_critical)]
+mod certified {
+    /// Enables `clippy::safety_critical` again, and `clippy::as_conversions`.
+    #[warn(clippy::certified)]
+    pub fn certified(v: &[u64]) -> u32 {
+        v[0] as u32
+    }
+}
+
+/// Enables all restriction lints.
+#[warn(clippy::everything)]
+mod blanket {}
+
+/// Enables all restriction lints through `clippy::everything`.
+#[warn(clippy::nested)]
+mod nested {}
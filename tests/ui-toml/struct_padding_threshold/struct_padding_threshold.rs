@@ -0,0 +1,18 @@
+#![warn(clippy::struct_padding)]
+#![allow(dead_code)]
+
+#[repr(C)]
+struct Small {
+    a: u8,
+    b: u32,
+    c: u8,
+}
+
+#[repr(C)]
+struct Ordered {
+    b: u32,
+    a: u8,
+    c: u8,
+}
+
+fn main() {}
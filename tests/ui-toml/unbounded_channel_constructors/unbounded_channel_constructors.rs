@@ -0,0 +1,19 @@
+#![warn(clippy::unbounded_channel)]
+
+mod queue {
+    use std::sync::mpsc::{self, Receiver, Sender, SyncSender};
+
+    pub fn unbounded<T>() -> (Sender<T>, Receiver<T>) {
+        mpsc::channel()
+    }
+
+    pub fn bounded<T>(capacity: usize) -> (SyncSender<T>, Receiver<T>) {
+        mpsc::sync_channel(capacity)
+    }
+}
+
+fn main() {
+    let _ = queue::unbounded::<u32>();
+    let _ = queue::bounded::<u32>(8);
+    let _ = std::sync::mpsc::channel::<u32>();
+}
@@ -0,0 +1,94 @@
+#![warn(clippy::panicking_atomic_ordering, clippy::compare_exchange_failure_ordering)]
+#![warn(clippy::relaxed_atomic_flag)]
+#![allow(invalid_atomic_ordering, unused)]
+
+use std::cell::UnsafeCell;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+const PUBLISH: Ordering = Ordering::Release;
+
+fn invalid(x: &AtomicUsize, flag: &AtomicBool) {
+    let ordering = Ordering::Release;
+    x.load(ordering);
+    x.store(1, ordering);
+    flag.load(PUBLISH);
+    let acquire = Ordering::Acquire;
+    flag.store(true, acquire);
+    let _ = x.compare_exchange(0, 1, Ordering::SeqCst, PUBLISH);
+    let _ = x.fetch_update(Ordering::SeqCst, ordering, |x| Some(x + 1));
+
+    // linted by rustc
+    x.load(Ordering::Release);
+
+    // valid
+    x.load(acquire);
+    x.store(1, ordering);
+}
+
+fn failure_stronger(x: &AtomicUsize) {
+    let _ = x.compare_exchange(0, 1, Ordering::Relaxed, Ordering::Acquire);
+    let _ = x.compare_exchange_weak(0, 1, Ordering::Release, Ordering::Acquire);
+    let _ = x.compare_exchange(0, 1, Ordering::Acquire, Ordering::SeqCst);
+    let _ = x.fetch_update(Ordering::Release, Ordering::Acquire, |x| Some(x + 1));
+
+    // valid
+    let _ = x.compare_exchange(0, 1, Ordering::AcqRel, Ordering::Acquire);
+    let _ = x.compare_exchange(0, 1, Ordering::Acquire, Ordering::Relaxed);
+    let _ = x.compare_exchange(0, 1, Ordering::SeqCst, Ordering::SeqCst);
+}
+
+struct Slot {
+    ready: AtomicBool,
+    closed: AtomicBool,
+    value: UnsafeCell<u32>,
+}
+
+impl Slot {
+    unsafe fn publish(&self, value: u32) {
+        *self.value.get() = value;
+        self.ready.store(true, Ordering::Relaxed);
+    }
+
+    fn read(&self) -> Option<u32> {
+        if self.ready.load(Ordering::Relaxed) {
+            Some(unsafe { *self.value.get() })
+        } else {
+            None
+        }
+    }
+
+    fn wait(&self) -> u32 {
+        while !self.ready.load(Ordering::Relaxed) {
+            std::hint::spin_loop();
+        }
+        // not guarded by the loop condition
+        unsafe { *self.value.get() }
+    }
+
+    fn sum(&self) -> u32 {
+        let mut sum = 0;
+        while self.ready.load(Ordering::Relaxed) {
+            sum += unsafe { *self.value.get() };
+        }
+        sum
+    }
+
+    fn read_open(&self) -> Option<u32> {
+        if !self.closed.load(Ordering::Relaxed) && self.ready.load(Ordering::Acquire) {
+            Some(unsafe { *self.value.get() })
+        } else {
+            None
+        }
+    }
+
+    fn close(&self) {
+        // not `Relaxed`
+        self.closed.store(true, Ordering::Release);
+    }
+
+    fn is_ready(&self) -> bool {
+        self.ready.load(Ordering::Relaxed)
+    }
+}
+
+fn main() {}
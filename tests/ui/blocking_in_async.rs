@@ -0,0 +1,68 @@
+// edition:2018
+#![warn(clippy::blocking_in_async)]
+
+use std::sync::{mpsc, Mutex};
+use std::time::Duration;
+
+async fn sleep_in_async_fn() {
+    std::thread::sleep(Duration::from_secs(1));
+}
+
+async fn fs_and_net() -> std::io::Result<()> {
+    let _ = std::fs::read_to_string("config.toml")?;
+    let _file = std::fs::File::open("data.bin")?;
+    let _stream = std::net::TcpStream::connect("127.0.0.1:8080")?;
+    Ok(())
+}
+
+async fn locks_and_channels(mutex: &Mutex<u32>, rx: mpsc::Receiver<u32>) {
+    let _guard = mutex.lock().unwrap();
+    let _ = rx.recv();
+}
+
+fn load_config() -> String {
+    std::fs::read_to_string("config.toml").unwrap()
+}
+
+fn nested_helper() -> String {
+    load_config()
+}
+
+fn not_blocking() -> u32 {
+    42
+}
+
+async fn helpers() {
+    let _ = load_config();
+    // Helpers are only followed one level deep.
+    let _ = nested_helper();
+    let _ = not_blocking();
+}
+
+fn blocks() {
+    let _fut = async {
+        std::thread::sleep(Duration::from_secs(1));
+    };
+    let _fut = async move {
+        let _ = load_config();
+    };
+}
+
+async fn no_lint() {
+    // Closures usually run somewhere else, e.g. in `spawn_blocking`.
+    let read = || std::fs::read("data.bin");
+    let _ = read;
+    // Not blocking.
+    let _ = std::fs::OpenOptions::new();
+    let _ = Mutex::new(1);
+
+    #[allow(clippy::blocking_in_async)]
+    std::thread::sleep(Duration::from_secs(1));
+}
+
+fn not_async() {
+    std::thread::sleep(Duration::from_secs(1));
+    let _ = load_config();
+}
+
+fn main() {}
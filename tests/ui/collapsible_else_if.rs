@@ -88,6 +88,14 @@ fn main() {
             println!("world!")
         }
     }
+
+    if x == "hello" {
+        print!("Hello ");
+    } else {
+        if y == "world" {
+            println!("world!")
+        } /* greet the world */
+    }
 }
 
 #[rustfmt::skip]
@@ -166,4 +166,11 @@ fn main() {
             println!("Hello world!");
         }
     }
+
+    // Comments outside of the kept code are moved in front of the suggestion
+    if x == "hello" {
+        if y == "world" {
+            println!("Hello world!");
+        } // greet the world
+    }
 }
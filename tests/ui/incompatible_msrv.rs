@@ -0,0 +1,26 @@
+#![feature(custom_inner_attributes)]
+#![warn(clippy::incompatible_msrv)]
+#![clippy::msrv = "1.59"]
+
+use std::thread;
+
+fn main() {
+    let _ = true.then_some(1);
+    let _ = 3u8.abs_diff(5);
+    let _ = thread::available_parallelism();
+    let _ = true.then(|| 1);
+    thread::scope(|_| {});
+    let Some(_x) = Some(1) else { return };
+    let _ = vec![1].len();
+}
+
+#[clippy::msrv = "1.62"]
+fn newer_msrv() {
+    let _ = true.then_some(1);
+    thread::scope(|_| {});
+}
+
+#[clippy::msrv = "1.65"]
+fn let_else() {
+    let Some(_x) = Some(1) else { return };
+}
@@ -0,0 +1,158 @@
+#![warn(clippy::inconsistent_impl_fields)]
+#![allow(clippy::derive_ord_xor_partial_ord, clippy::derived_hash_with_manual_eq)]
+
+use std::cmp::Ordering;
+use std::hash::{Hash, Hasher};
+
+struct User {
+    id: u32,
+    name: String,
+    age: u8,
+}
+
+impl PartialEq for User {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id && self.name == other.name
+    }
+}
+
+impl Eq for User {}
+
+impl Hash for User {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+        self.name.hash(state);
+        self.age.hash(state);
+    }
+}
+
+impl PartialOrd for User {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for User {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let Self { id, age, .. } = self;
+        id.cmp(&other.id).then(age.cmp(&other.age))
+    }
+}
+
+struct Version {
+    major: u32,
+    minor: u32,
+}
+
+impl PartialEq for Version {
+    fn eq(&self, other: &Self) -> bool {
+        self.major == other.major && self.minor == other.minor
+    }
+}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.major.partial_cmp(&other.major)
+    }
+}
+
+// Hashing fewer fields than `eq` compares is fine
+struct Key(u32, u32);
+
+impl PartialEq for Key {
+    fn eq(&self, other: &Self) -> bool {
+        match *self {
+            Key(a, b) => a == other.0 && b == other.1,
+        }
+    }
+}
+
+impl Hash for Key {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash(state);
+    }
+}
+
+impl PartialOrd for Key {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(Ord::cmp(self, other))
+    }
+}
+
+impl Ord for Key {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.0, self.1).cmp(&(other.0, other.1))
+    }
+}
+
+impl Eq for Key {}
+
+// `self` is used as a whole, the fields are unknown
+struct Opaque {
+    a: u32,
+    b: u32,
+}
+
+impl Opaque {
+    fn key(&self) -> u32 {
+        self.a
+    }
+}
+
+impl PartialEq for Opaque {
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key()
+    }
+}
+
+impl Hash for Opaque {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.b.hash(state);
+    }
+}
+
+// The fields read through `Deref` are those of `Inner`, which has more fields
+struct Inner {
+    a: u32,
+    b: u32,
+    c: u32,
+}
+
+struct Outer {
+    inner: Inner,
+}
+
+impl std::ops::Deref for Outer {
+    type Target = Inner;
+
+    fn deref(&self) -> &Inner {
+        &self.inner
+    }
+}
+
+impl PartialEq for Outer {
+    fn eq(&self, other: &Self) -> bool {
+        self.c == other.c
+    }
+}
+
+impl PartialOrd for Outer {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.inner.a.partial_cmp(&other.inner.a)
+    }
+}
+
+// Derived `PartialEq` compares every field
+#[derive(PartialEq)]
+struct Derived {
+    a: u32,
+    b: u32,
+}
+
+impl PartialOrd for Derived {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.a.partial_cmp(&other.a)
+    }
+}
+
+fn main() {}
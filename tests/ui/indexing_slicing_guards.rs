@@ -0,0 +1,156 @@
+#![warn(clippy::indexing_slicing)]
+#![warn(clippy::out_of_bounds_indexing)]
+#![allow(
+    clippy::no_effect,
+    clippy::unnecessary_operation,
+    clippy::len_zero,
+    clippy::needless_range_loop,
+    unused
+)]
+
+fn checked_by_if(v: &[u8], i: usize) {
+    if i < v.len() {
+        v[i];
+    }
+    if v.len() > i {
+        &v[..=i];
+    }
+    if i <= v.len() {
+        &v[..i];
+        &v[i..];
+        v[i]; // the index may be equal to the length
+    }
+    if i >= v.len() {
+        return;
+    }
+    v[i];
+}
+
+fn checked_in_condition(v: &[u8], i: usize) -> bool {
+    i < v.len() && v[i] == 0 || i >= v.len() || v[i] == 1
+}
+
+fn checked_by_assert(v: &Vec<u8>, i: usize, j: usize) {
+    assert!(i < v.len() && j < v.len());
+    v[i];
+    v[j];
+    debug_assert!(i + 1 < v.len());
+    v[i + 1]; // not checked in release builds
+}
+
+fn checked_by_local_len(v: &mut Vec<u8>, i: usize) {
+    let len = v.len();
+    if i < len {
+        v[i];
+    }
+    v.clear();
+    if i < len {
+        v[i]; // the length changed
+    }
+}
+
+fn checked_by_loop(v: &mut Vec<u8>, s: &mut [u8]) {
+    for i in 0..v.len() {
+        v[i] += 1;
+        v.swap(0, i);
+    }
+    for i in (1..s.len()).rev() {
+        s[i] = s[i - 1]; // only `i` is checked
+    }
+    for i in 0..=v.len() {
+        v[i]; // the range includes the length
+    }
+    for i in 0..v.len() {
+        v[i];
+        v.pop(); // the length changes in the loop
+    }
+    let mut i = 0;
+    while i < v.len() {
+        v[i];
+        i += 1;
+    }
+}
+
+fn index_changed(v: &[u8], mut i: usize) {
+    if i < v.len() {
+        i += 1;
+        v[i];
+    }
+}
+
+fn constant_indexes(v: &[u8], w: &Vec<u8>) {
+    if v.len() >= 3 {
+        v[0];
+        v[2];
+        &v[..3];
+        v[3]; // doesn't prove it
+    }
+    if !v.is_empty() {
+        v[0];
+    }
+    if v.len() != 0 {
+        v[0];
+    }
+    if w.len() == 2 {
+        w[1];
+        w[2]; // out of bounds
+        &w[..3]; // out of bounds
+    }
+    if w.len() < 2 {
+        w[1]; // out of bounds
+    }
+    if w.is_empty() {
+        w[0]; // out of bounds
+    }
+}
+
+struct S {
+    items: Vec<u32>,
+}
+
+impl S {
+    fn get(&self, i: usize) -> u32 {
+        if i < self.items.len() { self.items[i] } else { 0 }
+    }
+
+    fn take(&mut self, i: usize) -> u32 {
+        if i < self.items.len() {
+            self.reset();
+            self.items[i] // `reset` may shrink `items`
+        } else {
+            0
+        }
+    }
+
+    fn reset(&mut self) {
+        self.items.clear();
+    }
+}
+
+/// `len` counts the set bits, it isn't the bound of the indexes
+struct Bits(Vec<bool>);
+
+impl Bits {
+    fn len(&self) -> usize {
+        self.0.iter().filter(|bit| **bit).count()
+    }
+}
+
+impl std::ops::Index<usize> for Bits {
+    type Output = bool;
+
+    fn index(&self, i: usize) -> &bool {
+        self.0.get(i).unwrap_or(&false)
+    }
+}
+
+fn checked_by_other_len(bits: &Bits, i: usize) {
+    if i < bits.len() {
+        bits[i];
+    }
+    if i >= bits.len() {
+        bits[i]; // `len` doesn't prove that this is out of bounds
+    }
+}
+
+fn main() {}
@@ -0,0 +1,72 @@
+// edition:2018
+#![warn(clippy::large_futures)]
+
+use std::future::Future;
+
+fn spawn<F: Future>(_future: F) {}
+
+struct Runtime;
+
+impl Runtime {
+    fn block_on<F: Future>(&self, _future: F) {}
+}
+
+async fn small() {}
+
+async fn holds_buffer() {
+    let buffer = [0u8; 32 * 1024];
+    small().await;
+    let _ = buffer.len();
+}
+
+async fn awaits_large_future() {
+    holds_buffer().await;
+}
+
+async fn boxed() {
+    let buffer = Box::new([0u8; 32 * 1024]);
+    small().await;
+    let _ = buffer.len();
+}
+
+async fn held_until_end_of_scope() {
+    let buffer = [0u8; 32 * 1024];
+    let _ = buffer.len();
+    small().await;
+}
+
+async fn not_held() {
+    {
+        let buffer = [0u8; 32 * 1024];
+        let _ = buffer.len();
+    }
+    small().await;
+}
+
+async fn same_type_not_held() {
+    let moved = [0u8; 32 * 1024];
+    drop(moved);
+    let held = [0u8; 32 * 1024];
+    small().await;
+    let _ = held.len();
+}
+
+fn main() {
+    spawn(async {
+        let buffer = [0u8; 20 * 1024];
+        small().await;
+        let _ = buffer.len();
+    });
+    Runtime.block_on(async {
+        let buffer = [0u8; 20 * 1024];
+        small().await;
+        let _ = buffer.len();
+    });
+    spawn(Box::pin(holds_buffer()));
+    // Not spawned.
+    let _future = async {
+        let buffer = [0u8; 20 * 1024];
+        small().await;
+        let _ = buffer.len();
+    };
+}
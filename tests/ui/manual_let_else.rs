@@ -135,6 +135,13 @@ fn fire() {
         };
     }
     create_binding_if_some!(w, g());
+
+    // Comments outside of the kept code are moved in front of the suggestion
+    let v = if let Some(v) = g() {
+        v
+    } else /* nothing to do */ {
+        return;
+    };
 }
 
 fn not_fire() {
@@ -125,6 +125,13 @@ fn main() {
         // =>
         _ => println!("Not an array index start"),
     }
+
+    // Lint
+    let p = (1, 2);
+    match p {
+        // swap the coordinates
+        (x, y) => println!("{} {}", y, x),
+    }
 }
 
 #[allow(dead_code)]
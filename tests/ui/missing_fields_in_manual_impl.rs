@@ -0,0 +1,135 @@
+#![warn(clippy::missing_fields_in_manual_impl)]
+#![allow(clippy::expl_impl_clone_on_copy, clippy::derivable_impls)]
+
+use std::cell::Cell;
+use std::fmt;
+
+struct Connection {
+    host: String,
+    port: u16,
+    retries: u32,
+    _guard: (),
+    #[clippy::skip_in_manual_impls]
+    cache: Cell<Option<u32>>,
+}
+
+impl fmt::Debug for Connection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Connection").field("host", &self.host).finish()
+    }
+}
+
+impl Clone for Connection {
+    fn clone(&self) -> Self {
+        Self {
+            host: self.host.clone(),
+            port: self.port,
+            retries: Default::default(),
+            _guard: (),
+            cache: Cell::new(None),
+        }
+    }
+}
+
+// The base sets the other fields
+impl Default for Connection {
+    fn default() -> Self {
+        Self {
+            host: String::new(),
+            ..Self::with_port(80)
+        }
+    }
+}
+
+impl Connection {
+    fn with_port(port: u16) -> Self {
+        Self {
+            host: String::new(),
+            port,
+            retries: 0,
+            _guard: (),
+            cache: Cell::new(None),
+        }
+    }
+}
+
+// Every field is mentioned
+struct Point(i32, i32);
+
+impl fmt::Debug for Point {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Point(x, y) = self;
+        write!(f, "({x}, {y})")
+    }
+}
+
+impl Clone for Point {
+    fn clone(&self) -> Self {
+        Point(self.0, self.1)
+    }
+}
+
+impl Default for Point {
+    fn default() -> Self {
+        Point(0, 0)
+    }
+}
+
+// Already says that some fields are not shown
+struct Secret {
+    name: String,
+    key: Vec<u8>,
+}
+
+impl fmt::Debug for Secret {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Secret").field("name", &self.name).finish_non_exhaustive()
+    }
+}
+
+// `self` is used as a whole
+#[derive(Copy)]
+struct Pair {
+    a: u8,
+    b: u8,
+}
+
+impl Clone for Pair {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl fmt::Debug for Pair {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.sum())
+    }
+}
+
+impl Pair {
+    fn sum(&self) -> u8 {
+        self.a + self.b
+    }
+}
+
+// Neither branch shows `verbose`
+struct Report {
+    title: String,
+    body: String,
+    verbose: bool,
+}
+
+impl fmt::Debug for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            f.debug_struct("Report")
+                .field("title", &self.title)
+                .field("body", &self.body)
+                .finish()
+        } else {
+            f.debug_struct("Report").field("title", &self.title).finish()
+        }
+    }
+}
+
+fn main() {}
@@ -81,6 +81,20 @@ fn main() {
         panic!();
     }
 
+    let h: usize;
+    if true {
+        h = /* the answer */ 42;
+    } else {
+        panic!();
+    }
+
+    let i: usize /* the comments of the removed local are kept */;
+    if true {
+        i = 5;
+    } else {
+        panic!();
+    }
+
     // Drop order only matters if both are significant
     let x;
     let y = SignificantDrop;
@@ -307,4 +307,13 @@ fn issue10051() -> Result<String, String> {
     }
 }
 
+fn comment_in_return() -> u32 {
+    return /* the answer */ 42;
+}
+
+fn line_comment_in_return() -> u32 {
+    return // the answer
+        42;
+}
+
 fn main() {}
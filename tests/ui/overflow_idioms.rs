@@ -0,0 +1,65 @@
+#![warn(clippy::overflowing_midpoint, clippy::mul_div_overflow, clippy::unchecked_len_sub)]
+#![allow(clippy::no_effect, clippy::unnecessary_operation, clippy::manual_strip, unused)]
+
+fn midpoint(lo: usize, hi: usize, a: i32, b: i32, c: i128, small: u8) {
+    let _ = (lo + hi) / 2;
+    let _ = (lo + hi) >> 1;
+    // no suggestion, `b - a` overflows as well for `i32::MAX` and `-1`
+    let _ = (a + b) / 2;
+    let _ = (c + c) >> 1;
+
+    // no overflow
+    let _ = (u16::from(small) + u16::from(small)) / 2;
+    let _ = lo + (hi - lo) / 2;
+    let _ = (lo + hi) / 3;
+    let _ = (lo * hi + lo) / 2; // not a midpoint
+}
+
+fn mul_div(value: u64, num: u64, den: u64, x: u32, big: u128) {
+    let _ = value * num / den;
+    let _ = x * x / 7;
+    let _ = big * big / 2;
+
+    // no overflow
+    let _ = u64::from(x) * u64::from(x) / 7;
+    let _ = (value / den) * num;
+}
+
+fn len_sub(v: &[u8], w: &mut Vec<u8>, s: &str, k: u8) {
+    let _ = v.len() - 1;
+    let n = v.len();
+    let _ = n - 1;
+    if !v.is_empty() {
+        let _ = v.len() - 1;
+    }
+    if v.len() > 2 {
+        let _ = v.len() - 1;
+    }
+    if w.is_empty() {
+        return;
+    }
+    let _ = w.len() - 1;
+    w.clear();
+    let _ = w.len() - 1; // `w` may be empty again
+    let _ = v.len() - 2; // not linted, only `- 1` is
+    let _ = v[v.len() - 1]; // panics anyway
+    let _ = &v[1..v.len() - 1]; // panics anyway
+    let _ = &v[(usize::from(k) + 2)..(v.len() - 1 - usize::from(k))]; // panics anyway
+    let _ = &v[..v.len() - 1];
+    if s.ends_with('_') {
+        let _ = &s[..s.len() - 1];
+    }
+    if let Some(last) = v.iter().last() {
+        let _ = v.len() - 1;
+    }
+    if let Some(first) = w.first() {
+        let _ = w.len() - 1;
+    }
+    if let Some(last) = v.iter().filter(|&&b| b > k).last() {
+        let _ = v.len() - 1; // may be empty
+    }
+    let _ = v.get(v.len() - 1); // linted by `get_last_with_len`
+    let _ = v.get(n - 1);
+}
+
+fn main() {}
@@ -0,0 +1,118 @@
+#![feature(c_unwind)]
+#![warn(clippy::panic_in_extern_fn)]
+#![allow(clippy::no_effect, clippy::unnecessary_operation, improper_ctypes_definitions, unused)]
+
+use std::ffi::c_void;
+use std::panic::catch_unwind;
+
+extern "C" fn unwraps(x: Option<u32>, y: Result<u32, ()>) -> u32 {
+    x.unwrap() + y.expect("no y")
+}
+
+extern "C" fn panics(x: u32) {
+    if x == 0 {
+        panic!("zero");
+    }
+    assert!(x < 10);
+    unreachable!();
+}
+
+extern "C" fn indexes(v: &[u8], i: usize) -> u8 {
+    if i < v.len() {
+        return v[i];
+    }
+    v[i + 1]
+}
+
+extern "C" fn arithmetic(a: u32, b: u32, c: u8) -> u32 {
+    let x = u32::from(c) + 1;
+    let y = a * b;
+    x / b + y
+}
+
+extern "C" fn in_closure(v: &[u32]) -> u32 {
+    v.iter().map(|x| x.checked_add(1).unwrap()).sum()
+}
+
+extern "C" fn caught(v: &[u8]) -> u8 {
+    catch_unwind(|| v[0]).unwrap_or(0)
+}
+
+extern "C" fn partly_caught(v: &[u8]) -> u8 {
+    let x = catch_unwind(|| v[0]).unwrap_or(0);
+    x + v[1]
+}
+
+extern "C" fn stored_and_caught(v: &[u8]) -> u8 {
+    let f = || v[0];
+    catch_unwind(f).unwrap_or(0)
+}
+
+extern "C" fn stored_and_called(v: &[u8]) -> u8 {
+    let f = || v[0];
+    let _ = catch_unwind(f);
+    f()
+}
+
+struct S;
+
+impl S {
+    extern "C" fn method(&self, x: Option<u8>) -> u8 {
+        x.unwrap()
+    }
+}
+
+extern "C-unwind" fn may_unwind(x: Option<u8>) -> u8 {
+    x.unwrap()
+}
+
+fn rust_abi(x: Option<u8>) -> u8 {
+    x.unwrap()
+}
+
+extern "C" fn no_panic(v: &[u8], x: Option<u8>) -> u8 {
+    x.unwrap_or(0).wrapping_add(v.get(3).copied().unwrap_or_default())
+}
+
+// Closures passed as C callbacks
+extern "C" fn trampoline<F: FnMut(usize) -> u8>(data: *mut c_void, i: usize) -> u8 {
+    let f = unsafe { &mut *data.cast::<F>() };
+    f(i)
+}
+
+fn get_trampoline<F: FnMut(usize) -> u8>(_: &F) -> extern "C" fn(*mut c_void, usize) -> u8 {
+    trampoline::<F>
+}
+
+fn register(cb: extern "C" fn(*mut c_void, usize) -> u8, data: *mut c_void) -> u8 {
+    cb(data, 0)
+}
+
+extern "C" fn typed_trampoline<F: FnMut(usize) -> u8>(f: *mut F, i: usize) -> u8 {
+    unsafe { (*f)(i) }
+}
+
+fn register_typed<T>(cb: extern "C" fn(*mut T, usize) -> u8, data: *mut T) -> u8 {
+    cb(data, 0)
+}
+
+fn call<F: FnMut(usize) -> u8>(mut f: F) -> u8 {
+    f(0)
+}
+
+fn callbacks(v: &[u8]) {
+    let mut f = |i: usize| v[i];
+    let cb = get_trampoline(&f);
+    register(cb, &mut f as *mut _ as *mut c_void);
+
+    let mut g = |i: usize| v.get(i).copied().unwrap();
+    register_typed(typed_trampoline, &mut g);
+
+    let mut caught = |i: usize| catch_unwind(|| v[i]).unwrap_or(0);
+    register_typed(typed_trampoline, &mut caught);
+
+    // Not called by an `extern "C"` function
+    call(|i: usize| v[i]);
+}
+
+fn main() {}
@@ -0,0 +1,70 @@
+#![warn(clippy::struct_padding)]
+#![allow(dead_code)]
+
+#[repr(C)]
+struct Packet {
+    kind: u8,
+    id: u64,
+    flags: u8,
+}
+
+// The fields are named by their position
+#[repr(C)]
+struct Tuple(u16, u64, u16, u32);
+
+#[repr(C, align(32))]
+struct Aligned {
+    a: u8,
+    b: u32,
+    c: u8,
+    d: u32,
+    e: u8,
+}
+
+// Only 4 bytes can be saved
+#[repr(C)]
+struct Small {
+    a: u8,
+    b: u32,
+    c: u8,
+}
+
+// Already ordered by alignment
+#[repr(C)]
+struct Ordered {
+    id: u64,
+    len: u32,
+    kind: u8,
+}
+
+#[repr(C, packed)]
+struct Packed {
+    kind: u8,
+    id: u64,
+    flags: u8,
+}
+
+// The compiler reorders the fields
+struct Rust {
+    kind: u8,
+    id: u64,
+    flags: u8,
+}
+
+// The unsized field has to stay last
+#[repr(C)]
+struct Unsized {
+    kind: u8,
+    id: u64,
+    flags: u8,
+    data: [u32],
+}
+
+#[repr(C)]
+struct Generic<T> {
+    kind: u8,
+    value: T,
+    flags: u8,
+}
+
+fn main() {}
@@ -0,0 +1,41 @@
+// run-rustfix
+#![warn(clippy::unbounded_channel, clippy::dropped_join_handle, clippy::join_unwrap)]
+#![allow(unused)]
+
+use std::sync::mpsc;
+use std::thread;
+
+fn channels() {
+    let (tx, rx) = mpsc::channel::<u32>();
+    let (tx, rx) = std::sync::mpsc::channel::<u32>();
+    let (tx, rx) = mpsc::sync_channel::<u32>(16);
+}
+
+fn dropped_handles() {
+    thread::spawn(|| {});
+    let _ = thread::spawn(|| {});
+    let handle = thread::spawn(|| {});
+    let _ = handle; // doesn't drop the handle
+
+    let _handle = thread::spawn(|| {});
+    drop(thread::spawn(|| {}));
+    thread::scope(|s| {
+        s.spawn(|| {});
+        let _ = s.spawn(|| {});
+    });
+}
+
+fn joins() {
+    let one = thread::spawn(|| 1).join().unwrap();
+    thread::scope(|s| {
+        let two = s.spawn(|| 2).join().unwrap();
+    });
+
+    let three = thread::spawn(|| 3)
+        .join()
+        .unwrap_or_else(|payload| std::panic::resume_unwind(payload));
+    let four: Result<u32, ()> = Ok(4);
+    let four = four.unwrap();
+}
+
+fn main() {}
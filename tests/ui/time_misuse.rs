@@ -0,0 +1,117 @@
+#![warn(
+    clippy::unchecked_system_time_difference,
+    clippy::serialized_instant,
+    clippy::overflowing_duration_constructor,
+    clippy::deserialized_instant_comparison
+)]
+#![allow(unused, clippy::manual_instant_elapsed)]
+
+extern crate serde;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+mod instant_serde {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use std::time::{Duration, Instant};
+
+    pub fn serialize<S: Serializer>(instant: &Instant, serializer: S) -> Result<S::Ok, S::Error> {
+        instant.elapsed().serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Instant, D::Error> {
+        Duration::deserialize(deserializer).map(|elapsed| Instant::now() - elapsed)
+    }
+}
+
+fn system_time(start: SystemTime) {
+    let _ = SystemTime::now().duration_since(start).unwrap();
+    let _ = start.elapsed().expect("clock went backwards");
+    let start_ref = &start;
+    let _ = start_ref.elapsed().unwrap();
+
+    let _ = SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
+    let _ = SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).unwrap();
+    let _ = SystemTime::now().duration_since(start).unwrap_or_default();
+    let _ = Instant::now().duration_since(Instant::now());
+}
+
+// The `Instant`s are skipped, or converted relative to `Instant::now()`
+#[derive(Serialize, Deserialize)]
+struct Session {
+    #[serde(with = "instant_serde")]
+    last_seen: Instant,
+    #[serde(skip, default = "Instant::now")]
+    loaded: Instant,
+    id: u64,
+}
+
+#[derive(Serialize)]
+enum Event {
+    Started(#[serde(skip)] Instant),
+    Stopped {
+        #[serde(skip_serializing)]
+        at: Instant,
+        after: Duration,
+    },
+}
+
+#[derive(Deserialize)]
+struct History {
+    #[serde(skip)]
+    events: Vec<Instant>,
+    #[serde(skip_deserializing)]
+    first: Option<Instant>,
+}
+
+// The conversion of the `Instant` is unknown
+struct Snapshot {
+    taken: Instant,
+}
+
+impl Serialize for Snapshot {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        instant_serde::serialize(&self.taken, serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Snapshot {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        instant_serde::deserialize(deserializer).map(|taken| Snapshot { taken })
+    }
+}
+
+struct NotSerialized {
+    at: Instant,
+}
+
+fn compare(snapshot: &Snapshot, session: &Session, local: &NotSerialized) {
+    let _ = Instant::now() - snapshot.taken;
+    let _ = Instant::now() > snapshot.taken;
+    let _ = snapshot.taken.elapsed();
+    let _ = Instant::now().duration_since(snapshot.taken);
+    let _ = Instant::now().cmp(&snapshot.taken);
+
+    let _ = Instant::now() - session.last_seen;
+    let _ = session.loaded.elapsed();
+    let _ = Instant::now() - local.at;
+    let _ = local.at.elapsed();
+    let _ = snapshot.taken.duration_since(local.at);
+}
+
+const MINUTE: u64 = 60;
+
+fn durations(hours: u64, minutes: u64, count: u32, a: u64, b: u64) {
+    let _ = Duration::from_secs(hours * 60 * 60);
+    let _ = Duration::from_millis(minutes * MINUTE * 1000);
+    let _ = Duration::from_secs(a * b * 60);
+    let _ = Duration::from_nanos((a + b) * 1_000);
+
+    let _ = Duration::from_secs(u64::from(count) * 3600);
+    let _ = Duration::from_secs(60 * 60);
+    let _ = Duration::from_secs(a * b);
+    let _ = Duration::from_secs(hours);
+    let _ = Duration::from_secs(hours.saturating_mul(3600));
+}
+
+fn main() {}
@@ -0,0 +1,73 @@
+#![warn(clippy::unaddressed_safety_preconditions)]
+#![allow(clippy::undocumented_unsafe_blocks, clippy::missing_safety_doc)]
+
+/// Reads the value behind `ptr`.
+///
+/// # Safety
+///
+/// - `ptr` must be valid for reads.
+/// - `ptr` must be properly aligned.
+/// - The value behind `ptr` must be initialized.
+unsafe fn read(ptr: *const u32) -> u32 {
+    *ptr
+}
+
+/// # Safety
+///
+/// The caller must hold the lock.
+unsafe fn no_list() {}
+
+/// # Safety
+///
+/// * The buffer must be zeroed.
+///   * Nested items don't count as separate preconditions.
+/// * Interrupts must be disabled.
+unsafe fn words() {}
+
+unsafe fn undocumented() {}
+
+struct S;
+
+impl S {
+    /// # Safety
+    ///
+    /// 1. `self` must not be shared with another thread.
+    unsafe fn method(&self) {}
+}
+
+fn main() {
+    let x = 0_u32;
+    let ptr = &x as *const u32;
+
+    // SAFETY: `ptr` comes from a reference, so it is valid for reads.
+    let _ = unsafe { read(ptr) };
+
+    // SAFETY: `ptr` comes from a reference, so it is valid for reads (1),
+    // aligned (2) and initialized (3).
+    let _ = unsafe { read(ptr) };
+
+    // SAFETY: the pointer comes from a reference, so it's initialized, aligned and valid.
+    let _ = unsafe { read(ptr) };
+
+    // SAFETY: we hold the lock.
+    unsafe { no_list() };
+
+    // SAFETY: the buffer was zeroed above.
+    unsafe { words() };
+
+    // SAFETY: the buffer was zeroed above and interrupts are disabled.
+    unsafe { words() };
+
+    // SAFETY: nothing to uphold.
+    unsafe { undocumented() };
+
+    // SAFETY: S is never sent anywhere.
+    unsafe { S.method() };
+
+    // SAFETY: `self` is local to this thread.
+    unsafe { S.method() };
+
+    #[allow(clippy::unaddressed_safety_preconditions)]
+    // SAFETY: `ptr` is valid for reads.
+    let _ = unsafe { read(ptr) };
+}
@@ -0,0 +1,80 @@
+// run-rustfix
+#![feature(lint_reasons)]
+#![warn(clippy::unused_clippy_allow)]
+#![allow(clippy::needless_return)]
+#![allow(dead_code, clippy::missing_panics_doc)]
+
+#[allow(clippy::needless_return)]
+fn used() -> i32 {
+    return 1;
+}
+
+fn used_by_crate_attribute() -> i32 {
+    return 1;
+}
+
+#[allow(clippy::needless_return)]
+fn unused() -> i32 {
+    1
+}
+
+#[allow(clippy::needless_return, reason = "no longer needed")]
+fn unused_with_reason() -> i32 {
+    1
+}
+
+#[allow(unused_variables, clippy::needless_return, clippy::let_and_return)]
+fn partly_used() -> i32 {
+    let x = 1;
+    x
+}
+
+#[allow(clippy::needless_return, /* keep me */ clippy::let_and_return)]
+fn unused_with_comment() -> i32 {
+    let x = 1;
+    x
+}
+
+fn statement() -> i32 {
+    #[allow(clippy::needless_return)]
+    let x = 1;
+    x * 2
+}
+
+// `missing_trait_methods` checks if it's allowed on every item, which counts as using the `allow`
+#[allow(clippy::missing_trait_methods)]
+fn only_checked() {}
+
+#[cfg_attr(all(), allow(dead_code, clippy::needless_return))]
+fn unused_in_cfg_attr() -> i32 {
+    1
+}
+
+#[allow(clippy::unused_clippy_allow, clippy::needless_return)]
+fn allowed() -> i32 {
+    1
+}
+
+#[allow(clippy::all, clippy::pedantic)]
+fn groups() {}
+
+macro_rules! allow_in_macro {
+    () => {
+        #[allow(clippy::needless_return)]
+        fn from_macro() -> i32 {
+            1
+        }
+    };
+}
+
+allow_in_macro!();
+
+// The lint checks whether it's allowed before looking for a safety comment
+#[warn(clippy::undocumented_unsafe_blocks)]
+fn allowed_before_linting(p: *const u8) -> u8 {
+    #[allow(clippy::undocumented_unsafe_blocks)]
+    let x = unsafe { *p };
+    x + 1
+}
+
+fn main() {}
@@ -0,0 +1,76 @@
+#![warn(clippy::cast_possible_truncation, clippy::cast_possible_wrap, clippy::cast_sign_loss)]
+#![allow(clippy::manual_clamp, clippy::manual_range_contains)]
+
+fn masks_and_remainders(x: u32, y: i64) {
+    let _ = (x % 256) as u8;
+    let _ = (x & 0xff) as u8;
+    let _ = (x >> 24) as u8;
+    let _ = (y.rem_euclid(100)) as u8;
+    let _ = (x / 16_777_216) as u8;
+    // The remainder of a negative value is negative
+    let _ = (y % 100) as u8;
+    let _ = (x % 257) as u8;
+    let _ = (x & 0x1ff) as u8;
+}
+
+fn min_and_clamp(x: u64, y: i32) {
+    let _ = x.min(255) as u8;
+    let _ = std::cmp::min(x, 1000) as u16;
+    let _ = y.clamp(0, 100) as u8;
+    let _ = y.max(0) as u32;
+    let _ = y.clamp(-1, 100) as u8;
+    let _ = x.max(255) as u8;
+}
+
+fn guards(n: u32, i: i32) {
+    if n < 100 {
+        let _ = n as u8;
+    } else {
+        let _ = n as u8;
+    }
+    if n > 300 {
+        return;
+    }
+    let _ = n as u16;
+    let _ = n as u8;
+    if (0..10).contains(&i) {
+        let _ = i as u8;
+    }
+    if i >= 0 && i <= 127 {
+        let _ = i as u8;
+        let _ = i as i8;
+    }
+    if !(i < 0 || i > 255) {
+        let _ = i as u8;
+    }
+    let m = n;
+    assert!(m <= 1000);
+    let _ = m as u16;
+}
+
+fn loops(v: &[u8]) {
+    for i in 0..10usize {
+        let _ = i as u8;
+    }
+    for i in (0..=255u32).rev() {
+        let _ = i as u8;
+    }
+    let mut sum = 0u8;
+    for &b in v {
+        let _ = b as i8;
+        sum = sum.wrapping_add(b);
+    }
+    let _ = sum;
+}
+
+fn conversions(b: u8, c: bool, x: u16) {
+    let _ = u32::from(b) as u8;
+    let _ = u32::from(c) as u8;
+    let wide: u64 = x.into();
+    let _ = wide as u16;
+    let _ = wide as u8;
+    let _ = (u32::from(b) + u32::from(b)) as u16;
+    let _ = (u32::from(b) * 2) as u8;
+}
+
+fn main() {}
@@ -0,0 +1,41 @@
+#![feature(once_cell)]
+
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+use test_utils::{CARGO_CLIPPY_PATH, IS_RUSTC_TEST_SUITE};
+
+mod test_utils;
+
+#[test]
+fn test_unsafe_report() {
+    if IS_RUSTC_TEST_SUITE {
+        return;
+    }
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let target_dir = root.join("target").join("unsafe_report_test");
+    let cwd = root.join("tests/unsafe_report_test");
+    let report_dir = target_dir.join("debug").join("clippy-unsafe-report");
+
+    for (flag, report, expected) in [
+        ("--unsafe-report", "unsafe_report_test-lib.json", "expected.json"),
+        ("--unsafe-report=markdown", "unsafe_report_test-lib.md", "expected.md"),
+    ] {
+        let output = Command::new(&*CARGO_CLIPPY_PATH)
+            .current_dir(&cwd)
+            .env("CARGO_INCREMENTAL", "0")
+            .env("CARGO_TARGET_DIR", &target_dir)
+            .arg("clippy")
+            .arg(flag)
+            .output()
+            .unwrap();
+        println!("status: {}", output.status);
+        println!("stdout: {}", String::from_utf8_lossy(&output.stdout));
+        println!("stderr: {}", String::from_utf8_lossy(&output.stderr));
+        assert!(output.status.success());
+
+        let report = fs::read_to_string(report_dir.join(report)).unwrap();
+        let expected = fs::read_to_string(cwd.join(expected)).unwrap();
+        assert_eq!(report, expected, "unexpected report for `{flag}`");
+    }
+}
@@ -0,0 +1,24 @@
+pub struct Wrapper(*const u8);
+
+// SAFETY: the pointer is never written through
+unsafe impl Send for Wrapper {}
+
+/// Reads the first byte.
+///
+/// # Safety
+///
+/// `ptr` must be valid for reads.
+pub unsafe fn first(ptr: *const u8) -> u8 {
+    *ptr
+}
+
+pub fn read(wrapper: &Wrapper) -> u8 {
+    // SAFETY: `Wrapper` always holds a valid pointer
+    unsafe { first(wrapper.0) }
+}
+
+pub fn undocumented(value: &u8) -> u8 {
+    let ptr: *const u8 = value;
+    let first = unsafe { *ptr };
+    first + unsafe { *ptr }
+}
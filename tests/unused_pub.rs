@@ -0,0 +1,38 @@
+#![feature(once_cell)]
+
+use std::path::PathBuf;
+use std::process::Command;
+use test_utils::{CARGO_CLIPPY_PATH, IS_RUSTC_TEST_SUITE};
+
+mod test_utils;
+
+#[test]
+fn test_unused_pub() {
+    if IS_RUSTC_TEST_SUITE {
+        return;
+    }
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let target_dir = root.join("target").join("unused_pub_test");
+    let cwd = root.join("tests/ui-cargo/unused_pub_items/workspace");
+
+    let output = Command::new(&*CARGO_CLIPPY_PATH)
+        .current_dir(&cwd)
+        .env("CARGO_INCREMENTAL", "0")
+        .env("CARGO_TARGET_DIR", &target_dir)
+        .arg("clippy")
+        .arg("--unused-pub")
+        .output()
+        .unwrap();
+    println!("status: {}", output.status);
+    println!("stdout: {}", String::from_utf8_lossy(&output.stdout));
+    println!("stderr: {}", String::from_utf8_lossy(&output.stderr));
+    assert!(output.status.success());
+
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("`unused` is exported, but no other crate of the workspace uses it"));
+    // Used by `app`, directly and through a re-export
+    assert!(!stderr.contains("`used` is exported"));
+    assert!(!stderr.contains("`inner::reexported` is exported"));
+    // `published_lib` is listed in `published-crates`
+    assert!(!stderr.contains("`api` is exported"));
+}